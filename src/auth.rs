@@ -5,7 +5,8 @@ use anyhow::{Context, Result};
 use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::auth::{LocalSigner, Normal, Signer as _};
 use polymarket_client_sdk::clob::types::SignatureType;
-use polymarket_client_sdk::{POLYGON, clob};
+use polymarket_client_sdk::types::Address;
+use polymarket_client_sdk::{POLYGON, clob, derive_proxy_wallet, derive_safe_wallet};
 
 use crate::config;
 
@@ -51,6 +52,17 @@ pub async fn authenticate_with_signer(
         .context("Failed to authenticate with Polymarket CLOB")
 }
 
+/// Address that holds funds and positions for the configured signature type:
+/// the proxy or Safe derived from `eoa`, or `eoa` itself.
+pub fn wallet_address(eoa: Address, signature_type_flag: Option<&str>) -> Address {
+    let derived = match parse_signature_type(&config::resolve_signature_type(signature_type_flag)) {
+        SignatureType::Proxy => derive_proxy_wallet(eoa, POLYGON),
+        SignatureType::GnosisSafe => derive_safe_wallet(eoa, POLYGON),
+        _ => None,
+    };
+    derived.unwrap_or(eoa)
+}
+
 pub async fn create_readonly_provider() -> Result<impl alloy::providers::Provider + Clone> {
     ProviderBuilder::new()
         .connect(RPC_URL)
@@ -1,33 +1,74 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
-use anyhow::Result;
-use chrono::NaiveDate;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use clap::{Args, Subcommand};
+use futures::{StreamExt as _, stream};
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::Signer as _;
+use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::clob;
 use polymarket_client_sdk::clob::types::{
-    Amount, AssetType, Interval, OrderType, Side, TimeRange,
+    Amount, AssetType, Interval, OrderStatusType, OrderType, Side, SignedOrder, TimeRange,
+    TraderSide,
     request::{
         BalanceAllowanceRequest, CancelMarketOrderRequest, DeleteNotificationsRequest,
         LastTradePriceRequest, MidpointRequest, OrderBookSummaryRequest, OrdersRequest,
         PriceHistoryRequest, PriceRequest, SpreadRequest, TradesRequest, UserRewardsEarningRequest,
     },
+    response::{
+        BalanceAllowanceResponse, CancelOrdersResponse, MarketRewardsConfig, OpenOrderResponse,
+        OrderBookSummaryResponse, OrderSummary, Page, PostOrderResponse, TradeResponse,
+    },
 };
-use polymarket_client_sdk::types::{Decimal, U256};
-
-use super::parse_condition_id;
-use crate::auth;
-use crate::output::OutputFormat;
+use polymarket_client_sdk::clob::ws::types::response::{
+    BookUpdate, LastTradePrice, OrderBookLevel, PriceChange, PriceChangeBatchEntry,
+};
+use polymarket_client_sdk::data::{
+    self,
+    types::{request::PositionsRequest, response::Position},
+};
+use polymarket_client_sdk::gamma::{
+    self,
+    types::request::{EventBySlugRequest, EventsRequest, MarketByIdRequest, MarketBySlugRequest},
+};
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+use rust_decimal::RoundingStrategy;
+use serde_json::json;
+
+use super::scan::walk_sets;
+use super::{AllPages, ExitStatus, is_numeric_id, parse_condition_id, parse_duration, parse_edge};
+use crate::interrupt;
+use crate::journal::{self, Action, Entry};
 use crate::output::clob::{
-    print_account_status, print_api_keys, print_balance, print_batch_prices, print_cancel_result,
-    print_clob_market, print_clob_markets, print_create_api_key, print_current_rewards,
-    print_delete_api_key, print_earnings, print_fee_rate, print_geoblock, print_last_trade,
-    print_last_trades_prices, print_market_reward, print_midpoint, print_midpoints, print_neg_risk,
-    print_notifications, print_ok, print_order_book, print_order_books, print_order_detail,
-    print_order_scoring, print_orders, print_orders_scoring, print_post_order_result,
-    print_post_orders_result, print_price, print_price_history, print_reward_percentages,
+    ComplementLeg, ComplementReport, EARNING_COLUMNS, END_CURSOR, EarningRow, EarningsRange,
+    ExecutionReport, FillEstimate, FlattenItem, Quote, RewardCheck, StreamRow, TokenBalance,
+    WaitOutcome, cancel_result_to_json, current_reward_to_json, market_order_result_to_json,
+    market_reward_to_json, open_order_to_json, post_order_to_json, print_account_status,
+    print_api_keys, print_balance, print_batch_prices, print_cancel_result, print_clob_market,
+    print_clob_markets, print_complement_report, print_create_api_key, print_current_rewards,
+    print_delete_api_key, print_earnings, print_earnings_range, print_execution_report,
+    print_fee_rate, print_flatten_plan, print_geoblock, print_journal, print_journal_entry,
+    print_journal_synced, print_ladder, print_last_trade, print_last_trades_prices,
+    print_market_order_result, print_market_reward, print_midpoint, print_midpoints,
+    print_neg_risk, print_notifications, print_ok, print_order_book, print_order_books,
+    print_order_detail, print_order_scoring, print_orders, print_orders_scoring,
+    print_post_order_result, print_post_orders_result, print_price, print_price_history,
+    print_quote, print_quotes, print_replace_result, print_reward_check, print_reward_percentages,
     print_rewards, print_server_time, print_simplified_markets, print_spread, print_spreads,
-    print_tick_size, print_trades, print_user_earnings_markets,
+    print_stream_view, print_tick_size, print_token_balances, print_trades, print_trigger_canceled,
+    print_trigger_saved, print_triggers, print_user_earnings_markets, print_wait_result,
+    trade_to_json, user_earning_to_json, wait_result_to_json,
 };
+use crate::output::scan::SetSide;
+use crate::output::{OutputFormat, json_value};
+use crate::paper;
+use crate::triggers::{self, Direction, PriceSource, Trigger};
+use crate::{auth, config};
 
 #[derive(Args)]
 pub struct ClobArgs {
@@ -35,6 +76,17 @@ pub struct ClobArgs {
     pub command: ClobCommand,
 }
 
+/// Selects a token by market and outcome name instead of by token ID.
+#[derive(Args)]
+pub struct OutcomeArgs {
+    /// Market slug, numeric ID, or condition ID (use with --outcome instead of a token ID)
+    #[arg(long, requires = "outcome")]
+    market: Option<String>,
+    /// Outcome name within --market (e.g. Yes, No)
+    #[arg(long, requires = "market")]
+    outcome: Option<String>,
+}
+
 #[derive(Subcommand)]
 pub enum ClobCommand {
     /// Check CLOB API health
@@ -43,7 +95,10 @@ pub enum ClobCommand {
     /// Get price for a token
     Price {
         /// Token ID (numeric string)
-        token_id: String,
+        #[arg(required_unless_present = "market", conflicts_with = "market")]
+        token_id: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
         /// Side: buy or sell
         #[arg(long)]
         side: CliSide,
@@ -88,7 +143,10 @@ pub enum ClobCommand {
     /// Get order book for a token
     Book {
         /// Token ID (numeric string)
-        token_id: String,
+        #[arg(required_unless_present = "market", conflicts_with = "market")]
+        token_id: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
     },
 
     /// Get order books for multiple tokens
@@ -97,6 +155,45 @@ pub enum ClobCommand {
         token_ids: String,
     },
 
+    /// Stream live order books, price changes and trades over WebSocket
+    Stream {
+        /// Token IDs (comma-separated numeric strings)
+        #[arg(long)]
+        tokens: String,
+    },
+
+    /// Simulate a market order against the live book without signing
+    Quote {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Amount to fill
+        #[arg(long)]
+        amount: String,
+        /// Unit of --amount: usdc or shares (default: usdc for buys, shares for sells)
+        #[arg(long)]
+        unit: Option<QuoteUnit>,
+    },
+
+    /// Simulate the same market order on several tokens at once
+    Quotes {
+        /// Token IDs (comma-separated numeric strings)
+        #[arg(long)]
+        tokens: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Amount to fill on each token
+        #[arg(long)]
+        amount: String,
+        /// Unit of --amount: usdc or shares (default: usdc for buys, shares for sells)
+        #[arg(long)]
+        unit: Option<QuoteUnit>,
+    },
+
     /// Get last trade price for a token
     LastTrade {
         /// Token ID (numeric string)
@@ -120,6 +217,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// List sampling markets (reward-eligible)
@@ -127,6 +226,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// List simplified markets (reduced detail)
@@ -134,6 +235,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// List simplified sampling markets
@@ -141,6 +244,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get tick size for a token
@@ -164,7 +269,10 @@ pub enum ClobCommand {
     /// Get price history for a token
     PriceHistory {
         /// Token ID (numeric string)
-        token_id: String,
+        #[arg(required_unless_present = "market", conflicts_with = "market")]
+        token_id: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
         /// Time interval: 1m, 1h, 6h, 1d, 1w, max
         #[arg(long)]
         interval: CliInterval,
@@ -190,6 +298,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get a single order by ID (authenticated)
@@ -198,11 +308,32 @@ pub enum ClobCommand {
         order_id: String,
     },
 
+    /// Wait for an order to fill or be canceled (authenticated)
+    ///
+    /// Exits 0 once the --until state is reached. Otherwise exits 3 when
+    /// partially filled, 4 when canceled, 5 on timeout and 6 when filled
+    /// (only possible with --until cancelled).
+    Wait {
+        /// Order ID
+        order_id: String,
+        /// Give up after this long (e.g. 30s, 5m, 1h)
+        #[arg(long, default_value = "5m")]
+        timeout: String,
+        /// Stop once the order reaches this state (or any terminal state)
+        #[arg(long, value_enum, default_value_t = WaitUntil::Filled)]
+        until: WaitUntil,
+        /// How often to poll the order
+        #[arg(long, default_value = "2s")]
+        interval: String,
+    },
+
     /// Create a limit order (authenticated)
     CreateOrder {
         /// Token ID (numeric string)
-        #[arg(long)]
-        token: String,
+        #[arg(long, required_unless_present = "market", conflicts_with = "market")]
+        token: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
         /// Side: buy or sell
         #[arg(long)]
         side: CliSide,
@@ -218,6 +349,15 @@ pub enum ClobCommand {
         /// Post-only order
         #[arg(long)]
         post_only: bool,
+        /// Snap an off-grid price (and an undersized or over-precise size) instead of failing
+        #[arg(long, value_enum)]
+        round: Option<RoundMode>,
+        /// Block until the order is filled, canceled or --wait-timeout passes
+        #[arg(long)]
+        wait: bool,
+        /// Give up waiting after this long (with --wait)
+        #[arg(long, default_value = "5m", requires = "wait")]
+        wait_timeout: String,
     },
 
     /// Post multiple orders at once (authenticated)
@@ -237,13 +377,252 @@ pub enum ClobCommand {
         /// Order type: GTC, FOK, GTD, FAK (default: GTC)
         #[arg(long, default_value = "GTC")]
         order_type: CliOrderType,
+        /// Snap an off-grid price (and an undersized or over-precise size) instead of failing
+        #[arg(long, value_enum)]
+        round: Option<RoundMode>,
     },
 
-    /// Create a market order (authenticated)
-    MarketOrder {
+    /// Post a ladder of limit orders across a price range (authenticated)
+    Ladder {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// First price of the ladder (e.g. 0.40)
+        #[arg(long)]
+        from: String,
+        /// Last price of the ladder (e.g. 0.48)
+        #[arg(long)]
+        to: String,
+        /// Number of orders in the ladder
+        #[arg(long)]
+        levels: u32,
+        /// Total size in shares, spread across all levels
+        #[arg(long)]
+        total_size: String,
+        /// Size distribution: flat, linear, or geometric (default: flat)
+        #[arg(long, default_value = "flat")]
+        distribution: Distribution,
+        /// Growth ratio between levels for the geometric distribution
+        #[arg(long, default_value = "1.5")]
+        ratio: String,
+        /// Order type: GTC, FOK, GTD, FAK (default: GTC)
+        #[arg(long, default_value = "GTC")]
+        order_type: CliOrderType,
+        /// Post-only orders
+        #[arg(long)]
+        post_only: bool,
+        /// Show the ladder without signing or posting
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Work a large order as evenly spaced market slices over time (authenticated)
+    Twap {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Total size in shares
+        #[arg(long)]
+        total: String,
+        /// How long to spread the order over (e.g. 30m, 2h)
+        #[arg(long)]
+        duration: String,
+        /// Number of child orders
+        #[arg(long, default_value = "10")]
+        slices: u32,
+        /// Worst price any slice may fill at
+        #[arg(long)]
+        limit_price: Option<String>,
+    },
+
+    /// Rest a large limit order while only showing part of it (authenticated)
+    Iceberg {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Limit price for every clip
+        #[arg(long)]
+        price: String,
+        /// Size shown on the book at a time, in shares
+        #[arg(long)]
+        visible: String,
+        /// Total size in shares
+        #[arg(long)]
+        total: String,
+        /// How often to check the resting clip for fills
+        #[arg(long, default_value = "5s")]
+        poll: String,
+        /// Post-only clips
+        #[arg(long)]
+        post_only: bool,
+    },
+
+    /// Save a stop-loss / take-profit trigger and watch it (authenticated)
+    Stop {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side of the order sent when the trigger fires
+        #[arg(long)]
+        side: CliSide,
+        /// Price that fires the trigger
+        #[arg(long)]
+        trigger_price: String,
+        /// Order size in shares
+        #[arg(long)]
+        size: String,
+        /// Send a limit order at this price instead of a market order
+        #[arg(long)]
+        limit: Option<String>,
+        /// Fire when the price is below or above the trigger (default: below for sells, above for buys)
+        #[arg(long)]
+        when: Option<Direction>,
+        /// Price to watch: midpoint or last-trade (default: midpoint)
+        #[arg(long, default_value = "midpoint")]
+        source: PriceSource,
+        /// How often to check prices while watching
+        #[arg(long, default_value = "5s")]
+        interval: String,
+        /// Save the trigger without watching it
+        #[arg(long)]
+        no_watch: bool,
+    },
+
+    /// List pending stop triggers
+    Triggers,
+
+    /// Remove a pending stop trigger
+    CancelTrigger {
+        /// Trigger ID (from `clob triggers`)
+        id: u64,
+    },
+
+    /// Reset the --paper account to a fresh cash balance with no positions or orders
+    PaperReset {
+        /// Starting cash in USDC
+        #[arg(long, default_value = "1000")]
+        cash: String,
+    },
+
+    /// Browse the local journal of posted and canceled orders
+    Journal {
+        #[command(subcommand)]
+        command: JournalCommand,
+    },
+
+    /// Watch pending stop triggers and fire orders when they cross (authenticated)
+    WatchTriggers {
+        /// How often to check prices
+        #[arg(long, default_value = "5s")]
+        interval: String,
+    },
+
+    /// Keep a two-sided quote around the midpoint until Ctrl-C (authenticated)
+    Make {
         /// Token ID (numeric string)
         #[arg(long)]
         token: String,
+        /// Total width between bid and ask, in ticks (e.g. 2t) or price (e.g. 0.02)
+        #[arg(long)]
+        spread: String,
+        /// Size of each quote in shares
+        #[arg(long)]
+        size: String,
+        /// Stop bidding once holding this many shares
+        #[arg(long)]
+        max_inventory: String,
+        /// Shift both quotes down as inventory builds up (and up as it drains)
+        #[arg(long)]
+        skew: bool,
+        /// Requote when the midpoint moves this far, in ticks or price
+        #[arg(long, default_value = "1t")]
+        requote: String,
+        /// How often to refresh prices and inventory
+        #[arg(long, default_value = "5s")]
+        interval: String,
+    },
+
+    /// Sell all or part of a position (authenticated)
+    Close {
+        /// Token ID (numeric string)
+        #[arg(
+            long,
+            required_unless_present = "outcome_market",
+            conflicts_with = "outcome_market"
+        )]
+        token: Option<String>,
+        /// Market slug, numeric ID, or condition ID (use with --outcome instead of a token ID)
+        #[arg(long, requires = "outcome")]
+        outcome_market: Option<String>,
+        /// Outcome name within --outcome-market (e.g. Yes, No)
+        #[arg(long, requires = "outcome_market")]
+        outcome: Option<String>,
+        /// Sell with a limit order at this price
+        #[arg(long, conflicts_with = "market", required_unless_present = "market")]
+        limit: Option<String>,
+        /// Sell with a FOK market order
+        #[arg(long)]
+        market: bool,
+        /// Percentage of the position to sell
+        #[arg(long, default_value = "100")]
+        percent: String,
+    },
+
+    /// Sell every position, or those in one event or tag (authenticated)
+    #[command(group(clap::ArgGroup::new("scope").required(true).args(["event", "tag", "all"])))]
+    Flatten {
+        /// Only positions in this event (slug)
+        #[arg(long)]
+        event: Option<String>,
+        /// Only positions in events with this tag (slug)
+        #[arg(long)]
+        tag: Option<String>,
+        /// Every open position
+        #[arg(long)]
+        all: bool,
+        /// Worst price to accept, in basis points below the best bid
+        #[arg(long, default_value = "500")]
+        max_slippage_bps: u32,
+        /// Show what would be sold without canceling or selling anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Buy both sides of a binary market below $1 and merge them into USDC (authenticated)
+    ArbComplement {
+        /// Market slug, numeric ID, or condition ID
+        #[arg(long)]
+        market: String,
+        /// Most sets (shares of each side) to buy
+        #[arg(long)]
+        max_size: String,
+        /// Minimum edge per $1 set after fees, as a percentage (0.5%) or fraction (0.005)
+        #[arg(long, default_value = "0")]
+        min_edge: String,
+        /// Show the planned trade without placing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Create a market order (authenticated)
+    MarketOrder {
+        /// Token ID (numeric string)
+        #[arg(long, required_unless_present = "market", conflicts_with = "market")]
+        token: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
         /// Side: buy or sell
         #[arg(long)]
         side: CliSide,
@@ -253,6 +632,39 @@ pub enum ClobCommand {
         /// Order type: FOK or FAK (default: FOK)
         #[arg(long, default_value = "FOK")]
         order_type: CliOrderType,
+        /// Worst price to pay on a buy; refuses if the book walk goes above it
+        #[arg(long)]
+        max_price: Option<String>,
+        /// Worst price to accept on a sell; refuses if the book walk goes below it
+        #[arg(long)]
+        min_price: Option<String>,
+        /// Refuse if the estimated average fill is further than this from the best price
+        #[arg(long)]
+        max_slippage_bps: Option<u32>,
+        /// Send even if the book can't fill the whole amount
+        #[arg(long)]
+        allow_partial: bool,
+        /// Block until the order is filled, canceled or --wait-timeout passes
+        #[arg(long)]
+        wait: bool,
+        /// Give up waiting after this long (with --wait)
+        #[arg(long, default_value = "5m", requires = "wait")]
+        wait_timeout: String,
+    },
+
+    /// Replace an open order with a new price and/or size (authenticated)
+    Replace {
+        /// Order ID to replace
+        order_id: String,
+        /// New price (defaults to the original price)
+        #[arg(long)]
+        price: Option<String>,
+        /// New size in shares (defaults to the unfilled remainder)
+        #[arg(long)]
+        size: Option<String>,
+        /// Post-only replacement order
+        #[arg(long)]
+        post_only: bool,
     },
 
     /// Cancel an order by ID (authenticated)
@@ -280,8 +692,67 @@ pub enum ClobCommand {
         asset: Option<String>,
     },
 
+    /// Cancel open orders matching every given filter (authenticated)
+    #[command(group(
+        clap::ArgGroup::new("filter")
+            .required(true)
+            .multiple(true)
+            .args(["side", "price_above", "price_below", "older_than", "token", "event", "not_scoring"])
+    ))]
+    CancelWhere {
+        /// Only orders on this side
+        #[arg(long)]
+        side: Option<CliSide>,
+        /// Only orders priced above this
+        #[arg(long)]
+        price_above: Option<String>,
+        /// Only orders priced below this
+        #[arg(long)]
+        price_below: Option<String>,
+        /// Only orders placed longer ago than this (e.g. 30m, 1h, 2d)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Only orders on this token
+        #[arg(long)]
+        token: Option<String>,
+        /// Only orders in markets of this event (slug)
+        #[arg(long)]
+        event: Option<String>,
+        /// Only orders not currently scoring liquidity rewards
+        #[arg(long)]
+        not_scoring: bool,
+        /// List matching orders without canceling them
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Cancel orders when a strategy stops touching a file (authenticated)
+    Heartbeat {
+        /// File the strategy touches periodically; its modification time is the heartbeat
+        #[arg(long)]
+        touch_file: PathBuf,
+        /// How often to check the touch file
+        #[arg(long, default_value = "10s")]
+        interval: String,
+        /// Cancel once the touch file is older than this
+        #[arg(long, default_value = "30s")]
+        timeout: String,
+        /// Only cancel orders in these markets (comma-separated condition IDs; default: all orders)
+        #[arg(long)]
+        markets: Option<String>,
+        /// Append events to this file (default: ~/.config/polymarket/heartbeat.log)
+        #[arg(long)]
+        log: Option<PathBuf>,
+    },
+
     /// List trades (authenticated)
+    #[command(args_conflicts_with_subcommands = true)]
     Trades {
+        #[command(subcommand)]
+        command: Option<TradesCommand>,
         /// Filter by market condition ID
         #[arg(long)]
         market: Option<String>,
@@ -291,6 +762,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get balance and allowance (authenticated)
@@ -299,8 +772,20 @@ pub enum ClobCommand {
         #[arg(long)]
         asset_type: CliAssetType,
         /// Token ID (required for conditional)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "market")]
         token: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
+    },
+
+    /// Compare CLOB and on-chain balances of every token held (authenticated)
+    Balances {
+        /// Only list balances, without offering to refresh stale ones
+        #[arg(long)]
+        dry_run: bool,
+        /// Refresh stale balance allowances without asking
+        #[arg(long)]
+        force: bool,
     },
 
     /// Refresh balance allowance on-chain (authenticated)
@@ -323,29 +808,43 @@ pub enum ClobCommand {
     },
 
     /// List reward earnings (authenticated)
+    #[command(group(
+        clap::ArgGroup::new("paging")
+            .args(["all", "max_items"])
+            .multiple(true)
+            .conflicts_with("from")
+    ))]
     Rewards {
         /// Date (YYYY-MM-DD)
-        #[arg(long)]
-        date: String,
+        #[arg(long, required_unless_present = "from")]
+        date: Option<String>,
+        #[command(flatten)]
+        range: DateRange,
         /// Pagination cursor
-        #[arg(long)]
+        #[arg(long, conflicts_with = "from")]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get total earnings for a date (authenticated)
     Earnings {
         /// Date (YYYY-MM-DD)
-        #[arg(long)]
-        date: String,
+        #[arg(long, required_unless_present = "from")]
+        date: Option<String>,
+        #[command(flatten)]
+        range: DateRange,
     },
 
     /// Get earnings with market reward config (authenticated)
     EarningsMarkets {
         /// Date (YYYY-MM-DD)
-        #[arg(long)]
-        date: String,
+        #[arg(long, required_unless_present = "from")]
+        date: Option<String>,
+        #[command(flatten)]
+        range: DateRange,
         /// Pagination cursor
-        #[arg(long)]
+        #[arg(long, conflicts_with = "from")]
         cursor: Option<String>,
     },
 
@@ -357,6 +856,8 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get reward details for a market (authenticated)
@@ -366,6 +867,26 @@ pub enum ClobCommand {
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        #[command(flatten)]
+        pages: AllPages,
+    },
+
+    /// Estimate whether a prospective order would earn liquidity rewards (authenticated)
+    RewardCheck {
+        /// Token ID (numeric string)
+        #[arg(long, required_unless_present = "market", conflicts_with = "market")]
+        token: Option<String>,
+        #[command(flatten)]
+        by_outcome: OutcomeArgs,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Price (decimal, e.g. 0.50)
+        #[arg(long)]
+        price: String,
+        /// Size (number of shares, e.g. 10)
+        #[arg(long)]
+        size: String,
     },
 
     /// Check if an order is scoring rewards (authenticated)
@@ -459,6 +980,146 @@ impl From<CliOrderType> for OrderType {
     }
 }
 
+/// How a ladder's total size is spread across its levels, from `--from` to `--to`.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Distribution {
+    /// Equal size at every level
+    Flat,
+    /// Size grows linearly (1, 2, 3, ...)
+    Linear,
+    /// Size grows by `--ratio` at each level
+    Geometric,
+}
+
+#[derive(Subcommand)]
+pub enum JournalCommand {
+    /// List journal entries, oldest first
+    List {
+        /// Only entries involving this order ID
+        #[arg(long)]
+        order: Option<String>,
+        /// Only entries of this kind
+        #[arg(long)]
+        action: Option<Action>,
+        /// Only the most recent N matching entries
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Show one entry in full, including the signed order and raw response
+    Show {
+        /// Entry number (from `clob journal list`)
+        entry: usize,
+    },
+    /// Export every entry as JSON lines or CSV
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Jsonl)]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Append fills of journaled orders from trade history (authenticated)
+    Sync,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum ExportFormat {
+    Jsonl,
+    Csv,
+}
+
+#[derive(Subcommand)]
+pub enum TradesCommand {
+    /// Export every fill with its market, fee and notional, oldest first
+    Export {
+        /// Only fills matched at or after this date (YYYY-MM-DD, UTC) or RFC 3339 time
+        #[arg(long)]
+        since: Option<String>,
+        /// Only fills matched before this date (YYYY-MM-DD, UTC) or RFC 3339 time
+        #[arg(long)]
+        until: Option<String>,
+        /// Filter by market condition ID
+        #[arg(long)]
+        market: Option<String>,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Csv)]
+        format: ReportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+/// `--from`/`--to` for the reward reports, in place of a single `--date`.
+#[derive(Args)]
+pub struct DateRange {
+    /// First day of a range report (YYYY-MM-DD)
+    #[arg(long, requires = "to", conflicts_with = "date")]
+    from: Option<String>,
+    /// Last day of the range, inclusive (YYYY-MM-DD)
+    #[arg(long, requires = "from")]
+    to: Option<String>,
+    /// Export the per-day rows instead of printing the summary
+    #[arg(long, value_enum, requires = "from")]
+    export: Option<ReportFormat>,
+    /// Write the export to this file instead of stdout
+    #[arg(long, requires = "export")]
+    file: Option<PathBuf>,
+}
+
+impl DateRange {
+    /// Longest range a report will walk, one request (or more) per day.
+    const MAX_DAYS: i64 = 366;
+
+    /// Every day from `--from` through `--to`, or `None` without a range.
+    fn days(&self) -> Result<Option<Vec<NaiveDate>>> {
+        let (Some(from), Some(to)) = (&self.from, &self.to) else {
+            return Ok(None);
+        };
+        let (from, to) = (parse_date(from)?, parse_date(to)?);
+        anyhow::ensure!(from <= to, "--from must not be after --to");
+        anyhow::ensure!(
+            (to - from).num_days() < Self::MAX_DAYS,
+            "Date ranges are limited to {} days",
+            Self::MAX_DAYS
+        );
+        Ok(Some(from.iter_days().take_while(|d| *d <= to).collect()))
+    }
+
+    /// Prints the range summary, or writes the rows with `--export`.
+    fn report(
+        self,
+        days: &[NaiveDate],
+        rows: Vec<EarningRow>,
+        output: &OutputFormat,
+    ) -> Result<()> {
+        if let Some(format) = self.export {
+            let fields: Vec<_> = rows.iter().map(EarningRow::fields).collect();
+            let data = export_rows(format, EARNING_COLUMNS, &fields)?;
+            return write_export(&data, self.file.as_deref(), rows.len(), "rows");
+        }
+        let report = EarningsRange {
+            from: days[0],
+            to: days[days.len() - 1],
+            rows,
+        };
+        print_earnings_range(&report, output)
+    }
+}
+
+/// File format for `trades export` and reward range reports.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+/// Unit of a quoted amount.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum QuoteUnit {
+    Usdc,
+    Shares,
+}
+
 #[derive(Clone, Debug, clap::ValueEnum)]
 pub enum CliAssetType {
     Collateral,
@@ -482,697 +1143,5669 @@ fn parse_token_ids(s: &str) -> Result<Vec<U256>> {
     s.split(',').map(|t| parse_token_id(t.trim())).collect()
 }
 
+/// Picks the token for `name` from a market's `(outcome, token)` pairs.
+///
+/// An exact (case-insensitive) match wins; otherwise, with `allow_prefix`, the
+/// name must be a prefix of exactly one outcome. Commands that post orders
+/// pass `false` so a typo can't land on the wrong side of a market.
+fn pick_outcome(outcomes: &[(String, U256)], name: &str, allow_prefix: bool) -> Result<U256> {
+    let wanted = name.trim().to_lowercase();
+    if let Some((_, token)) = outcomes.iter().find(|(o, _)| o.to_lowercase() == wanted) {
+        return Ok(*token);
+    }
+    let names = || {
+        outcomes
+            .iter()
+            .map(|(o, _)| o.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if !allow_prefix {
+        anyhow::bail!(
+            "No outcome named \"{name}\" (available: {}); orders need the full outcome name",
+            names()
+        );
+    }
+    let matches: Vec<_> = outcomes
+        .iter()
+        .filter(|(o, _)| o.to_lowercase().starts_with(&wanted))
+        .collect();
+    match matches.as_slice() {
+        [(_, token)] => Ok(*token),
+        [] => anyhow::bail!("No outcome named \"{name}\" (available: {})", names()),
+        _ => anyhow::bail!(
+            "Outcome \"{name}\" is ambiguous: matches {}",
+            matches
+                .iter()
+                .map(|(o, _)| o.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Looks up a market's outcomes by slug, numeric gamma ID, or condition ID.
+async fn market_outcomes(market: &str) -> Result<Vec<(String, U256)>> {
+    if market.starts_with("0x") {
+        let market = clob::Client::default().market(market).await?;
+        return Ok(market
+            .tokens
+            .into_iter()
+            .map(|t| (t.outcome, t.token_id))
+            .collect());
+    }
+
+    let client = gamma::Client::default();
+    let market = if is_numeric_id(market) {
+        let req = MarketByIdRequest::builder().id(market).build();
+        client.market_by_id(&req).await?
+    } else {
+        let req = MarketBySlugRequest::builder().slug(market).build();
+        client.market_by_slug(&req).await?
+    };
+    let outcomes = market.outcomes.unwrap_or_default();
+    let tokens = market.clob_token_ids.unwrap_or_default();
+    anyhow::ensure!(
+        !tokens.is_empty() && outcomes.len() == tokens.len(),
+        "Market {} has no tradable outcomes",
+        market.slug.as_deref().unwrap_or(&market.id)
+    );
+    Ok(outcomes.into_iter().zip(tokens).collect())
+}
+
+/// Resolves a token from either an explicit ID or `--market`/`--outcome`,
+/// accepting a unique outcome prefix.
+async fn resolve_token(token: Option<&str>, by_outcome: &OutcomeArgs) -> Result<U256> {
+    resolve_outcome_token(token, by_outcome, true).await
+}
+
+/// Like [`resolve_token`], but for commands that post orders: the outcome
+/// name must match exactly (ignoring case).
+async fn resolve_order_token(token: Option<&str>, by_outcome: &OutcomeArgs) -> Result<U256> {
+    resolve_outcome_token(token, by_outcome, false).await
+}
+
+async fn resolve_outcome_token(
+    token: Option<&str>,
+    by_outcome: &OutcomeArgs,
+    allow_prefix: bool,
+) -> Result<U256> {
+    match (token, &by_outcome.market, &by_outcome.outcome) {
+        (Some(token), _, _) => parse_token_id(token),
+        (None, Some(market), Some(outcome)) => {
+            pick_outcome(&market_outcomes(market).await?, outcome, allow_prefix)
+        }
+        _ => anyhow::bail!("Pass a token ID, or --market together with --outcome"),
+    }
+}
+
 fn parse_date(s: &str) -> Result<NaiveDate> {
     NaiveDate::parse_from_str(s, "%Y-%m-%d")
         .map_err(|_| anyhow::anyhow!("Invalid date: expected YYYY-MM-DD format"))
 }
 
-pub async fn execute(
-    args: ClobArgs,
-    output: OutputFormat,
-    private_key: Option<&str>,
-    signature_type: Option<&str>,
-) -> Result<()> {
-    match args.command {
-        // Unauthenticated read commands
-        ClobCommand::Ok
-        | ClobCommand::Price { .. }
-        | ClobCommand::BatchPrices { .. }
-        | ClobCommand::Midpoint { .. }
-        | ClobCommand::Midpoints { .. }
-        | ClobCommand::Spread { .. }
-        | ClobCommand::Spreads { .. }
-        | ClobCommand::Book { .. }
-        | ClobCommand::Books { .. }
-        | ClobCommand::LastTrade { .. }
-        | ClobCommand::LastTrades { .. }
-        | ClobCommand::Market { .. }
-        | ClobCommand::Markets { .. }
-        | ClobCommand::SamplingMarkets { .. }
-        | ClobCommand::SimplifiedMarkets { .. }
-        | ClobCommand::SamplingSimpMarkets { .. }
-        | ClobCommand::TickSize { .. }
-        | ClobCommand::FeeRate { .. }
-        | ClobCommand::NegRisk { .. }
-        | ClobCommand::PriceHistory { .. }
-        | ClobCommand::Time
-        | ClobCommand::Geoblock => execute_read(args.command, &output).await,
+/// Parses a UTC date (taken as midnight) or an RFC 3339 timestamp.
+fn parse_time_bound(s: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    parse_date(s)
+        .map(|d| d.and_time(NaiveTime::MIN).and_utc())
+        .map_err(|_| anyhow::anyhow!("Invalid time: {s} (expected YYYY-MM-DD or RFC 3339)"))
+}
 
-        // Authenticated trading commands
-        ClobCommand::Orders { .. }
-        | ClobCommand::Order { .. }
-        | ClobCommand::CreateOrder { .. }
-        | ClobCommand::PostOrders { .. }
-        | ClobCommand::MarketOrder { .. }
-        | ClobCommand::Cancel { .. }
-        | ClobCommand::CancelOrders { .. }
-        | ClobCommand::CancelAll
-        | ClobCommand::CancelMarket { .. }
-        | ClobCommand::Trades { .. }
-        | ClobCommand::Balance { .. }
-        | ClobCommand::UpdateBalance { .. }
-        | ClobCommand::Notifications
-        | ClobCommand::DeleteNotifications { .. } => {
-            execute_trade(args.command, &output, private_key, signature_type).await
-        }
+/// Order sizes are limited to 2 decimal places by the CLOB.
+const SIZE_DECIMALS: u32 = 2;
 
-        // Authenticated reward commands
-        ClobCommand::Rewards { .. }
-        | ClobCommand::Earnings { .. }
-        | ClobCommand::EarningsMarkets { .. }
-        | ClobCommand::RewardPercentages
-        | ClobCommand::CurrentRewards { .. }
-        | ClobCommand::MarketReward { .. }
-        | ClobCommand::OrderScoring { .. }
-        | ClobCommand::OrdersScoring { .. } => {
-            execute_rewards(args.command, &output, private_key, signature_type).await
-        }
+/// How `--round` snaps prices and sizes the CLOB would reject.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum RoundMode {
+    Down,
+    Up,
+    Nearest,
+}
 
-        // Account management commands
-        ClobCommand::ApiKeys
-        | ClobCommand::DeleteApiKey
-        | ClobCommand::CreateApiKey
-        | ClobCommand::AccountStatus => {
-            execute_account(args.command, &output, private_key, signature_type).await
+impl RoundMode {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::Down => RoundingStrategy::ToNegativeInfinity,
+            Self::Up => RoundingStrategy::ToPositiveInfinity,
+            Self::Nearest => RoundingStrategy::MidpointAwayFromZero,
         }
     }
 }
 
-async fn execute_read(command: ClobCommand, output: &OutputFormat) -> Result<()> {
-    match command {
-        ClobCommand::Ok => {
-            let client = clob::Client::default();
-            let result = client.ok().await?;
-            print_ok(&result, output)?;
-        }
-
-        ClobCommand::Price { token_id, side } => {
-            let client = clob::Client::default();
-            let request = PriceRequest::builder()
-                .token_id(parse_token_id(&token_id)?)
-                .side(Side::from(side))
-                .build();
-            let result = client.price(&request).await?;
-            print_price(&result, output)?;
-        }
+/// Tick size and minimum order size the CLOB enforces for a token.
+#[derive(Clone, Copy, Debug)]
+struct OrderRules {
+    tick: Decimal,
+    min_size: Decimal,
+}
 
-        ClobCommand::BatchPrices { token_ids, side } => {
-            let client = clob::Client::default();
-            let requests: Vec<_> = parse_token_ids(&token_ids)?
-                .into_iter()
-                .map(|id| {
-                    PriceRequest::builder()
-                        .token_id(id)
-                        .side(Side::from(side.clone()))
-                        .build()
-                })
-                .collect();
-            let result = client.prices(&requests).await?;
-            print_batch_prices(&result, output)?;
+impl OrderRules {
+    /// Check a limit order's price and size, snapping them with `round`
+    /// instead of failing.
+    fn apply(
+        &self,
+        price: Decimal,
+        size: Decimal,
+        round: Option<RoundMode>,
+    ) -> Result<(Decimal, Decimal)> {
+        anyhow::ensure!(
+            price > Decimal::ZERO && price < Decimal::ONE,
+            "Price {price} is outside the valid range (0, 1)"
+        );
+        let mut price = price;
+        if !(price % self.tick).is_zero() {
+            let Some(mode) = round else {
+                anyhow::bail!(
+                    "Price {price} is not a multiple of the {} tick size (use --round to snap it)",
+                    self.tick
+                );
+            };
+            price = ((price / self.tick).round_dp_with_strategy(0, mode.strategy()) * self.tick)
+                .clamp(self.tick, Decimal::ONE - self.tick);
         }
 
-        ClobCommand::Midpoint { token_id } => {
-            let client = clob::Client::default();
-            let request = MidpointRequest::builder()
-                .token_id(parse_token_id(&token_id)?)
-                .build();
-            let result = client.midpoint(&request).await?;
-            print_midpoint(&result, output)?;
+        let mut size = size;
+        if size.normalize().scale() > SIZE_DECIMALS {
+            let Some(mode) = round else {
+                anyhow::bail!(
+                    "Size {size} has more than {SIZE_DECIMALS} decimal places (use --round to snap it)"
+                );
+            };
+            size = size.round_dp_with_strategy(SIZE_DECIMALS, mode.strategy());
         }
-
-        ClobCommand::Midpoints { token_ids } => {
-            let client = clob::Client::default();
-            let requests: Vec<_> = parse_token_ids(&token_ids)?
-                .into_iter()
-                .map(|id| MidpointRequest::builder().token_id(id).build())
-                .collect();
-            let result = client.midpoints(&requests).await?;
-            print_midpoints(&result, output)?;
+        if size < self.min_size {
+            anyhow::ensure!(
+                round == Some(RoundMode::Up),
+                "Size {size} is below the {} share minimum for this market \
+                 (use --round up to raise it)",
+                self.min_size
+            );
+            size = self.min_size;
         }
+        Ok((price.normalize(), size.normalize()))
+    }
+}
 
-        ClobCommand::Spread { token_id, side } => {
-            let client = clob::Client::default();
-            let request = SpreadRequest::builder()
-                .token_id(parse_token_id(&token_id)?)
-                .maybe_side(side.map(Side::from))
-                .build();
-            let result = client.spread(&request).await?;
-            print_spread(&result, output)?;
-        }
+/// Per-token cache so a batch touching the same token fetches its rules once.
+#[derive(Default)]
+struct OrderRulesCache(HashMap<U256, OrderRules>);
 
-        ClobCommand::Spreads { token_ids } => {
-            let client = clob::Client::default();
-            let requests: Vec<_> = parse_token_ids(&token_ids)?
-                .into_iter()
-                .map(|id| SpreadRequest::builder().token_id(id).build())
-                .collect();
-            let result = client.spreads(&requests).await?;
-            print_spreads(&result, output)?;
+impl OrderRulesCache {
+    async fn get(&mut self, token_id: U256) -> Result<OrderRules> {
+        if let Some(rules) = self.0.get(&token_id) {
+            return Ok(*rules);
         }
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_id)
+            .build();
+        let book = clob::Client::default().order_book(&request).await?;
+        let rules = OrderRules {
+            tick: book.tick_size.as_decimal(),
+            min_size: book.min_order_size,
+        };
+        self.0.insert(token_id, rules);
+        Ok(rules)
+    }
 
-        ClobCommand::Book { token_id } => {
-            let client = clob::Client::default();
-            let request = OrderBookSummaryRequest::builder()
-                .token_id(parse_token_id(&token_id)?)
-                .build();
-            let result = client.order_book(&request).await?;
-            print_order_book(&result, output)?;
+    /// [`OrderRules::apply`] for `token_id`, noting any adjustment on stderr.
+    async fn check(
+        &mut self,
+        token_id: U256,
+        price: Decimal,
+        size: Decimal,
+        round: Option<RoundMode>,
+    ) -> Result<(Decimal, Decimal)> {
+        let (new_price, new_size) = self.get(token_id).await?.apply(price, size, round)?;
+        if new_price != price {
+            eprintln!("Adjusted price {price} -> {new_price}");
         }
-
-        ClobCommand::Books { token_ids } => {
-            let client = clob::Client::default();
-            let requests: Vec<_> = parse_token_ids(&token_ids)?
-                .into_iter()
-                .map(|id| OrderBookSummaryRequest::builder().token_id(id).build())
-                .collect();
-            let result = client.order_books(&requests).await?;
-            print_order_books(&result, output)?;
+        if new_size != size {
+            eprintln!("Adjusted size {size} -> {new_size}");
         }
+        Ok((new_price, new_size))
+    }
+}
 
-        ClobCommand::LastTrade { token_id } => {
-            let client = clob::Client::default();
-            let request = LastTradePriceRequest::builder()
-                .token_id(parse_token_id(&token_id)?)
-                .build();
-            let result = client.last_trade_price(&request).await?;
-            print_last_trade(&result, output)?;
+/// Builds `(price, size)` pairs for a ladder from `from` to `to`.
+///
+/// Prices are evenly spaced and snapped to the nearest tick; sizes are truncated
+/// to the lot size and the rounding remainder goes to the last level so the
+/// ladder always sums to `total_size`.
+fn ladder_levels(
+    from: Decimal,
+    to: Decimal,
+    levels: u32,
+    total_size: Decimal,
+    distribution: Distribution,
+    ratio: Decimal,
+    tick: Decimal,
+) -> Result<Vec<(Decimal, Decimal)>> {
+    anyhow::ensure!(levels > 0, "--levels must be at least 1");
+    anyhow::ensure!(total_size > Decimal::ZERO, "--total-size must be positive");
+    anyhow::ensure!(
+        total_size.normalize().scale() <= SIZE_DECIMALS,
+        "--total-size {total_size} has more than {SIZE_DECIMALS} decimal places"
+    );
+
+    let step = if levels > 1 {
+        (to - from) / Decimal::from(levels - 1)
+    } else {
+        Decimal::ZERO
+    };
+
+    let mut prices: Vec<Decimal> = Vec::with_capacity(levels as usize);
+    for i in 0..levels {
+        let raw = from + step * Decimal::from(i);
+        let price =
+            (raw / tick).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero) * tick;
+        anyhow::ensure!(
+            price > Decimal::ZERO && price < Decimal::ONE,
+            "Ladder price {price} is outside the valid range (0, 1)"
+        );
+        if prices.last() == Some(&price) {
+            anyhow::bail!(
+                "{levels} levels between {from} and {to} do not fit on the {tick} tick grid"
+            );
         }
+        prices.push(price);
+    }
 
-        ClobCommand::LastTrades { token_ids } => {
-            let client = clob::Client::default();
-            let requests: Vec<_> = parse_token_ids(&token_ids)?
-                .into_iter()
-                .map(|id| LastTradePriceRequest::builder().token_id(id).build())
-                .collect();
-            let result = client.last_trades_prices(&requests).await?;
-            print_last_trades_prices(&result, output)?;
+    let weights: Vec<Decimal> = match distribution {
+        Distribution::Flat => vec![Decimal::ONE; prices.len()],
+        Distribution::Linear => (1..=levels).map(Decimal::from).collect(),
+        Distribution::Geometric => {
+            anyhow::ensure!(ratio > Decimal::ZERO, "--ratio must be positive");
+            let mut weight = Decimal::ONE;
+            (0..levels)
+                .map(|_| {
+                    let current = weight;
+                    weight *= ratio;
+                    current
+                })
+                .collect()
         }
+    };
+    let total_weight: Decimal = weights.iter().sum();
+
+    let mut sizes: Vec<Decimal> = weights
+        .iter()
+        .map(|w| {
+            (total_size * w / total_weight)
+                .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero)
+        })
+        .collect();
+    let allocated: Decimal = sizes.iter().sum();
+    if let Some(last) = sizes.last_mut() {
+        *last += total_size - allocated;
+    }
+    anyhow::ensure!(
+        sizes.iter().all(|s| *s > Decimal::ZERO),
+        "--total-size {total_size} is too small to spread across {levels} levels"
+    );
 
-        ClobCommand::Market { condition_id } => {
-            let client = clob::Client::default();
-            let result = client.market(&condition_id).await?;
-            print_clob_market(&result, output)?;
-        }
+    Ok(prices.into_iter().zip(sizes).collect())
+}
 
-        ClobCommand::Markets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.markets(cursor).await?;
-            print_clob_markets(&result, output)?;
+/// Walks `levels` (best price last, as returned by the order book) until
+/// `amount` is filled, measured in `unit`.
+fn estimate_fill(
+    levels: &[OrderSummary],
+    side: Side,
+    amount: Decimal,
+    unit: QuoteUnit,
+) -> Result<FillEstimate> {
+    let best = levels
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("No opposing orders on the book"))?;
+    let mut estimate = FillEstimate {
+        side,
+        shares: Decimal::ZERO,
+        notional: Decimal::ZERO,
+        best_price: best.price,
+        worst_price: best.price,
+        complete: false,
+        fills: Vec::new(),
+    };
+    let filled = |e: &FillEstimate| match unit {
+        QuoteUnit::Shares => e.shares,
+        QuoteUnit::Usdc => e.notional,
+    };
+    for level in levels.iter().rev() {
+        let remaining = amount - filled(&estimate);
+        if remaining <= Decimal::ZERO {
+            break;
         }
+        let shares = match unit {
+            QuoteUnit::Shares => level.size.min(remaining),
+            QuoteUnit::Usdc => (level.size * level.price).min(remaining) / level.price,
+        };
+        estimate.shares += shares;
+        estimate.notional += shares * level.price;
+        estimate.worst_price = level.price;
+        estimate.fills.push((level.price, shares));
+    }
+    estimate.complete = filled(&estimate) >= amount;
+    Ok(estimate)
+}
 
-        ClobCommand::SamplingMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.sampling_markets(cursor).await?;
-            print_clob_markets(&result, output)?;
-        }
+fn quote_book(
+    book: &OrderBookSummaryResponse,
+    side: Side,
+    amount: Decimal,
+    unit: QuoteUnit,
+    fee_rate_bps: u32,
+) -> Result<Quote> {
+    let levels = match side {
+        Side::Sell => &book.bids,
+        _ => &book.asks,
+    };
+    let midpoint = match (book.bids.last(), book.asks.last()) {
+        (Some(bid), Some(ask)) => Some((bid.price + ask.price) / Decimal::TWO),
+        _ => None,
+    };
+    Ok(Quote {
+        token_id: book.asset_id,
+        estimate: estimate_fill(levels, side, amount, unit)?,
+        midpoint,
+        fee_rate_bps,
+    })
+}
 
-        ClobCommand::SimplifiedMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.simplified_markets(cursor).await?;
-            print_simplified_markets(&result, output)?;
-        }
+async fn fetch_quotes(
+    token_ids: &str,
+    side: CliSide,
+    amount: &str,
+    unit: Option<QuoteUnit>,
+) -> Result<Vec<Quote>> {
+    let client = clob::Client::default();
+    let side = Side::from(side);
+    let amount =
+        Decimal::from_str(amount).map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
+    let unit = unit.unwrap_or(match side {
+        Side::Sell => QuoteUnit::Shares,
+        _ => QuoteUnit::Usdc,
+    });
+    let requests: Vec<_> = parse_token_ids(token_ids)?
+        .into_iter()
+        .map(|id| OrderBookSummaryRequest::builder().token_id(id).build())
+        .collect();
+    let books = client.order_books(&requests).await?;
+
+    let mut quotes = Vec::with_capacity(books.len());
+    for book in &books {
+        let fee = client.fee_rate_bps(book.asset_id).await?.base_fee;
+        quotes.push(quote_book(book, side, amount, unit, fee)?);
+    }
+    anyhow::ensure!(!quotes.is_empty(), "No order books returned");
+    Ok(quotes)
+}
 
-        ClobCommand::SamplingSimpMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.sampling_simplified_markets(cursor).await?;
-            print_simplified_markets(&result, output)?;
-        }
+/// Time since the last touch, counting the watchdog's own start as a touch so
+/// a strategy gets one full timeout to come up.
+fn touch_age(modified: Option<SystemTime>, started: SystemTime, now: SystemTime) -> Duration {
+    let last = modified.map_or(started, |m| m.max(started));
+    now.duration_since(last).unwrap_or_default()
+}
 
-        ClobCommand::TickSize { token_id } => {
-            let client = clob::Client::default();
-            let result = client.tick_size(parse_token_id(&token_id)?).await?;
-            print_tick_size(&result, output)?;
-        }
+fn log_heartbeat_event(log: &Path, message: &str) {
+    let line = format!("{} {message}", chrono::Utc::now().to_rfc3339());
+    eprintln!("{line}");
+    let written = log
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log)
+        })
+        .and_then(|mut f| writeln!(f, "{line}"));
+    if let Err(e) = written {
+        eprintln!("Could not write to {}: {e}", log.display());
+    }
+}
 
-        ClobCommand::FeeRate { token_id } => {
-            let client = clob::Client::default();
-            let result = client.fee_rate_bps(parse_token_id(&token_id)?).await?;
-            print_fee_rate(&result, output)?;
+/// Watches `touch_file` and cancels orders once per stale episode.
+async fn run_heartbeat(
+    client: &clob::Client<Authenticated<Normal>>,
+    touch_file: &Path,
+    interval: Duration,
+    timeout: Duration,
+    markets: Option<Vec<B256>>,
+    log: &Path,
+) -> Result<()> {
+    let _guard = interrupt::install();
+    let started = SystemTime::now();
+    let scope = markets.as_ref().map_or_else(
+        || "all orders".to_string(),
+        |m| format!("{} market(s)", m.len()),
+    );
+    log_heartbeat_event(
+        log,
+        &format!(
+            "armed: watching {} (timeout {}s, cancels {scope})",
+            touch_file.display(),
+            timeout.as_secs()
+        ),
+    );
+
+    let mut fired = false;
+    loop {
+        let modified = std::fs::metadata(touch_file)
+            .and_then(|m| m.modified())
+            .ok();
+        let age = touch_age(modified, started, SystemTime::now());
+        if age <= timeout {
+            if fired {
+                log_heartbeat_event(log, "touch resumed: re-armed");
+                fired = false;
+            }
+        } else if !fired {
+            log_heartbeat_event(
+                log,
+                &format!("no touch for {}s: canceling {scope}", age.as_secs()),
+            );
+            let result = match &markets {
+                None => cancel_journaled(
+                    "heartbeat",
+                    &json!({"all": true}),
+                    client.cancel_all_orders(),
+                )
+                .await
+                .map(|r| r.canceled.len()),
+                Some(markets) => {
+                    let mut canceled = 0;
+                    let mut result = Ok(());
+                    for market in markets {
+                        let request = CancelMarketOrderRequest::builder().market(*market).build();
+                        match cancel_journaled(
+                            "heartbeat",
+                            &request,
+                            client.cancel_market_orders(&request),
+                        )
+                        .await
+                        {
+                            Ok(r) => canceled += r.canceled.len(),
+                            Err(e) => result = Err(e),
+                        }
+                    }
+                    result.map(|()| canceled)
+                }
+            };
+            match result {
+                Ok(n) => {
+                    log_heartbeat_event(log, &format!("canceled {n} order(s)"));
+                    fired = true;
+                }
+                Err(e) => log_heartbeat_event(log, &format!("cancel failed, will retry: {e}")),
+            }
         }
-
-        ClobCommand::NegRisk { token_id } => {
-            let client = clob::Client::default();
-            let result = client.neg_risk(parse_token_id(&token_id)?).await?;
-            print_neg_risk(&result, output)?;
+        if !interrupt::sleep(interval).await {
+            log_heartbeat_event(log, "stopped");
+            return Ok(());
         }
+    }
+}
 
-        ClobCommand::PriceHistory {
-            token_id,
-            interval,
-            fidelity,
-        } => {
-            let client = clob::Client::default();
-            let request = PriceHistoryRequest::builder()
-                .market(parse_token_id(&token_id)?)
-                .time_range(TimeRange::from_interval(Interval::from(interval)))
-                .maybe_fidelity(fidelity)
-                .build();
-            let result = client.price_history(&request).await?;
-            print_price_history(&result, output)?;
+/// Fetches every open order matching `request`, following pagination.
+async fn all_open_orders(
+    client: &clob::Client<Authenticated<Normal>>,
+    request: &OrdersRequest,
+) -> Result<Vec<OpenOrderResponse>> {
+    let mut orders = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client.orders(request, cursor).await?;
+        orders.extend(page.data);
+        if page.next_cursor.is_empty() || page.next_cursor == END_CURSOR {
+            return Ok(orders);
         }
+        cursor = Some(page.next_cursor);
+    }
+}
 
-        ClobCommand::Time => {
-            let client = clob::Client::default();
-            let result = client.server_time().await?;
-            print_server_time(result, output)?;
-        }
+/// Worst-price bound for a market order from `--max-price` / `--min-price`.
+fn market_order_bound(
+    side: Side,
+    max_price: Option<String>,
+    min_price: Option<String>,
+) -> Result<Option<Decimal>> {
+    match (side, max_price, min_price) {
+        (Side::Sell, Some(_), _) => anyhow::bail!("--max-price only applies to buys"),
+        (Side::Buy, _, Some(_)) => anyhow::bail!("--min-price only applies to sells"),
+        (_, Some(p), _) | (_, _, Some(p)) => Ok(Some(
+            Decimal::from_str(&p).map_err(|_| anyhow::anyhow!("Invalid price: {p}"))?,
+        )),
+        _ => Ok(None),
+    }
+}
 
-        ClobCommand::Geoblock => {
-            let client = clob::Client::default();
-            let result = client.check_geoblock().await?;
-            print_geoblock(&result, output)?;
-        }
+/// Refuses a market order the book can't fill in full, since only the
+/// available part would execute.
+fn ensure_fillable(estimate: &FillEstimate, amount: Decimal, unit: QuoteUnit) -> Result<()> {
+    let (filled, unit) = match unit {
+        QuoteUnit::Shares => (estimate.shares, "shares"),
+        QuoteUnit::Usdc => (estimate.notional, "USDC"),
+    };
+    anyhow::ensure!(
+        estimate.complete,
+        "The book can fill only {} of {amount} {unit}; order not sent \
+         (pass --allow-partial to send anyway)",
+        filled.normalize()
+    );
+    Ok(())
+}
 
-        _ => unreachable!(),
+/// Walk the live book for a market order and enforce its depth, price and
+/// slippage guards before anything is sent.
+async fn market_order_estimate(
+    token_id: U256,
+    side: Side,
+    amount: Decimal,
+    bound: Option<Decimal>,
+    max_slippage_bps: Option<u32>,
+    allow_partial: bool,
+) -> Result<(OrderBookSummaryResponse, FillEstimate)> {
+    let book = clob::Client::default()
+        .order_book(
+            &OrderBookSummaryRequest::builder()
+                .token_id(token_id)
+                .build(),
+        )
+        .await?;
+    let (levels, unit) = match side {
+        Side::Sell => (&book.bids, QuoteUnit::Shares),
+        _ => (&book.asks, QuoteUnit::Usdc),
+    };
+    let estimate = estimate_fill(levels, side, amount, unit)?;
+    if !allow_partial {
+        ensure_fillable(&estimate, amount, unit)?;
+    }
+    if let Some(bound) = bound {
+        let breached = match side {
+            Side::Sell => estimate.worst_price < bound,
+            _ => estimate.worst_price > bound,
+        };
+        anyhow::ensure!(
+            !breached,
+            "Estimated worst fill {} breaches the {bound} limit; order not sent",
+            estimate.worst_price
+        );
+    }
+    if let Some(max_bps) = max_slippage_bps {
+        anyhow::ensure!(
+            estimate.slippage_bps() <= Decimal::from(max_bps),
+            "Estimated slippage {} bps exceeds --max-slippage-bps {max_bps}; order not sent",
+            estimate.slippage_bps()
+        );
     }
+    Ok((book, estimate))
+}
 
-    Ok(())
+/// Post a signed order, recording the order and the response in the journal.
+async fn post_journaled(
+    client: &clob::Client<Authenticated<Normal>>,
+    order: SignedOrder,
+    command: &str,
+) -> polymarket_client_sdk::Result<PostOrderResponse> {
+    let request = serde_json::to_value(&order).unwrap_or_default();
+    let result = client.post_order(order).await;
+    let (ids, response) = match &result {
+        Ok(r) => (vec![r.order_id.clone()], post_order_to_json(r)),
+        Err(e) => (Vec::new(), json!({"error": e.to_string()})),
+    };
+    journal::record(&Entry::new(Action::Post, command, ids, request, response));
+    result
 }
 
-async fn execute_trade(
-    command: ClobCommand,
-    output: &OutputFormat,
-    private_key: Option<&str>,
-    signature_type: Option<&str>,
-) -> Result<()> {
-    match command {
-        ClobCommand::Orders {
-            market,
-            asset,
-            cursor,
-        } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = OrdersRequest::builder()
-                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
-                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
-                .build();
-            let result = client.orders(&request, cursor).await?;
-            print_orders(&result, output)?;
+/// Batch version of [`post_journaled`]; the batch is journaled as one entry.
+async fn post_many_journaled(
+    client: &clob::Client<Authenticated<Normal>>,
+    orders: Vec<SignedOrder>,
+    command: &str,
+) -> polymarket_client_sdk::Result<Vec<PostOrderResponse>> {
+    let request = serde_json::to_value(&orders).unwrap_or_default();
+    let result = client.post_orders(orders).await;
+    let (ids, response) = match &result {
+        Ok(rs) => (
+            rs.iter().map(|r| r.order_id.clone()).collect(),
+            rs.iter().map(post_order_to_json).collect(),
+        ),
+        Err(e) => (Vec::new(), json!({"error": e.to_string()})),
+    };
+    journal::record(&Entry::new(Action::Post, command, ids, request, response));
+    result
+}
+
+/// Run a cancel call, recording its parameters and response in the journal.
+async fn cancel_journaled(
+    command: &str,
+    request: &impl serde::Serialize,
+    call: impl Future<Output = polymarket_client_sdk::Result<CancelOrdersResponse>>,
+) -> polymarket_client_sdk::Result<CancelOrdersResponse> {
+    let request = serde_json::to_value(request).unwrap_or_default();
+    let result = call.await;
+    let (ids, response) = match &result {
+        Ok(r) => (
+            r.canceled
+                .iter()
+                .chain(r.not_canceled.keys())
+                .cloned()
+                .collect(),
+            cancel_result_to_json(r),
+        ),
+        Err(e) => (Vec::new(), json!({"error": e.to_string()})),
+    };
+    journal::record(&Entry::new(Action::Cancel, command, ids, request, response));
+    result
+}
+
+/// State `clob wait` stops at, in addition to the terminal ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum WaitUntil {
+    /// Fully filled (or canceled)
+    Filled,
+    /// Any fill at all (or canceled)
+    Partial,
+    /// Canceled (or fully filled)
+    #[value(alias = "canceled")]
+    Cancelled,
+}
+
+/// Whether `order` has reached `until` or a terminal state, and how.
+fn wait_outcome(order: &OpenOrderResponse, until: WaitUntil) -> Option<WaitOutcome> {
+    let filled = order.status == OrderStatusType::Matched
+        || (order.original_size > Decimal::ZERO && order.size_matched >= order.original_size);
+    if filled {
+        Some(WaitOutcome::Filled)
+    } else if matches!(
+        order.status,
+        OrderStatusType::Canceled | OrderStatusType::Unmatched
+    ) {
+        Some(WaitOutcome::Canceled)
+    } else if until == WaitUntil::Partial && order.size_matched > Decimal::ZERO {
+        Some(WaitOutcome::PartiallyFilled)
+    } else {
+        None
+    }
+}
+
+/// Poll `order_id` until [`wait_outcome`] settles or `timeout` passes,
+/// printing fill progress to stderr.
+async fn wait_for_order(
+    client: &clob::Client<Authenticated<Normal>>,
+    order_id: &str,
+    until: WaitUntil,
+    timeout: Duration,
+    interval: Duration,
+) -> Result<(OpenOrderResponse, WaitOutcome)> {
+    let deadline = tokio::time::Instant::now()
+        .checked_add(timeout)
+        .ok_or_else(|| anyhow::anyhow!("Timeout too large: {}s", timeout.as_secs()))?;
+    let mut last_matched = None;
+    loop {
+        let order = client.order(order_id).await?;
+        if last_matched != Some(order.size_matched) {
+            eprintln!(
+                "{} matched {} / {} ({})",
+                chrono::Utc::now().format("%H:%M:%S"),
+                order.size_matched,
+                order.original_size,
+                order.status
+            );
+            last_matched = Some(order.size_matched);
+        }
+        if let Some(outcome) = wait_outcome(&order, until) {
+            return Ok((order, outcome));
         }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            let outcome = if order.size_matched > Decimal::ZERO {
+                WaitOutcome::PartiallyFilled
+            } else {
+                WaitOutcome::TimedOut
+            };
+            return Ok((order, outcome));
+        }
+        tokio::time::sleep(interval.min(deadline - now)).await;
+    }
+}
 
-        ClobCommand::Order { order_id } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.order(&order_id).await?;
-            print_order_detail(&result, output)?;
+/// Succeeds if the wait ended in `until`, and otherwise fails with the exit
+/// status for how it did end.
+fn wait_exit(outcome: WaitOutcome, until: WaitUntil) -> Result<()> {
+    let reached = match outcome {
+        // A full fill is also "any fill at all".
+        WaitOutcome::Filled => until != WaitUntil::Cancelled,
+        WaitOutcome::PartiallyFilled => until == WaitUntil::Partial,
+        WaitOutcome::Canceled => until == WaitUntil::Cancelled,
+        WaitOutcome::TimedOut => false,
+    };
+    if reached {
+        Ok(())
+    } else {
+        Err(ExitStatus(outcome.exit_code()).into())
+    }
+}
+
+/// `--wait` on order placement: block until the posted order is terminal.
+///
+/// In table mode the caller has already printed the post result. In JSON mode
+/// it hasn't, and `posted` is printed here with the wait nested under `wait`
+/// so the output stays a single document.
+async fn wait_after_post(
+    client: &clob::Client<Authenticated<Normal>>,
+    result: &PostOrderResponse,
+    mut posted: serde_json::Value,
+    timeout: Duration,
+    output: &OutputFormat,
+) -> Result<()> {
+    let waited = if result.success && !result.order_id.is_empty() {
+        wait_for_order(
+            client,
+            &result.order_id,
+            WaitUntil::Filled,
+            timeout,
+            Duration::from_secs(2),
+        )
+        .await
+    } else {
+        Err(anyhow::anyhow!(
+            "Order was not accepted; nothing to wait for"
+        ))
+    };
+    match output {
+        OutputFormat::Table => {
+            let (order, outcome) = waited?;
+            print_wait_result(&order, outcome, output)?;
+            wait_exit(outcome, WaitUntil::Filled)
         }
+        OutputFormat::Json => match waited {
+            Ok((order, outcome)) => {
+                posted["wait"] = wait_result_to_json(&order, outcome);
+                crate::output::print_json(&posted)?;
+                wait_exit(outcome, WaitUntil::Filled)
+            }
+            Err(e) => {
+                posted["wait"] = json!({"error": e.to_string()});
+                crate::output::print_json(&posted)?;
+                Err(ExitStatus(1).into())
+            }
+        },
+    }
+}
 
-        ClobCommand::CreateOrder {
-            token,
-            side,
-            price,
-            size,
-            order_type,
-            post_only,
-        } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+/// Client-side filter for `clob cancel-where`; unset fields match everything.
+#[derive(Default)]
+struct OrderFilter {
+    side: Option<Side>,
+    price_above: Option<Decimal>,
+    price_below: Option<Decimal>,
+    placed_before: Option<chrono::DateTime<chrono::Utc>>,
+    token: Option<U256>,
+    markets: Option<HashSet<B256>>,
+}
 
-            let price_dec =
-                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
-            let size_dec =
-                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+impl OrderFilter {
+    fn matches(&self, order: &OpenOrderResponse) -> bool {
+        self.side.is_none_or(|s| order.side == s)
+            && self.price_above.is_none_or(|p| order.price > p)
+            && self.price_below.is_none_or(|p| order.price < p)
+            && self.placed_before.is_none_or(|t| order.created_at < t)
+            && self.token.is_none_or(|t| order.asset_id == t)
+            && self
+                .markets
+                .as_ref()
+                .is_none_or(|m| m.contains(&order.market))
+    }
+}
 
-            let order = client
-                .limit_order()
-                .token_id(parse_token_id(&token)?)
-                .side(Side::from(side))
-                .price(price_dec)
-                .size(size_dec)
-                .order_type(OrderType::from(order_type))
-                .post_only(post_only)
-                .build()
-                .await?;
-            let order = client.sign(&signer, order).await?;
-            let result = client.post_order(order).await?;
-            print_post_order_result(&result, output)?;
+/// Maximum order IDs sent in one scoring or cancel request.
+const ORDER_ID_CHUNK: usize = 100;
+
+/// Shares to sell when closing `percent` of `held`, truncated to the lot size.
+fn close_size(held: Decimal, percent: Decimal) -> Decimal {
+    (held * percent / Decimal::ONE_HUNDRED)
+        .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero)
+}
+
+/// Lowest acceptable sell price: `bps` below the best bid, rounded up to the tick.
+fn slippage_floor(best_bid: Decimal, bps: u32, tick: Decimal) -> Decimal {
+    let raw = best_bid * (Decimal::ONE - Decimal::from(bps) / Decimal::from(10_000));
+    ((raw / tick).ceil() * tick).max(tick)
+}
+
+/// Every open position held by `user`, following offset pagination.
+async fn all_positions(user: Address) -> Result<Vec<Position>> {
+    const PAGE: i32 = 500;
+    let client = data::Client::default();
+    let mut positions = Vec::new();
+    for page in 0.. {
+        let request = PositionsRequest::builder()
+            .user(user)
+            .limit(PAGE)?
+            .offset(page * PAGE)?
+            .build();
+        let batch = client.positions(&request).await?;
+        let done = batch.len() < PAGE as usize;
+        positions.extend(batch);
+        if done {
+            break;
         }
+    }
+    Ok(positions)
+}
 
-        ClobCommand::PostOrders {
-            tokens,
-            side,
-            prices,
-            sizes,
-            order_type,
-        } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+/// Condition IDs of every open market in events carrying `tag`.
+async fn tagged_condition_ids(tag: &str) -> Result<HashSet<B256>> {
+    const PAGE: i32 = 100;
+    let client = gamma::Client::default();
+    let mut ids = HashSet::new();
+    for page in 0.. {
+        let request = EventsRequest::builder()
+            .tag_slug(tag.to_string())
+            .closed(false)
+            .limit(PAGE)
+            .offset(page * PAGE)
+            .build();
+        let events = client.events(&request).await?;
+        ids.extend(
+            events
+                .iter()
+                .flat_map(|e| e.markets.iter().flatten())
+                .filter_map(|m| m.condition_id),
+        );
+        if events.len() < PAGE as usize {
+            break;
+        }
+    }
+    Ok(ids)
+}
 
-            let token_ids = parse_token_ids(&tokens)?;
-            let price_strs: Vec<&str> = prices.split(',').map(str::trim).collect();
-            let size_strs: Vec<&str> = sizes.split(',').map(str::trim).collect();
+/// Sizes a complement trade from the live books: the sets whose asks still
+/// sum below $1 after fees, capped at `max_size`. Also returns whether the
+/// market is neg-risk, which decides how the sets are merged.
+async fn complement_plan(
+    outcomes: &[(String, U256)],
+    max_size: Decimal,
+    min_edge: Decimal,
+) -> Result<(ComplementReport, bool)> {
+    let client = clob::Client::default();
+    let requests: Vec<_> = outcomes
+        .iter()
+        .map(|(_, id)| OrderBookSummaryRequest::builder().token_id(*id).build())
+        .collect();
+    let mut books = client.order_books(&requests).await?;
+    books.sort_by_key(|b| outcomes.iter().position(|(_, id)| *id == b.asset_id));
+    anyhow::ensure!(books.len() == 2, "Could not fetch both order books");
+    let mut fees = Vec::with_capacity(2);
+    for (_, id) in outcomes {
+        fees.push(client.fee_rate_bps(*id).await?.base_fee);
+    }
 
-            if token_ids.len() != price_strs.len() || token_ids.len() != size_strs.len() {
-                anyhow::bail!(
-                    "tokens, prices, and sizes must have the same number of comma-separated values"
-                );
-            }
+    let asks: Vec<&[OrderSummary]> = books.iter().map(|b| b.asks.as_slice()).collect();
+    let ask_sum: Decimal = asks
+        .iter()
+        .map(|a| a.last().map_or(Decimal::ONE, |l| l.price))
+        .sum();
+    let fill = walk_sets(&asks, &fees, SetSide::BuyAll, min_edge)
+        .ok_or_else(|| anyhow::anyhow!("No complement edge: best asks sum to {ask_sum}"))?;
+    let sets = fill
+        .sets
+        .min(max_size)
+        .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+    for book in &books {
+        anyhow::ensure!(
+            sets >= book.min_order_size,
+            "{sets} sets is below the {} minimum order size",
+            book.min_order_size
+        );
+    }
 
-            let sdk_side = Side::from(side);
-            let sdk_order_type = OrderType::from(order_type);
+    let mut legs = Vec::with_capacity(2);
+    let mut est_profit = sets;
+    for (((outcome, token_id), book), fee) in outcomes.iter().zip(&books).zip(&fees) {
+        let estimate = estimate_fill(&book.asks, Side::Buy, sets, QuoteUnit::Shares)?;
+        est_profit -= estimate.notional + estimate.fee(*fee);
+        legs.push(ComplementLeg {
+            outcome: outcome.clone(),
+            token_id: *token_id,
+            limit: estimate.worst_price,
+            est_cost: estimate.notional,
+            filled: None,
+        });
+    }
+    let report = ComplementReport {
+        condition_id: books[0].market,
+        sets,
+        ask_sum,
+        est_profit,
+        legs,
+        merged: None,
+    };
+    Ok((report, books[0].neg_risk))
+}
 
-            let mut signed_orders = Vec::with_capacity(token_ids.len());
-            for ((token_id, price_str), size_str) in
-                token_ids.into_iter().zip(price_strs).zip(size_strs)
-            {
-                let price_dec = Decimal::from_str(price_str)
-                    .map_err(|_| anyhow::anyhow!("Invalid price: {price_str}"))?;
-                let size_dec = Decimal::from_str(size_str)
-                    .map_err(|_| anyhow::anyhow!("Invalid size: {size_str}"))?;
+/// Buys one leg of a complement trade fill-or-kill, returning the shares
+/// received and USDC paid.
+async fn buy_complement_leg(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    leg: &ComplementLeg,
+    sets: Decimal,
+) -> Result<(Decimal, Decimal)> {
+    let order = client
+        .market_order()
+        .token_id(leg.token_id)
+        .side(Side::Buy)
+        .amount(Amount::shares(sets)?)
+        .price(leg.limit)
+        .order_type(OrderType::FOK)
+        .build()
+        .await?;
+    let result =
+        post_journaled(client, client.sign(signer, order).await?, "arb-complement").await?;
+    let (shares, usdc) = filled_amounts(Side::Buy, &result);
+    anyhow::ensure!(
+        result.success && shares > Decimal::ZERO,
+        "{} order not filled: {}",
+        leg.outcome,
+        result.error_msg.as_deref().unwrap_or("no match")
+    );
+    Ok((shares, usdc))
+}
 
+/// Places both legs, unwinding the first if the second does not fill, then
+/// merges the matched sets back into USDC.
+async fn run_complement(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    report: &mut ComplementReport,
+    neg_risk: bool,
+    private_key: Option<&str>,
+) -> Result<()> {
+    let first = buy_complement_leg(client, signer, &report.legs[0], report.sets)
+        .await
+        .map_err(|e| e.context("First leg failed; nothing was bought"))?;
+    report.legs[0].filled = Some(first);
+
+    let second = match buy_complement_leg(client, signer, &report.legs[1], report.sets).await {
+        Ok(filled) => filled,
+        Err(e) => {
+            let leg = &report.legs[0];
+            eprintln!("{e:#}; unwinding {} {}", first.0, leg.outcome);
+            let unwind = async {
                 let order = client
-                    .limit_order()
-                    .token_id(token_id)
-                    .side(sdk_side)
-                    .price(price_dec)
-                    .size(size_dec)
-                    .order_type(sdk_order_type.clone())
+                    .market_order()
+                    .token_id(leg.token_id)
+                    .side(Side::Sell)
+                    .amount(Amount::shares(first.0)?)
+                    .order_type(OrderType::FAK)
                     .build()
                     .await?;
-                signed_orders.push(client.sign(&signer, order).await?);
-            }
+                post_journaled(client, client.sign(signer, order).await?, "arb-complement").await
+            };
+            let result = unwind.await.with_context(|| {
+                format!(
+                    "Second leg failed and selling the first leg back failed too; \
+                     you still hold {} {} shares unhedged",
+                    first.0, leg.outcome
+                )
+            })?;
+            let (sold, proceeds) = filled_amounts(Side::Sell, &result);
+            anyhow::bail!(
+                "Second leg failed; sold {sold} of {} {} back for {proceeds} USDC \
+                 (unwind loss {})",
+                first.0,
+                leg.outcome,
+                first.1 - proceeds
+            );
+        }
+    };
+    report.legs[1].filled = Some(second);
+
+    // Fills settle on-chain shortly after matching; the merge needs both
+    // balances in the wallet.
+    let matched = first.0.min(second.0);
+    let retry = || {
+        format!(
+            "run `polymarket ctf merge --condition {} --amount {matched}` to retry",
+            report.condition_id
+        )
+    };
+    let mut settled = false;
+    for _ in 0..30 {
+        settled = true;
+        for leg in &report.legs {
+            settled &= token_inventory(client, leg.token_id).await? >= matched;
+        }
+        if settled {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    anyhow::ensure!(
+        settled,
+        "Both legs filled but the shares haven't settled in the wallet after 60s; {}",
+        retry()
+    );
+    let raw: u64 = (matched * Decimal::from(10u64.pow(SHARE_SCALE)))
+        .trunc()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Merge amount out of range: {matched}"))?;
+    let (tx, _) =
+        super::ctf::merge_binary_sets(private_key, report.condition_id, neg_risk, U256::from(raw))
+            .await
+            .with_context(|| format!("Both legs filled but the merge failed; {}", retry()))?;
+    report.merged = Some((matched, tx));
+    Ok(())
+}
 
-            let results = client.post_orders(signed_orders).await?;
-            print_post_orders_result(&results, output)?;
+/// Conditional token balances are reported in 6-decimal base units.
+const SHARE_SCALE: u32 = 6;
+
+/// Parses an amount given either in ticks (`2t`) or as a price (`0.02`).
+fn parse_ticks(s: &str, tick: Decimal) -> Result<Decimal> {
+    let value = match s.strip_suffix('t') {
+        Some(ticks) => Decimal::from_str(ticks).map(|t| t * tick),
+        None => Decimal::from_str(s),
+    };
+    value.map_err(|_| anyhow::anyhow!("Invalid tick amount: {s} (expected e.g. 2t or 0.02)"))
+}
+
+/// A two-sided quote; either side is `None` when inventory limits rule it out.
+#[derive(Debug, PartialEq)]
+struct Quotes {
+    bid: Option<(Decimal, Decimal)>,
+    ask: Option<(Decimal, Decimal)>,
+}
+
+/// Prices a bid and ask `half_spread` either side of `mid`.
+///
+/// Prices are rounded away from the mid onto the tick grid. Bids stop at
+/// `max_inventory` and asks never sell more than is held. With `skew`, both
+/// quotes shift down by up to `half_spread` as inventory approaches the cap.
+#[allow(clippy::too_many_arguments)]
+fn make_quotes(
+    mid: Decimal,
+    tick: Decimal,
+    half_spread: Decimal,
+    size: Decimal,
+    inventory: Decimal,
+    max_inventory: Decimal,
+    skew: bool,
+) -> Quotes {
+    let shift = if skew && max_inventory > Decimal::ZERO {
+        half_spread * (inventory / max_inventory).clamp(Decimal::ZERO, Decimal::ONE)
+    } else {
+        Decimal::ZERO
+    };
+    let clamp = |p: Decimal| p.clamp(tick, Decimal::ONE - tick);
+    let bid_price = clamp(((mid - half_spread - shift) / tick).floor() * tick);
+    let ask_price = clamp(((mid + half_spread - shift) / tick).ceil() * tick);
+    let lot = |s: Decimal| s.round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+    let bid_size = lot(size.min(max_inventory - inventory));
+    let ask_size = lot(size.min(inventory));
+
+    Quotes {
+        bid: (bid_size > Decimal::ZERO && bid_price < ask_price).then_some((bid_price, bid_size)),
+        ask: (ask_size > Decimal::ZERO).then_some((ask_price, ask_size)),
+    }
+}
+
+async fn token_inventory(
+    client: &clob::Client<Authenticated<Normal>>,
+    token_id: U256,
+) -> Result<Decimal> {
+    let request = BalanceAllowanceRequest::builder()
+        .asset_type(AssetType::Conditional)
+        .token_id(token_id)
+        .build();
+    let balance = client.balance_allowance(request).await?.balance;
+    Ok(balance / Decimal::from(10u64.pow(SHARE_SCALE)))
+}
+
+/// Order book for one token, kept current from `clob stream` events.
+#[derive(Debug, Default)]
+struct LocalBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    last_trade: Option<(Decimal, Option<Side>)>,
+    updated: Option<i64>,
+    /// Timestamp of the snapshot the levels were last rebuilt from. Changes
+    /// older than it arrived late and are already part of the snapshot.
+    snapshot: Option<i64>,
+}
+
+impl LocalBook {
+    /// Whether an update stamped `timestamp` predates the current snapshot.
+    fn is_stale(&self, timestamp: i64) -> bool {
+        self.snapshot.is_some_and(|snapshot| timestamp < snapshot)
+    }
+
+    /// Replaces both sides with a full snapshot, unless it is older than the
+    /// one already applied. Returns whether it was applied.
+    fn replace(
+        &mut self,
+        bids: impl IntoIterator<Item = (Decimal, Decimal)>,
+        asks: impl IntoIterator<Item = (Decimal, Decimal)>,
+        timestamp: i64,
+    ) -> bool {
+        if self.is_stale(timestamp) {
+            return false;
         }
+        self.bids = bids.into_iter().collect();
+        self.asks = asks.into_iter().collect();
+        self.snapshot = Some(timestamp);
+        self.updated = Some(timestamp);
+        true
+    }
 
-        ClobCommand::MarketOrder {
-            token,
-            side,
-            amount,
-            order_type,
-        } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+    fn apply_snapshot(&mut self, book: &BookUpdate) -> bool {
+        let levels = |levels: &[OrderBookLevel]| -> Vec<_> {
+            levels.iter().map(|l| (l.price, l.size)).collect()
+        };
+        self.replace(levels(&book.bids), levels(&book.asks), book.timestamp)
+    }
 
-            let amount_dec = Decimal::from_str(&amount)
-                .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
-            let sdk_side = Side::from(side);
-            let parsed_amount = if matches!(sdk_side, Side::Sell) {
-                Amount::shares(amount_dec)?
-            } else {
-                Amount::usdc(amount_dec)?
-            };
+    /// Rebuilds the book from a REST snapshot after the stream dropped updates.
+    fn apply_resync(&mut self, book: &OrderBookSummaryResponse) -> bool {
+        let levels = |levels: &[OrderSummary]| -> Vec<_> {
+            levels.iter().map(|l| (l.price, l.size)).collect()
+        };
+        self.replace(
+            levels(&book.bids),
+            levels(&book.asks),
+            book.timestamp.timestamp_millis(),
+        )
+    }
 
-            let order = client
-                .market_order()
-                .token_id(parse_token_id(&token)?)
-                .side(sdk_side)
-                .amount(parsed_amount)
-                .order_type(OrderType::from(order_type))
-                .build()
-                .await?;
-            let order = client.sign(&signer, order).await?;
-            let result = client.post_order(order).await?;
-            print_post_order_result(&result, output)?;
+    /// Sets one level to its new total size; a zero size removes it. Returns
+    /// `false` for a change older than the last snapshot, which is skipped.
+    fn apply_change(&mut self, change: &PriceChangeBatchEntry, timestamp: i64) -> bool {
+        if self.is_stale(timestamp) {
+            return false;
         }
+        self.updated = Some(timestamp);
+        let Some(size) = change.size else {
+            return true;
+        };
+        let side = match change.side {
+            Side::Buy => &mut self.bids,
+            _ => &mut self.asks,
+        };
+        if size.is_zero() {
+            side.remove(&change.price);
+        } else {
+            side.insert(change.price, size);
+        }
+        true
+    }
 
-        ClobCommand::Cancel { order_id } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.cancel_order(&order_id).await?;
-            print_cancel_result(&result, output)?;
+    fn row(&self, token_id: U256) -> StreamRow {
+        StreamRow {
+            token_id,
+            best_bid: self.bids.last_key_value().map(|(p, s)| (*p, *s)),
+            best_ask: self.asks.first_key_value().map(|(p, s)| (*p, *s)),
+            last_trade: self.last_trade,
+            updated: self.updated,
         }
+    }
+}
 
-        ClobCommand::CancelOrders { order_ids } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
-            let result = client.cancel_orders(&ids).await?;
-            print_cancel_result(&result, output)?;
+/// NDJSON line for a full book snapshot, from the stream or a resync.
+fn book_line(
+    asset_id: U256,
+    market: B256,
+    timestamp: i64,
+    bids: impl Iterator<Item = (Decimal, Decimal)>,
+    asks: impl Iterator<Item = (Decimal, Decimal)>,
+) -> serde_json::Value {
+    fn levels(levels: impl Iterator<Item = (Decimal, Decimal)>) -> Vec<serde_json::Value> {
+        levels
+            .map(|(price, size)| json!({"price": price.to_string(), "size": size.to_string()}))
+            .collect()
+    }
+    json!({
+        "event": "book",
+        "asset_id": asset_id.to_string(),
+        "market": market.to_string(),
+        "timestamp": timestamp,
+        "bids": levels(bids),
+        "asks": levels(asks),
+    })
+}
+
+enum MarketEvent {
+    Book(BookUpdate),
+    Price(PriceChange),
+    Trade(LastTradePrice),
+}
+
+impl MarketEvent {
+    /// Applies the event to the books it touches and returns its NDJSON lines,
+    /// one per token update.
+    fn apply(&self, books: &mut HashMap<U256, LocalBook>) -> Vec<serde_json::Value> {
+        match self {
+            Self::Book(book) => {
+                let Some(local) = books.get_mut(&book.asset_id) else {
+                    return Vec::new();
+                };
+                if !local.apply_snapshot(book) {
+                    return Vec::new();
+                }
+                vec![book_line(
+                    book.asset_id,
+                    book.market,
+                    book.timestamp,
+                    book.bids.iter().map(|l| (l.price, l.size)),
+                    book.asks.iter().map(|l| (l.price, l.size)),
+                )]
+            }
+            Self::Price(change) => change
+                .price_changes
+                .iter()
+                .filter_map(|entry| {
+                    let applied = books
+                        .get_mut(&entry.asset_id)?
+                        .apply_change(entry, change.timestamp);
+                    applied.then(|| {
+                        json!({
+                            "event": "price_change",
+                            "asset_id": entry.asset_id.to_string(),
+                            "market": change.market.to_string(),
+                            "timestamp": change.timestamp,
+                            "side": entry.side.to_string(),
+                            "price": entry.price.to_string(),
+                            "size": entry.size.map(|s| s.to_string()),
+                            "best_bid": entry.best_bid.map(|p| p.to_string()),
+                            "best_ask": entry.best_ask.map(|p| p.to_string()),
+                        })
+                    })
+                })
+                .collect(),
+            Self::Trade(trade) => {
+                let Some(local) = books.get_mut(&trade.asset_id) else {
+                    return Vec::new();
+                };
+                local.last_trade = Some((trade.price, trade.side));
+                local.updated = Some(trade.timestamp);
+                vec![json!({
+                    "event": "last_trade",
+                    "asset_id": trade.asset_id.to_string(),
+                    "market": trade.market.to_string(),
+                    "timestamp": trade.timestamp,
+                    "price": trade.price.to_string(),
+                    "side": trade.side.map(|s| s.to_string()),
+                    "size": trade.size.map(|s| s.to_string()),
+                })]
+            }
         }
+    }
+}
 
-        ClobCommand::CancelAll => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.cancel_all_orders().await?;
-            print_cancel_result(&result, output)?;
+/// How often the table view of `clob stream` redraws while events arrive.
+const STREAM_REDRAW: Duration = Duration::from_millis(250);
+
+/// Pause before resubscribing after a subscription stops.
+const STREAM_RESYNC_DELAY: Duration = Duration::from_secs(1);
+
+/// Resyncs in a row, with no event in between, before `clob stream` gives up.
+const MAX_STREAM_RESYNCS: u32 = 5;
+
+/// The book, price and trade subscriptions for `token_ids`, merged. Each one
+/// yields `None` when it stops, which the SDK does after a lag or any other
+/// error, so the caller knows updates may have been missed.
+fn market_events(
+    client: &clob::ws::Client,
+    token_ids: &[U256],
+) -> Result<impl futures::Stream<Item = Option<Result<MarketEvent>>>> {
+    let books = client
+        .subscribe_orderbook(token_ids.to_vec())?
+        .map(|r| Some(r.map(MarketEvent::Book).map_err(Into::into)))
+        .chain(stream::iter([None]));
+    let prices = client
+        .subscribe_prices(token_ids.to_vec())?
+        .map(|r| Some(r.map(MarketEvent::Price).map_err(Into::into)))
+        .chain(stream::iter([None]));
+    let trades = client
+        .subscribe_last_trade_price(token_ids.to_vec())?
+        .map(|r| Some(r.map(MarketEvent::Trade).map_err(Into::into)))
+        .chain(stream::iter([None]));
+    Ok(stream::select(books, stream::select(prices, trades)))
+}
+
+/// Rebuilds every book from a REST snapshot and returns their NDJSON lines.
+async fn resync_books(
+    token_ids: &[U256],
+    books: &mut HashMap<U256, LocalBook>,
+) -> Result<Vec<serde_json::Value>> {
+    let requests: Vec<_> = token_ids
+        .iter()
+        .map(|id| OrderBookSummaryRequest::builder().token_id(*id).build())
+        .collect();
+    let snapshots = clob::Client::default()
+        .order_books(&requests)
+        .await
+        .context("Failed to re-fetch order books after the stream dropped updates")?;
+    Ok(snapshots
+        .iter()
+        .filter(|book| {
+            books
+                .get_mut(&book.asset_id)
+                .is_some_and(|local| local.apply_resync(book))
+        })
+        .map(|book| {
+            book_line(
+                book.asset_id,
+                book.market,
+                book.timestamp.timestamp_millis(),
+                book.bids.iter().map(|l| (l.price, l.size)),
+                book.asks.iter().map(|l| (l.price, l.size)),
+            )
+        })
+        .collect())
+}
+
+/// Follows the market channel for `token_ids` until the connection gives up.
+/// The SDK reconnects and resubscribes on its own; each reconnect starts with
+/// fresh book snapshots. When a subscription lags or stops, the books are
+/// rebuilt over REST and the subscriptions are set up again.
+async fn stream_market(token_ids: Vec<U256>, output: &OutputFormat) -> Result<()> {
+    let client = clob::ws::Client::default();
+    let mut events = Box::pin(market_events(&client, &token_ids)?);
+
+    let mut local: HashMap<U256, LocalBook> = token_ids
+        .iter()
+        .map(|id| (*id, LocalBook::default()))
+        .collect();
+    let table = matches!(output, OutputFormat::Table);
+    let mut redraw = tokio::time::interval(STREAM_REDRAW);
+    let mut dirty = true;
+    let mut resyncs = 0;
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let lines = match event {
+                    Some(Some(Ok(event))) => {
+                        resyncs = 0;
+                        event.apply(&mut local)
+                    }
+                    Some(Some(Err(e))) => {
+                        eprintln!("Stream error: {e}");
+                        continue;
+                    }
+                    Some(None) | None => {
+                        resyncs += 1;
+                        anyhow::ensure!(resyncs <= MAX_STREAM_RESYNCS, "Market stream closed");
+                        eprintln!("Market stream interrupted, resyncing order books");
+                        tokio::time::sleep(STREAM_RESYNC_DELAY).await;
+                        events = Box::pin(market_events(&client, &token_ids)?);
+                        resync_books(&token_ids, &mut local).await?
+                    }
+                };
+                if !table {
+                    let mut out = std::io::stdout().lock();
+                    for line in lines {
+                        writeln!(out, "{line}")?;
+                    }
+                    out.flush()?;
+                }
+                dirty = true;
+            }
+            _ = redraw.tick(), if table => {
+                if dirty {
+                    let rows: Vec<StreamRow> =
+                        token_ids.iter().map(|id| local[id].row(*id)).collect();
+                    print_stream_view(&rows);
+                    dirty = false;
+                }
+            }
         }
+    }
+}
 
-        ClobCommand::CancelMarket { market, asset } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = CancelMarketOrderRequest::builder()
-                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
-                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
-                .build();
-            let result = client.cancel_market_orders(&request).await?;
-            print_cancel_result(&result, output)?;
+/// Whether a CLOB-reported allowance (a decimal string) is non-zero.
+fn allowance_granted(allowance: &str) -> bool {
+    allowance
+        .trim()
+        .parse::<U256>()
+        .is_ok_and(|amount| !amount.is_zero())
+}
+
+/// Whether every exchange allowance is granted. An empty map means the CLOB
+/// reported none, which needs a refresh as much as a zero one.
+fn allowances_granted(allowances: &HashMap<Address, String>) -> bool {
+    !allowances.is_empty() && allowances.values().all(|a| allowance_granted(a))
+}
+
+/// Midpoints where one-sided liquidity still earns rewards, at a third of
+/// its score; outside this band only two-sided liquidity scores.
+const SINGLE_SIDED_BAND: (Decimal, Decimal) = (
+    Decimal::from_parts(10, 0, 0, false, 2),
+    Decimal::from_parts(90, 0, 0, false, 2),
+);
+
+/// An order's rewards score: `((v - s) / v)^2 * size`, where `v` is the max
+/// spread and `s` the order's distance from the midpoint.
+fn reward_score(distance: Decimal, max_spread: Decimal, size: Decimal) -> Decimal {
+    if max_spread <= Decimal::ZERO || distance >= max_spread {
+        return Decimal::ZERO;
+    }
+    let closeness = (max_spread - distance) / max_spread;
+    closeness * closeness * size
+}
+
+/// Combines bid-side and ask-side scores the way the rewards program does:
+/// the smaller side counts in full, one-sided liquidity at a third inside the band.
+fn two_sided_score(midpoint: Decimal, bids: Decimal, asks: Decimal) -> Decimal {
+    let paired = bids.min(asks);
+    let (low, high) = SINGLE_SIDED_BAND;
+    if (low..=high).contains(&midpoint) {
+        paired.max(bids.max(asks) / Decimal::from(3))
+    } else {
+        paired
+    }
+}
+
+/// Score of the resting levels big enough to earn rewards. `mirror` scores
+/// levels from the complement token's book at `1 - price`, which is where
+/// they compete on this token.
+fn book_score(levels: &[OrderSummary], check: &RewardCheck, mirror: bool) -> Decimal {
+    levels
+        .iter()
+        .filter(|l| l.size >= check.min_size)
+        .map(|l| {
+            let price = if mirror {
+                Decimal::ONE - l.price
+            } else {
+                l.price
+            };
+            reward_score((price - check.midpoint).abs(), check.max_spread, l.size)
+        })
+        .sum()
+}
+
+/// Fills in whether `check`'s order would score and how it compares with
+/// the liquidity already on the book (best level last on each side).
+///
+/// `complement` is the other outcome's `(bids, asks)`: the rewards program
+/// counts its asks with this token's bids and its bids with this token's asks.
+fn score_order(
+    check: &mut RewardCheck,
+    bids: &[OrderSummary],
+    asks: &[OrderSummary],
+    complement: (&[OrderSummary], &[OrderSummary]),
+) {
+    let (other_bids, other_asks) = complement;
+    let others = two_sided_score(
+        check.midpoint,
+        book_score(bids, check, false) + book_score(other_asks, check, true),
+        book_score(asks, check, false) + book_score(other_bids, check, true),
+    );
+    check.competing = others;
+
+    let crosses = match check.side {
+        Side::Buy => asks.last().is_some_and(|a| check.price >= a.price),
+        _ => bids.last().is_some_and(|b| check.price <= b.price),
+    };
+    let distance = (check.price - check.midpoint).abs();
+    let raw = reward_score(distance, check.max_spread, check.size);
+    let (bid_score, ask_score) = match check.side {
+        Side::Buy => (raw, Decimal::ZERO),
+        _ => (Decimal::ZERO, raw),
+    };
+    let score = two_sided_score(check.midpoint, bid_score, ask_score);
+
+    check.blocker = if check.daily_rate.is_zero() {
+        Some("no active reward program".into())
+    } else if crosses {
+        Some("crosses the book, so it would fill instead of resting".into())
+    } else if check.size < check.min_size {
+        Some(format!("size below the minimum of {}", check.min_size))
+    } else if raw.is_zero() {
+        Some(format!("more than {} from the midpoint", check.max_spread))
+    } else if score.is_zero() {
+        Some("one-sided quotes do not score with the midpoint outside 0.10-0.90".into())
+    } else {
+        None
+    };
+    if check.blocker.is_none() {
+        check.score = score;
+    }
+}
+
+/// Reward pool paid per day in USDC by the configs running on `today`.
+/// Configs paying in other tokens are left out rather than summed as dollars.
+fn usdc_daily_rate(configs: &[MarketRewardsConfig], usdc: Address, today: NaiveDate) -> Decimal {
+    configs
+        .iter()
+        .filter(|c| c.asset_address == usdc && c.start_date <= today && today <= c.end_date)
+        .map(|c| c.rate_per_day)
+        .sum()
+}
+
+/// How many balance requests `clob balances` keeps in flight at once.
+const BALANCE_CONCURRENCY: usize = 8;
+
+/// The CLOB's cached balance and allowances for each token, in order.
+async fn conditional_balances(
+    client: &clob::Client<Authenticated<Normal>>,
+    token_ids: &[U256],
+) -> Result<Vec<BalanceAllowanceResponse>> {
+    let mut balances = Vec::with_capacity(token_ids.len());
+    for chunk in token_ids.chunks(BALANCE_CONCURRENCY) {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, &token_id) in chunk.iter().enumerate() {
+            let client = client.clone();
+            tasks.spawn(async move {
+                let request = BalanceAllowanceRequest::builder()
+                    .asset_type(AssetType::Conditional)
+                    .token_id(token_id)
+                    .build();
+                (i, client.balance_allowance(request).await)
+            });
+        }
+        let mut results: Vec<_> = tasks.join_all().await;
+        results.sort_by_key(|(i, _)| *i);
+        for (_, result) in results {
+            balances.push(result?);
         }
+    }
+    Ok(balances)
+}
 
-        ClobCommand::Trades {
-            market,
-            asset,
-            cursor,
-        } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = TradesRequest::builder()
-                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
-                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
-                .build();
-            let result = client.trades(&request, cursor).await?;
-            print_trades(&result, output)?;
+/// Runs `update-balance` for each token, a few at a time.
+async fn refresh_conditional_balances(
+    client: &clob::Client<Authenticated<Normal>>,
+    token_ids: &[U256],
+) -> Result<()> {
+    for chunk in token_ids.chunks(BALANCE_CONCURRENCY) {
+        let mut tasks = tokio::task::JoinSet::new();
+        for &token_id in chunk {
+            let client = client.clone();
+            tasks.spawn(async move {
+                let request = BalanceAllowanceRequest::builder()
+                    .asset_type(AssetType::Conditional)
+                    .token_id(token_id)
+                    .build();
+                client
+                    .update_balance_allowance(request)
+                    .await
+                    .with_context(|| format!("Failed to refresh token {token_id}"))
+            });
         }
+        for result in tasks.join_all().await {
+            result?;
+        }
+    }
+    Ok(())
+}
+
+/// Settings for `clob make`, resolved against the market's tick and rewards.
+struct MakerConfig {
+    token_id: U256,
+    tick: Decimal,
+    half_spread: Decimal,
+    size: Decimal,
+    max_inventory: Decimal,
+    skew: bool,
+    requote: Decimal,
+    interval: Duration,
+}
+
+/// Runs the quoting loop until Ctrl-C, then cancels whatever is resting.
+async fn run_maker(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    cfg: &MakerConfig,
+) -> Result<CancelOrdersResponse> {
+    let _guard = interrupt::install();
+    let mut live: Vec<String> = Vec::new();
+    let mut last: Option<(Decimal, Decimal)> = None;
+
+    loop {
+        let cycle = async {
+            let request = MidpointRequest::builder().token_id(cfg.token_id).build();
+            let mid = client.midpoint(&request).await?.mid;
+            let inventory = token_inventory(client, cfg.token_id).await?;
+
+            let stale = last.is_none_or(|(last_mid, last_inv)| {
+                (mid - last_mid).abs() >= cfg.requote || inventory != last_inv
+            });
+            if stale {
+                if !live.is_empty() {
+                    let ids: Vec<&str> = live.iter().map(String::as_str).collect();
+                    cancel_journaled(
+                        "make",
+                        &json!({"order_ids": ids}),
+                        client.cancel_orders(&ids),
+                    )
+                    .await?;
+                    live.clear();
+                }
+                let quotes = make_quotes(
+                    mid,
+                    cfg.tick,
+                    cfg.half_spread,
+                    cfg.size,
+                    inventory,
+                    cfg.max_inventory,
+                    cfg.skew,
+                );
+                let mut orders = Vec::new();
+                for (side, quote) in [(Side::Buy, quotes.bid), (Side::Sell, quotes.ask)] {
+                    let Some((price, size)) = quote else { continue };
+                    let order = client
+                        .limit_order()
+                        .token_id(cfg.token_id)
+                        .side(side)
+                        .price(price)
+                        .size(size)
+                        .post_only(true)
+                        .build()
+                        .await?;
+                    orders.push(client.sign(signer, order).await?);
+                }
+                if !orders.is_empty() {
+                    for result in post_many_journaled(client, orders, "make").await? {
+                        if result.success {
+                            live.push(result.order_id);
+                        } else {
+                            eprintln!(
+                                "Quote rejected: {}",
+                                result.error_msg.as_deref().unwrap_or("unknown error")
+                            );
+                        }
+                    }
+                }
+                let fmt = |q: Option<(Decimal, Decimal)>| {
+                    q.map_or_else(|| "—".to_string(), |(p, s)| format!("{s} @ {p}"))
+                };
+                eprintln!(
+                    "mid {mid} inv {inventory} | bid {} | ask {}",
+                    fmt(quotes.bid),
+                    fmt(quotes.ask)
+                );
+                last = Some((mid, inventory));
+            }
+
+            if !live.is_empty() {
+                let ids: Vec<&str> = live.iter().map(String::as_str).collect();
+                let scoring = client.are_orders_scoring(&ids).await?;
+                let count = scoring.values().filter(|s| **s).count();
+                eprintln!("scoring {count}/{}", live.len());
+            }
+            anyhow::Ok(())
+        };
+        if let Err(e) = cycle.await {
+            eprintln!("Cycle failed: {e}");
+            // Force a fresh quote next time rather than trusting half-updated state.
+            last = None;
+        }
+        if !interrupt::sleep(cfg.interval).await {
+            break;
+        }
+    }
+
+    eprintln!("Shutting down: canceling quotes");
+    if live.is_empty() {
+        return Ok(CancelOrdersResponse::builder().build());
+    }
+    let ids: Vec<&str> = live.iter().map(String::as_str).collect();
+    Ok(cancel_journaled(
+        "make",
+        &json!({"order_ids": ids}),
+        client.cancel_orders(&ids),
+    )
+    .await?)
+}
+
+/// Size of the next TWAP slice: the unfilled remainder spread evenly over the
+/// slices left, truncated to the lot size. The last slice takes everything.
+fn twap_slice(remaining: Decimal, slices_left: u32) -> Decimal {
+    if slices_left <= 1 {
+        return remaining;
+    }
+    (remaining / Decimal::from(slices_left))
+        .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero)
+}
+
+/// Returns `(shares, usdc)` filled by a posted order, based on its side.
+fn filled_amounts(side: Side, result: &PostOrderResponse) -> (Decimal, Decimal) {
+    match side {
+        Side::Sell => (result.making_amount, result.taking_amount),
+        _ => (result.taking_amount, result.making_amount),
+    }
+}
+
+async fn arrival_mid(
+    client: &clob::Client<Authenticated<Normal>>,
+    token_id: U256,
+) -> Result<Decimal> {
+    let request = MidpointRequest::builder().token_id(token_id).build();
+    Ok(client.midpoint(&request).await?.mid)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_twap(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    token_id: U256,
+    side: Side,
+    total: Decimal,
+    duration: Duration,
+    slices: u32,
+    limit_price: Option<Decimal>,
+) -> Result<ExecutionReport> {
+    let mut report = ExecutionReport {
+        algo: "twap",
+        side,
+        target: total,
+        filled: Decimal::ZERO,
+        notional: Decimal::ZERO,
+        arrival_mid: arrival_mid(client, token_id).await?,
+        orders: 0,
+        interrupted: false,
+    };
+    let interval = duration / slices;
+    let _guard = interrupt::install();
+    let start = tokio::time::Instant::now();
+
+    for i in 0..slices {
+        // Slices run on a fixed schedule so time spent posting doesn't
+        // stretch the total past `duration`.
+        if i > 0 && !interrupt::sleep_until(start + interval * i).await {
+            report.interrupted = true;
+            break;
+        }
+        let size = twap_slice(total - report.filled, slices - i);
+        if size <= Decimal::ZERO {
+            continue;
+        }
+
+        let mut builder = client
+            .market_order()
+            .token_id(token_id)
+            .side(side)
+            .amount(Amount::shares(size)?)
+            .order_type(OrderType::FAK);
+        if let Some(price) = limit_price {
+            builder = builder.price(price);
+        }
+        let posted = match builder.build().await {
+            Ok(order) => post_journaled(client, client.sign(signer, order).await?, "twap").await,
+            Err(e) => Err(e),
+        };
+        match posted {
+            Ok(result) => {
+                report.orders += 1;
+                let (shares, usdc) = filled_amounts(side, &result);
+                report.filled += shares;
+                report.notional += usdc;
+                eprintln!(
+                    "Slice {}/{slices}: filled {shares} of {size} ({}/{total} total)",
+                    i + 1,
+                    report.filled
+                );
+            }
+            Err(e) => eprintln!("Slice {}/{slices}: {e}", i + 1),
+        }
+        if interrupt::requested() {
+            report.interrupted = true;
+            break;
+        }
+    }
+    Ok(report)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_iceberg(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    token_id: U256,
+    side: Side,
+    price: Decimal,
+    visible: Decimal,
+    total: Decimal,
+    poll: Duration,
+    post_only: bool,
+) -> Result<ExecutionReport> {
+    let mut report = ExecutionReport {
+        algo: "iceberg",
+        side,
+        target: total,
+        filled: Decimal::ZERO,
+        notional: Decimal::ZERO,
+        arrival_mid: arrival_mid(client, token_id).await?,
+        orders: 0,
+        interrupted: false,
+    };
+    let _guard = interrupt::install();
+
+    while report.filled < total && !interrupt::requested() {
+        let clip = visible.min(total - report.filled);
+        let order = client
+            .limit_order()
+            .token_id(token_id)
+            .side(side)
+            .price(price)
+            .size(clip)
+            .post_only(post_only)
+            .build()
+            .await?;
+        let result = post_journaled(client, client.sign(signer, order).await?, "iceberg").await?;
+        if !result.success {
+            anyhow::bail!(
+                "Clip rejected: {}",
+                result.error_msg.as_deref().unwrap_or("unknown error")
+            );
+        }
+        report.orders += 1;
+        let order_id = result.order_id;
+        eprintln!(
+            "Clip {}: resting {clip} @ {price} ({order_id})",
+            report.orders
+        );
+
+        let status = loop {
+            let still_running = interrupt::sleep(poll).await;
+            let status = match client.order(&order_id).await {
+                Ok(status) => status,
+                Err(e) => {
+                    // Don't leave a clip resting that nothing is watching any more.
+                    let _ = cancel_journaled(
+                        "iceberg",
+                        &json!({"order_id": order_id}),
+                        client.cancel_order(&order_id),
+                    )
+                    .await;
+                    return Err(e.into());
+                }
+            };
+            if status.status != OrderStatusType::Live {
+                break status;
+            }
+            if !still_running {
+                report.interrupted = true;
+                cancel_journaled(
+                    "iceberg",
+                    &json!({"order_id": order_id}),
+                    client.cancel_order(&order_id),
+                )
+                .await?;
+                eprintln!("Canceled resting clip {order_id}");
+                // Fills can land between the last poll and the cancel.
+                break client.order(&order_id).await?;
+            }
+        };
+
+        let trades = fetch_trades_by_id(client, &status.associate_trades).await?;
+        let (matched, notional) = order_fills(&status, &trades);
+        report.filled += matched;
+        report.notional += notional;
+        if !report.interrupted && matched < clip {
+            anyhow::bail!(
+                "Clip {order_id} is {} with {matched}/{clip} filled; stopping",
+                status.status
+            );
+        }
+    }
+    report.interrupted |= interrupt::requested();
+    Ok(report)
+}
+
+async fn fetch_trades_by_id(
+    client: &clob::Client<Authenticated<Normal>>,
+    ids: &[String],
+) -> Result<Vec<TradeResponse>> {
+    let mut trades = Vec::with_capacity(ids.len());
+    for id in ids {
+        let request = TradesRequest::builder().id(id.clone()).build();
+        trades.extend(client.trades(&request, None).await?.data);
+    }
+    Ok(trades)
+}
+
+/// Shares filled and USDC notional for `order`, priced at the trades it
+/// matched in rather than its limit price. Fills the trades endpoint hasn't
+/// caught up with yet are priced at the limit.
+fn order_fills(order: &OpenOrderResponse, trades: &[TradeResponse]) -> (Decimal, Decimal) {
+    let mut size = Decimal::ZERO;
+    let mut notional = Decimal::ZERO;
+    for trade in trades {
+        if trade.taker_order_id == order.id {
+            size += trade.size;
+            notional += trade.size * trade.price;
+        }
+        for maker in trade.maker_orders.iter().filter(|m| m.order_id == order.id) {
+            size += maker.matched_amount;
+            notional += maker.matched_amount * maker.price;
+        }
+    }
+    let missing = (order.size_matched - size).max(Decimal::ZERO);
+    (size + missing, notional + missing * order.price)
+}
+
+async fn trigger_price(
+    client: &clob::Client<Authenticated<Normal>>,
+    trigger: &Trigger,
+) -> Result<Decimal> {
+    let token_id = parse_token_id(&trigger.token_id)?;
+    Ok(match trigger.source {
+        PriceSource::Midpoint => {
+            let request = MidpointRequest::builder().token_id(token_id).build();
+            client.midpoint(&request).await?.mid
+        }
+        PriceSource::LastTrade => {
+            let request = LastTradePriceRequest::builder().token_id(token_id).build();
+            client.last_trade_price(&request).await?.price
+        }
+    })
+}
+
+async fn fire_trigger(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    trigger: &Trigger,
+) -> Result<PostOrderResponse> {
+    let token_id = parse_token_id(&trigger.token_id)?;
+    let order = match trigger.limit_price {
+        Some(price) => {
+            client
+                .limit_order()
+                .token_id(token_id)
+                .side(trigger.side)
+                .price(price)
+                .size(trigger.size)
+                .build()
+                .await?
+        }
+        None => {
+            client
+                .market_order()
+                .token_id(token_id)
+                .side(trigger.side)
+                .amount(Amount::shares(trigger.size)?)
+                .order_type(OrderType::FAK)
+                .build()
+                .await?
+        }
+    };
+    let order = client.sign(signer, order).await?;
+    Ok(post_journaled(client, order, "stop").await?)
+}
+
+/// Polls every pending trigger until none are left or Ctrl-C is pressed.
+///
+/// The triggers file is re-read on every pass so `clob cancel-trigger` from
+/// another terminal takes effect. A trigger is removed before its order is
+/// posted, so a failed post is reported rather than retried.
+async fn watch_triggers(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    interval: Duration,
+    output: &OutputFormat,
+) -> Result<()> {
+    let _guard = interrupt::install();
+    loop {
+        let pending = triggers::load()?;
+        if pending.is_empty() {
+            eprintln!("No pending triggers.");
+            return Ok(());
+        }
+        for trigger in &pending {
+            let price = match trigger_price(client, trigger).await {
+                Ok(price) => price,
+                Err(e) => {
+                    eprintln!("Trigger #{}: could not fetch price: {e}", trigger.id);
+                    continue;
+                }
+            };
+            if !trigger.is_crossed(price) || !triggers::remove(trigger.id)? {
+                continue;
+            }
+            eprintln!(
+                "Trigger #{} fired at {price} (trigger {})",
+                trigger.id, trigger.trigger_price
+            );
+            match fire_trigger(client, signer, trigger).await {
+                Ok(result) => print_post_order_result(&result, output)?,
+                Err(e) => eprintln!(
+                    "Trigger #{} was removed but its order failed to post: {e}",
+                    trigger.id
+                ),
+            }
+        }
+        if !interrupt::sleep(interval).await {
+            eprintln!("Stopped watching; pending triggers are kept.");
+            return Ok(());
+        }
+    }
+}
+
+pub async fn execute(
+    args: ClobArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+    paper: bool,
+) -> Result<()> {
+    // Anything the paper account can't simulate is refused rather than run live.
+    if paper {
+        return execute_paper(args.command, &output).await;
+    }
+    match args.command {
+        // Unauthenticated read commands
+        ClobCommand::Ok
+        | ClobCommand::Price { .. }
+        | ClobCommand::BatchPrices { .. }
+        | ClobCommand::Midpoint { .. }
+        | ClobCommand::Midpoints { .. }
+        | ClobCommand::Spread { .. }
+        | ClobCommand::Spreads { .. }
+        | ClobCommand::Book { .. }
+        | ClobCommand::Books { .. }
+        | ClobCommand::Stream { .. }
+        | ClobCommand::Quote { .. }
+        | ClobCommand::Quotes { .. }
+        | ClobCommand::LastTrade { .. }
+        | ClobCommand::LastTrades { .. }
+        | ClobCommand::Market { .. }
+        | ClobCommand::Markets { .. }
+        | ClobCommand::SamplingMarkets { .. }
+        | ClobCommand::SimplifiedMarkets { .. }
+        | ClobCommand::SamplingSimpMarkets { .. }
+        | ClobCommand::TickSize { .. }
+        | ClobCommand::FeeRate { .. }
+        | ClobCommand::NegRisk { .. }
+        | ClobCommand::PriceHistory { .. }
+        | ClobCommand::Time
+        | ClobCommand::Geoblock => execute_read(args.command, &output).await,
+
+        // Authenticated trading commands
+        ClobCommand::Orders { .. }
+        | ClobCommand::Order { .. }
+        | ClobCommand::Wait { .. }
+        | ClobCommand::CreateOrder { .. }
+        | ClobCommand::PostOrders { .. }
+        | ClobCommand::Ladder { .. }
+        | ClobCommand::Stop { .. }
+        | ClobCommand::WatchTriggers { .. }
+        | ClobCommand::Twap { .. }
+        | ClobCommand::Iceberg { .. }
+        | ClobCommand::Make { .. }
+        | ClobCommand::Close { .. }
+        | ClobCommand::Flatten { .. }
+        | ClobCommand::ArbComplement { .. }
+        | ClobCommand::MarketOrder { .. }
+        | ClobCommand::Replace { .. }
+        | ClobCommand::Cancel { .. }
+        | ClobCommand::CancelOrders { .. }
+        | ClobCommand::CancelAll
+        | ClobCommand::CancelMarket { .. }
+        | ClobCommand::CancelWhere { .. }
+        | ClobCommand::Heartbeat { .. }
+        | ClobCommand::Trades { .. }
+        | ClobCommand::Balance { .. }
+        | ClobCommand::Balances { .. }
+        | ClobCommand::UpdateBalance { .. }
+        | ClobCommand::Notifications
+        | ClobCommand::DeleteNotifications { .. } => {
+            execute_trade(args.command, &output, private_key, signature_type).await
+        }
+
+        // Authenticated reward commands
+        ClobCommand::Rewards { .. }
+        | ClobCommand::Earnings { .. }
+        | ClobCommand::EarningsMarkets { .. }
+        | ClobCommand::RewardPercentages
+        | ClobCommand::CurrentRewards { .. }
+        | ClobCommand::MarketReward { .. }
+        | ClobCommand::RewardCheck { .. }
+        | ClobCommand::OrderScoring { .. }
+        | ClobCommand::OrdersScoring { .. } => {
+            execute_rewards(args.command, &output, private_key, signature_type).await
+        }
+
+        // Account management commands
+        ClobCommand::ApiKeys
+        | ClobCommand::DeleteApiKey
+        | ClobCommand::CreateApiKey
+        | ClobCommand::AccountStatus => {
+            execute_account(args.command, &output, private_key, signature_type).await
+        }
+
+        ClobCommand::Journal { command } => {
+            execute_journal(command, &output, private_key, signature_type).await
+        }
+
+        ClobCommand::Triggers => print_triggers(&triggers::load()?, &output),
+        ClobCommand::CancelTrigger { id } => {
+            anyhow::ensure!(triggers::remove(id)?, "No pending trigger with ID {id}");
+            print_trigger_canceled(id, &output)
+        }
+
+        ClobCommand::PaperReset { cash } => {
+            let cash =
+                Decimal::from_str(&cash).map_err(|_| anyhow::anyhow!("Invalid amount: {cash}"))?;
+            anyhow::ensure!(cash >= Decimal::ZERO, "--cash must not be negative");
+            paper::save(&paper::Account::new(cash))?;
+            match output {
+                OutputFormat::Table => println!("Paper account reset to {cash} USDC."),
+                OutputFormat::Json => {
+                    println!("{}", json!({"cash": cash.to_string()}));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn execute_read(command: ClobCommand, output: &OutputFormat) -> Result<()> {
+    match command {
+        ClobCommand::Ok => {
+            let client = clob::Client::default();
+            let result = client.ok().await?;
+            print_ok(&result, output)?;
+        }
+
+        ClobCommand::Price {
+            token_id,
+            by_outcome,
+            side,
+        } => {
+            let client = clob::Client::default();
+            let request = PriceRequest::builder()
+                .token_id(resolve_token(token_id.as_deref(), &by_outcome).await?)
+                .side(Side::from(side))
+                .build();
+            let result = client.price(&request).await?;
+            print_price(&result, output)?;
+        }
+
+        ClobCommand::BatchPrices { token_ids, side } => {
+            let client = clob::Client::default();
+            let requests: Vec<_> = parse_token_ids(&token_ids)?
+                .into_iter()
+                .map(|id| {
+                    PriceRequest::builder()
+                        .token_id(id)
+                        .side(Side::from(side.clone()))
+                        .build()
+                })
+                .collect();
+            let result = client.prices(&requests).await?;
+            print_batch_prices(&result, output)?;
+        }
+
+        ClobCommand::Midpoint { token_id } => {
+            let client = clob::Client::default();
+            let request = MidpointRequest::builder()
+                .token_id(parse_token_id(&token_id)?)
+                .build();
+            let result = client.midpoint(&request).await?;
+            print_midpoint(&result, output)?;
+        }
+
+        ClobCommand::Midpoints { token_ids } => {
+            let client = clob::Client::default();
+            let requests: Vec<_> = parse_token_ids(&token_ids)?
+                .into_iter()
+                .map(|id| MidpointRequest::builder().token_id(id).build())
+                .collect();
+            let result = client.midpoints(&requests).await?;
+            print_midpoints(&result, output)?;
+        }
+
+        ClobCommand::Spread { token_id, side } => {
+            let client = clob::Client::default();
+            let request = SpreadRequest::builder()
+                .token_id(parse_token_id(&token_id)?)
+                .maybe_side(side.map(Side::from))
+                .build();
+            let result = client.spread(&request).await?;
+            print_spread(&result, output)?;
+        }
+
+        ClobCommand::Spreads { token_ids } => {
+            let client = clob::Client::default();
+            let requests: Vec<_> = parse_token_ids(&token_ids)?
+                .into_iter()
+                .map(|id| SpreadRequest::builder().token_id(id).build())
+                .collect();
+            let result = client.spreads(&requests).await?;
+            print_spreads(&result, output)?;
+        }
+
+        ClobCommand::Book {
+            token_id,
+            by_outcome,
+        } => {
+            let client = clob::Client::default();
+            let request = OrderBookSummaryRequest::builder()
+                .token_id(resolve_token(token_id.as_deref(), &by_outcome).await?)
+                .build();
+            let result = client.order_book(&request).await?;
+            print_order_book(&result, output)?;
+        }
+
+        ClobCommand::Books { token_ids } => {
+            let client = clob::Client::default();
+            let requests: Vec<_> = parse_token_ids(&token_ids)?
+                .into_iter()
+                .map(|id| OrderBookSummaryRequest::builder().token_id(id).build())
+                .collect();
+            let result = client.order_books(&requests).await?;
+            print_order_books(&result, output)?;
+        }
+
+        ClobCommand::Stream { tokens } => {
+            stream_market(parse_token_ids(&tokens)?, output).await?;
+        }
+
+        ClobCommand::Quote {
+            token,
+            side,
+            amount,
+            unit,
+        } => {
+            let quotes = fetch_quotes(&token, side, &amount, unit).await?;
+            print_quote(&quotes[0], output)?;
+        }
+
+        ClobCommand::Quotes {
+            tokens,
+            side,
+            amount,
+            unit,
+        } => {
+            let quotes = fetch_quotes(&tokens, side, &amount, unit).await?;
+            print_quotes(&quotes, output)?;
+        }
+
+        ClobCommand::LastTrade { token_id } => {
+            let client = clob::Client::default();
+            let request = LastTradePriceRequest::builder()
+                .token_id(parse_token_id(&token_id)?)
+                .build();
+            let result = client.last_trade_price(&request).await?;
+            print_last_trade(&result, output)?;
+        }
+
+        ClobCommand::LastTrades { token_ids } => {
+            let client = clob::Client::default();
+            let requests: Vec<_> = parse_token_ids(&token_ids)?
+                .into_iter()
+                .map(|id| LastTradePriceRequest::builder().token_id(id).build())
+                .collect();
+            let result = client.last_trades_prices(&requests).await?;
+            print_last_trades_prices(&result, output)?;
+        }
+
+        ClobCommand::Market { condition_id } => {
+            let client = clob::Client::default();
+            let result = client.market(&condition_id).await?;
+            print_clob_market(&result, output)?;
+        }
+
+        ClobCommand::Markets { cursor, pages } => {
+            let client = clob::Client::default();
+            let fetch = |c| client.markets(c);
+            print_paged(pages, cursor, output, json_value, fetch, print_clob_markets).await?;
+        }
+
+        ClobCommand::SamplingMarkets { cursor, pages } => {
+            let client = clob::Client::default();
+            let fetch = |c| client.sampling_markets(c);
+            print_paged(pages, cursor, output, json_value, fetch, print_clob_markets).await?;
+        }
+
+        ClobCommand::SimplifiedMarkets { cursor, pages } => {
+            let client = clob::Client::default();
+            let fetch = |c| client.simplified_markets(c);
+            print_paged(
+                pages,
+                cursor,
+                output,
+                json_value,
+                fetch,
+                print_simplified_markets,
+            )
+            .await?;
+        }
+
+        ClobCommand::SamplingSimpMarkets { cursor, pages } => {
+            let client = clob::Client::default();
+            let fetch = |c| client.sampling_simplified_markets(c);
+            print_paged(
+                pages,
+                cursor,
+                output,
+                json_value,
+                fetch,
+                print_simplified_markets,
+            )
+            .await?;
+        }
+
+        ClobCommand::TickSize { token_id } => {
+            let client = clob::Client::default();
+            let result = client.tick_size(parse_token_id(&token_id)?).await?;
+            print_tick_size(&result, output)?;
+        }
+
+        ClobCommand::FeeRate { token_id } => {
+            let client = clob::Client::default();
+            let result = client.fee_rate_bps(parse_token_id(&token_id)?).await?;
+            print_fee_rate(&result, output)?;
+        }
+
+        ClobCommand::NegRisk { token_id } => {
+            let client = clob::Client::default();
+            let result = client.neg_risk(parse_token_id(&token_id)?).await?;
+            print_neg_risk(&result, output)?;
+        }
+
+        ClobCommand::PriceHistory {
+            token_id,
+            by_outcome,
+            interval,
+            fidelity,
+        } => {
+            let client = clob::Client::default();
+            let request = PriceHistoryRequest::builder()
+                .market(resolve_token(token_id.as_deref(), &by_outcome).await?)
+                .time_range(TimeRange::from_interval(Interval::from(interval)))
+                .maybe_fidelity(fidelity)
+                .build();
+            let result = client.price_history(&request).await?;
+            print_price_history(&result, output)?;
+        }
+
+        ClobCommand::Time => {
+            let client = clob::Client::default();
+            let result = client.server_time().await?;
+            print_server_time(result, output)?;
+        }
+
+        ClobCommand::Geoblock => {
+            let client = clob::Client::default();
+            let result = client.check_geoblock().await?;
+            print_geoblock(&result, output)?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+async fn execute_trade(
+    command: ClobCommand,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match command {
+        ClobCommand::Orders {
+            market,
+            asset,
+            cursor,
+            pages,
+        } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = OrdersRequest::builder()
+                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
+                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
+                .build();
+            let fetch = |c| client.orders(&request, c);
+            print_paged(
+                pages,
+                cursor,
+                output,
+                open_order_to_json,
+                fetch,
+                print_orders,
+            )
+            .await?;
+        }
+
+        ClobCommand::Order { order_id } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.order(&order_id).await?;
+            print_order_detail(&result, output)?;
+        }
+
+        ClobCommand::Wait {
+            order_id,
+            timeout,
+            until,
+            interval,
+        } => {
+            let timeout = parse_duration(&timeout)?;
+            let interval = parse_duration(&interval)?;
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let (order, outcome) =
+                wait_for_order(&client, &order_id, until, timeout, interval).await?;
+            print_wait_result(&order, outcome, output)?;
+            wait_exit(outcome, until)?;
+        }
+
+        ClobCommand::CreateOrder {
+            token,
+            by_outcome,
+            side,
+            price,
+            size,
+            order_type,
+            post_only,
+            round,
+            wait,
+            wait_timeout,
+        } => {
+            let wait_timeout = wait.then(|| parse_duration(&wait_timeout)).transpose()?;
+            let token_id = resolve_order_token(token.as_deref(), &by_outcome).await?;
+            let price_dec =
+                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
+            let size_dec =
+                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            let (price_dec, size_dec) = OrderRulesCache::default()
+                .check(token_id, price_dec, size_dec, round)
+                .await?;
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+
+            let order = client
+                .limit_order()
+                .token_id(token_id)
+                .side(Side::from(side))
+                .price(price_dec)
+                .size(size_dec)
+                .order_type(OrderType::from(order_type))
+                .post_only(post_only)
+                .build()
+                .await?;
+            let order = client.sign(&signer, order).await?;
+            let result = post_journaled(&client, order, "create-order").await?;
+            match wait_timeout {
+                Some(timeout) => {
+                    if matches!(output, OutputFormat::Table) {
+                        print_post_order_result(&result, output)?;
+                    }
+                    let posted = post_order_to_json(&result);
+                    wait_after_post(&client, &result, posted, timeout, output).await?;
+                }
+                None => print_post_order_result(&result, output)?,
+            }
+        }
+
+        ClobCommand::PostOrders {
+            tokens,
+            side,
+            prices,
+            sizes,
+            order_type,
+            round,
+        } => {
+            let token_ids = parse_token_ids(&tokens)?;
+            let price_strs: Vec<&str> = prices.split(',').map(str::trim).collect();
+            let size_strs: Vec<&str> = sizes.split(',').map(str::trim).collect();
+
+            if token_ids.len() != price_strs.len() || token_ids.len() != size_strs.len() {
+                anyhow::bail!(
+                    "tokens, prices, and sizes must have the same number of comma-separated values"
+                );
+            }
+
+            let mut rules = OrderRulesCache::default();
+            let mut checked = Vec::with_capacity(token_ids.len());
+            for ((token_id, price_str), size_str) in
+                token_ids.into_iter().zip(price_strs).zip(size_strs)
+            {
+                let price_dec = Decimal::from_str(price_str)
+                    .map_err(|_| anyhow::anyhow!("Invalid price: {price_str}"))?;
+                let size_dec = Decimal::from_str(size_str)
+                    .map_err(|_| anyhow::anyhow!("Invalid size: {size_str}"))?;
+                let (price_dec, size_dec) = rules
+                    .check(token_id, price_dec, size_dec, round)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Order on token {token_id}: {e}"))?;
+                checked.push((token_id, price_dec, size_dec));
+            }
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let sdk_side = Side::from(side);
+            let sdk_order_type = OrderType::from(order_type);
+
+            let mut signed_orders = Vec::with_capacity(checked.len());
+            for (token_id, price_dec, size_dec) in checked {
+                let order = client
+                    .limit_order()
+                    .token_id(token_id)
+                    .side(sdk_side)
+                    .price(price_dec)
+                    .size(size_dec)
+                    .order_type(sdk_order_type.clone())
+                    .build()
+                    .await?;
+                signed_orders.push(client.sign(&signer, order).await?);
+            }
+
+            let results = post_many_journaled(&client, signed_orders, "post-orders").await?;
+            print_post_orders_result(&results, output)?;
+        }
+
+        ClobCommand::Ladder {
+            token,
+            side,
+            from,
+            to,
+            levels,
+            total_size,
+            distribution,
+            ratio,
+            order_type,
+            post_only,
+            dry_run,
+        } => {
+            let token_id = parse_token_id(&token)?;
+            let from_dec =
+                Decimal::from_str(&from).map_err(|_| anyhow::anyhow!("Invalid price: {from}"))?;
+            let to_dec =
+                Decimal::from_str(&to).map_err(|_| anyhow::anyhow!("Invalid price: {to}"))?;
+            let total_dec = Decimal::from_str(&total_size)
+                .map_err(|_| anyhow::anyhow!("Invalid size: {total_size}"))?;
+            let ratio_dec =
+                Decimal::from_str(&ratio).map_err(|_| anyhow::anyhow!("Invalid ratio: {ratio}"))?;
+
+            let rules = OrderRulesCache::default().get(token_id).await?;
+            let ladder = ladder_levels(
+                from_dec,
+                to_dec,
+                levels,
+                total_dec,
+                distribution,
+                ratio_dec,
+                rules.tick,
+            )?;
+            if let Some((price, size)) = ladder.iter().find(|(_, size)| *size < rules.min_size) {
+                anyhow::bail!(
+                    "Ladder level at {price} has size {size}, below the {} share minimum \
+                     for this market; use fewer levels or a larger --total-size",
+                    rules.min_size
+                );
+            }
+
+            if dry_run {
+                print_ladder(&ladder, output)?;
+                return Ok(());
+            }
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+
+            let sdk_side = Side::from(side);
+            let sdk_order_type = OrderType::from(order_type);
+
+            let mut signed_orders = Vec::with_capacity(ladder.len());
+            for (price, size) in ladder {
+                let order = client
+                    .limit_order()
+                    .token_id(token_id)
+                    .side(sdk_side)
+                    .price(price)
+                    .size(size)
+                    .order_type(sdk_order_type.clone())
+                    .post_only(post_only)
+                    .build()
+                    .await?;
+                signed_orders.push(client.sign(&signer, order).await?);
+            }
+
+            let results = post_many_journaled(&client, signed_orders, "ladder").await?;
+            print_post_orders_result(&results, output)?;
+        }
+
+        ClobCommand::Stop {
+            token,
+            side,
+            trigger_price,
+            size,
+            limit,
+            when,
+            source,
+            interval,
+            no_watch,
+        } => {
+            parse_token_id(&token)?;
+            let trigger_dec = Decimal::from_str(&trigger_price)
+                .map_err(|_| anyhow::anyhow!("Invalid price: {trigger_price}"))?;
+            let size_dec =
+                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            let limit_dec = limit
+                .map(|p| Decimal::from_str(&p).map_err(|_| anyhow::anyhow!("Invalid price: {p}")))
+                .transpose()?;
+            anyhow::ensure!(
+                trigger_dec > Decimal::ZERO && trigger_dec < Decimal::ONE,
+                "--trigger-price must be between 0 and 1"
+            );
+            anyhow::ensure!(size_dec > Decimal::ZERO, "--size must be positive");
+            if let Some(limit) = limit_dec {
+                anyhow::ensure!(
+                    limit > Decimal::ZERO && limit < Decimal::ONE,
+                    "--limit must be between 0 and 1"
+                );
+            }
+            let interval = parse_duration(&interval)?;
+            let sdk_side = Side::from(side);
+            let direction = when.unwrap_or(match sdk_side {
+                Side::Buy => Direction::Above,
+                _ => Direction::Below,
+            });
+
+            let trigger = triggers::add(Trigger {
+                id: 0,
+                token_id: token,
+                side: sdk_side,
+                direction,
+                trigger_price: trigger_dec,
+                size: size_dec,
+                limit_price: limit_dec,
+                source,
+                created_at: chrono::Utc::now().to_rfc3339(),
+            })?;
+            print_trigger_saved(&trigger, output)?;
+            if no_watch {
+                return Ok(());
+            }
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            watch_triggers(&client, &signer, interval, output).await?;
+        }
+
+        ClobCommand::WatchTriggers { interval } => {
+            let interval = parse_duration(&interval)?;
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            watch_triggers(&client, &signer, interval, output).await?;
+        }
+
+        ClobCommand::Twap {
+            token,
+            side,
+            total,
+            duration,
+            slices,
+            limit_price,
+        } => {
+            let total_dec =
+                Decimal::from_str(&total).map_err(|_| anyhow::anyhow!("Invalid size: {total}"))?;
+            let limit_dec = limit_price
+                .map(|p| Decimal::from_str(&p).map_err(|_| anyhow::anyhow!("Invalid price: {p}")))
+                .transpose()?;
+            let duration = parse_duration(&duration)?;
+            anyhow::ensure!(
+                tokio::time::Instant::now().checked_add(duration).is_some(),
+                "Duration too large: {}s",
+                duration.as_secs()
+            );
+            anyhow::ensure!(slices > 0, "--slices must be at least 1");
+            anyhow::ensure!(total_dec > Decimal::ZERO, "--total must be positive");
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let report = run_twap(
+                &client,
+                &signer,
+                parse_token_id(&token)?,
+                Side::from(side),
+                total_dec,
+                duration,
+                slices,
+                limit_dec,
+            )
+            .await?;
+            print_execution_report(&report, output)?;
+        }
+
+        ClobCommand::Iceberg {
+            token,
+            side,
+            price,
+            visible,
+            total,
+            poll,
+            post_only,
+        } => {
+            let price_dec =
+                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
+            let visible_dec = Decimal::from_str(&visible)
+                .map_err(|_| anyhow::anyhow!("Invalid size: {visible}"))?;
+            let total_dec =
+                Decimal::from_str(&total).map_err(|_| anyhow::anyhow!("Invalid size: {total}"))?;
+            let poll = parse_duration(&poll)?;
+            anyhow::ensure!(visible_dec > Decimal::ZERO, "--visible must be positive");
+            anyhow::ensure!(total_dec > Decimal::ZERO, "--total must be positive");
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let report = run_iceberg(
+                &client,
+                &signer,
+                parse_token_id(&token)?,
+                Side::from(side),
+                price_dec,
+                visible_dec,
+                total_dec,
+                poll,
+                post_only,
+            )
+            .await?;
+            print_execution_report(&report, output)?;
+        }
+
+        ClobCommand::Close {
+            token,
+            outcome_market,
+            outcome,
+            limit,
+            market: _,
+            percent,
+        } => {
+            // `--market` is the FOK switch here, so the selector has its own name.
+            let by_outcome = OutcomeArgs {
+                market: outcome_market,
+                outcome,
+            };
+            let token_id = resolve_order_token(token.as_deref(), &by_outcome).await?;
+            let limit_dec = limit
+                .map(|p| Decimal::from_str(&p).map_err(|_| anyhow::anyhow!("Invalid price: {p}")))
+                .transpose()?;
+            let percent_dec = Decimal::from_str(&percent)
+                .map_err(|_| anyhow::anyhow!("Invalid percent: {percent}"))?;
+            anyhow::ensure!(
+                percent_dec > Decimal::ZERO && percent_dec <= Decimal::ONE_HUNDRED,
+                "--percent must be between 0 and 100"
+            );
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+
+            let held = token_inventory(&client, token_id).await?;
+            let shares = close_size(held, percent_dec);
+            anyhow::ensure!(
+                shares > Decimal::ZERO,
+                "Nothing to sell: position is {held} shares"
+            );
+
+            // Build and sign before touching resting orders, so a bad price or
+            // an empty book leaves them in place.
+            let order = match limit_dec {
+                Some(price) => {
+                    client
+                        .limit_order()
+                        .token_id(token_id)
+                        .side(Side::Sell)
+                        .price(price)
+                        .size(shares)
+                        .build()
+                        .await?
+                }
+                None => {
+                    client
+                        .market_order()
+                        .token_id(token_id)
+                        .side(Side::Sell)
+                        .amount(Amount::shares(shares)?)
+                        .order_type(OrderType::FOK)
+                        .build()
+                        .await?
+                }
+            };
+            let order = client.sign(&signer, order).await?;
+
+            // Resting sells would double-sell the shares we're about to close.
+            let request = OrdersRequest::builder().asset_id(token_id).build();
+            let resting: Vec<String> = all_open_orders(&client, &request)
+                .await?
+                .into_iter()
+                .filter(|o| o.side == Side::Sell)
+                .map(|o| o.id)
+                .collect();
+            if !resting.is_empty() {
+                let ids: Vec<&str> = resting.iter().map(String::as_str).collect();
+                let canceled = cancel_journaled(
+                    "close",
+                    &json!({"order_ids": ids}),
+                    client.cancel_orders(&ids),
+                )
+                .await?;
+                eprintln!(
+                    "Canceled {} resting sell order(s) on this token",
+                    canceled.canceled.len()
+                );
+            }
+
+            let result = post_journaled(&client, order, "close").await?;
+            print_post_order_result(&result, output)?;
+            if percent_dec == Decimal::ONE_HUNDRED && held > shares {
+                eprintln!(
+                    "{} shares remain below the {} share lot size",
+                    held - shares,
+                    Decimal::new(1, SIZE_DECIMALS)
+                );
+            }
+        }
+
+        ClobCommand::Flatten {
+            event,
+            tag,
+            all: _,
+            max_slippage_bps,
+            dry_run,
+            force,
+        } => {
+            super::ensure_can_confirm(output, force, dry_run)?;
+            let signer = auth::resolve_signer(private_key)?;
+            let owner = auth::wallet_address(signer.address(), signature_type);
+
+            let mut positions = all_positions(owner).await?;
+            // Resolved markets have no book to sell into; they're redeemed instead.
+            let resolved = positions.iter().filter(|p| p.redeemable).count();
+            positions.retain(|p| !p.redeemable);
+            if resolved > 0 {
+                eprintln!("Skipping {resolved} resolved position(s); use `ctf redeem` for those");
+            }
+            if let Some(event) = &event {
+                positions.retain(|p| &p.event_slug == event);
+            }
+            if let Some(tag) = &tag {
+                let markets = tagged_condition_ids(tag).await?;
+                positions.retain(|p| markets.contains(&p.condition_id));
+            }
+
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let mut plan = Vec::new();
+            for position in positions {
+                let skip = |e: &dyn std::fmt::Display| {
+                    eprintln!("Skipped {} ({}): {e}", position.title, position.outcome);
+                };
+                let held = match token_inventory(&client, position.asset).await {
+                    Ok(held) => held,
+                    Err(e) => {
+                        skip(&e);
+                        continue;
+                    }
+                };
+                let shares = close_size(held, Decimal::ONE_HUNDRED);
+                if shares <= Decimal::ZERO {
+                    continue;
+                }
+                let request = OrderBookSummaryRequest::builder()
+                    .token_id(position.asset)
+                    .build();
+                let book = match client.order_book(&request).await {
+                    Ok(book) => book,
+                    Err(e) => {
+                        skip(&e);
+                        continue;
+                    }
+                };
+                let tick = book.tick_size.as_decimal();
+                let estimate =
+                    estimate_fill(&book.bids, Side::Sell, shares, QuoteUnit::Shares).ok();
+                let floor = estimate.as_ref().map_or(tick, |e| {
+                    slippage_floor(e.best_price, max_slippage_bps, tick)
+                });
+                plan.push(FlattenItem {
+                    title: position.title,
+                    outcome: position.outcome,
+                    token_id: position.asset,
+                    shares,
+                    floor,
+                    estimate,
+                });
+            }
+
+            // JSON prints one document: the plan, or the results once sent.
+            if matches!(output, OutputFormat::Table) || dry_run || plan.is_empty() {
+                print_flatten_plan(&plan, output)?;
+            }
+            let prompt = format!("Cancel orders and sell {} position(s)?", plan.len());
+            if plan.is_empty() || !super::confirm(&prompt, output, force, dry_run)? {
+                return Ok(());
+            }
+
+            let mut results = Vec::new();
+            for item in &plan {
+                let request = CancelMarketOrderRequest::builder()
+                    .asset_id(item.token_id)
+                    .build();
+                let canceled =
+                    cancel_journaled("flatten", &request, client.cancel_market_orders(&request))
+                        .await;
+                if let Err(e) = canceled {
+                    eprintln!("Skipped {} ({}): {e}", item.title, item.outcome);
+                    continue;
+                }
+                if item.estimate.is_none() {
+                    eprintln!(
+                        "Canceled orders on {} ({}) but did not sell: no bids",
+                        item.title, item.outcome
+                    );
+                    continue;
+                }
+
+                let order = client
+                    .market_order()
+                    .token_id(item.token_id)
+                    .side(Side::Sell)
+                    .amount(Amount::shares(item.shares)?)
+                    .price(item.floor)
+                    .order_type(OrderType::FAK)
+                    .build()
+                    .await;
+                let posted = match order {
+                    Ok(order) => match client.sign(&signer, order).await {
+                        Ok(signed) => post_journaled(&client, signed, "flatten").await,
+                        Err(e) => Err(e),
+                    },
+                    Err(e) => Err(e),
+                };
+                match posted {
+                    Ok(result) => results.push(result),
+                    Err(e) => eprintln!("{} ({}): {e}", item.title, item.outcome),
+                }
+            }
+            print_post_orders_result(&results, output)?;
+        }
+
+        ClobCommand::ArbComplement {
+            market,
+            max_size,
+            min_edge,
+            dry_run,
+        } => {
+            let max_size = Decimal::from_str(&max_size)
+                .map_err(|_| anyhow::anyhow!("Invalid size: {max_size}"))?;
+            anyhow::ensure!(max_size > Decimal::ZERO, "--max-size must be positive");
+            let min_edge = parse_edge(&min_edge)?;
+            let outcomes = market_outcomes(&market).await?;
+            anyhow::ensure!(
+                outcomes.len() == 2,
+                "arb-complement needs a binary market; {market} has {} outcomes",
+                outcomes.len()
+            );
+
+            let signer = auth::resolve_signer(private_key)?;
+            // The merge is sent from the signing key, so the shares must land there.
+            anyhow::ensure!(
+                auth::wallet_address(signer.address(), signature_type) == signer.address(),
+                "arb-complement merges from your signing key; use --signature-type eoa"
+            );
+
+            let (mut report, neg_risk) = complement_plan(&outcomes, max_size, min_edge).await?;
+            if dry_run {
+                return print_complement_report(&report, output);
+            }
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            run_complement(&client, &signer, &mut report, neg_risk, private_key).await?;
+            print_complement_report(&report, output)?;
+        }
+
+        ClobCommand::Make {
+            token,
+            spread,
+            size,
+            max_inventory,
+            skew,
+            requote,
+            interval,
+        } => {
+            let token_id = parse_token_id(&token)?;
+            let size_dec =
+                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            let max_inventory_dec = Decimal::from_str(&max_inventory)
+                .map_err(|_| anyhow::anyhow!("Invalid size: {max_inventory}"))?;
+            let interval = parse_duration(&interval)?;
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let book = client
+                .order_book(
+                    &OrderBookSummaryRequest::builder()
+                        .token_id(token_id)
+                        .build(),
+                )
+                .await?;
+            let tick = book.tick_size.as_decimal();
+            let spread_dec = parse_ticks(&spread, tick)?;
+            anyhow::ensure!(spread_dec > Decimal::ZERO, "--spread must be positive");
+            let mut half_spread = spread_dec / Decimal::TWO;
+            let mut size_dec = size_dec;
+
+            // Keep quotes inside the liquidity rewards band when the market has one.
+            let rewards = client
+                .raw_rewards_for_market(&book.market.to_string(), None)
+                .await
+                .context("Failed to fetch the market's rewards config")?
+                .data;
+            if let Some(reward) = rewards.first() {
+                let max_spread = reward.rewards_max_spread / Decimal::ONE_HUNDRED;
+                if max_spread > Decimal::ZERO && half_spread > max_spread {
+                    eprintln!(
+                        "Narrowing spread to the rewards max of {max_spread} either side of mid"
+                    );
+                    half_spread = max_spread;
+                }
+                if size_dec < reward.rewards_min_size {
+                    eprintln!(
+                        "Raising size to the rewards minimum of {}",
+                        reward.rewards_min_size
+                    );
+                    size_dec = reward.rewards_min_size;
+                }
+            }
+
+            let cfg = MakerConfig {
+                token_id,
+                tick,
+                half_spread,
+                size: size_dec,
+                max_inventory: max_inventory_dec,
+                skew,
+                requote: parse_ticks(&requote, tick)?,
+                interval,
+            };
+            let result = run_maker(&client, &signer, &cfg).await?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::MarketOrder {
+            token,
+            by_outcome,
+            side,
+            amount,
+            order_type,
+            max_price,
+            min_price,
+            max_slippage_bps,
+            allow_partial,
+            wait,
+            wait_timeout,
+        } => {
+            let wait_timeout = wait.then(|| parse_duration(&wait_timeout)).transpose()?;
+            let sdk_side = Side::from(side);
+            let bound = market_order_bound(sdk_side, max_price, min_price)?;
+            let token_id = resolve_order_token(token.as_deref(), &by_outcome).await?;
+            let amount_dec = Decimal::from_str(&amount)
+                .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
+            let (_, estimate) = market_order_estimate(
+                token_id,
+                sdk_side,
+                amount_dec,
+                bound,
+                max_slippage_bps,
+                allow_partial,
+            )
+            .await?;
+
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+
+            let parsed_amount = if matches!(sdk_side, Side::Sell) {
+                Amount::shares(amount_dec)?
+            } else {
+                Amount::usdc(amount_dec)?
+            };
+
+            let mut builder = client
+                .market_order()
+                .token_id(token_id)
+                .side(sdk_side)
+                .amount(parsed_amount)
+                .order_type(OrderType::from(order_type));
+            // The bound doubles as the order's own price cap, so the exchange
+            // enforces it even if the book moves after the estimate.
+            if let Some(bound) = bound {
+                builder = builder.price(bound);
+            }
+            let order = client.sign(&signer, builder.build().await?).await?;
+            let result = post_journaled(&client, order, "market-order").await?;
+            match wait_timeout {
+                Some(timeout) => {
+                    if matches!(output, OutputFormat::Table) {
+                        print_market_order_result(&estimate, &result, output)?;
+                    }
+                    let posted = market_order_result_to_json(&estimate, &result);
+                    wait_after_post(&client, &result, posted, timeout, output).await?;
+                }
+                None => print_market_order_result(&estimate, &result, output)?,
+            }
+        }
+
+        ClobCommand::Replace {
+            order_id,
+            price,
+            size,
+            post_only,
+        } => {
+            anyhow::ensure!(
+                price.is_some() || size.is_some(),
+                "Nothing to change: pass --price and/or --size"
+            );
+            let signer = auth::resolve_signer(private_key)?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+
+            let original = client.order(&order_id).await?;
+            anyhow::ensure!(
+                original.status == OrderStatusType::Live,
+                "Order {order_id} is {} and cannot be replaced",
+                original.status
+            );
+
+            let price_dec = match price {
+                Some(p) => {
+                    Decimal::from_str(&p).map_err(|_| anyhow::anyhow!("Invalid price: {p}"))?
+                }
+                None => original.price,
+            };
+            let size_dec = match size {
+                Some(s) => {
+                    Decimal::from_str(&s).map_err(|_| anyhow::anyhow!("Invalid size: {s}"))?
+                }
+                None => original.original_size - original.size_matched,
+            };
+
+            // Sign the replacement before touching the original so validation
+            // errors leave the resting order untouched.
+            let mut builder = client
+                .limit_order()
+                .token_id(original.asset_id)
+                .side(original.side)
+                .price(price_dec)
+                .size(size_dec)
+                .order_type(original.order_type.clone())
+                .post_only(post_only);
+            if original.order_type == OrderType::GTD {
+                builder = builder.expiration(original.expiration);
+            }
+            let order = client.sign(&signer, builder.build().await?).await?;
+
+            let canceled = cancel_journaled(
+                "replace",
+                &json!({"order_id": order_id}),
+                client.cancel_order(&order_id),
+            )
+            .await?;
+            if !canceled.canceled.contains(&order_id) {
+                let reason = canceled
+                    .not_canceled
+                    .get(&order_id)
+                    .map_or("unknown reason", String::as_str);
+                anyhow::bail!(
+                    "Order {order_id} was not canceled ({reason}); no replacement was posted"
+                );
+            }
+
+            let result = post_journaled(&client, order, "replace")
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Order {order_id} was canceled but the replacement failed to post: {e}. \
+                     No order is resting in its place."
+                    )
+                })?;
+            print_replace_result(&order_id, &result, output)?;
+        }
+
+        ClobCommand::Cancel { order_id } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = cancel_journaled(
+                "cancel",
+                &json!({"order_id": order_id}),
+                client.cancel_order(&order_id),
+            )
+            .await?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelOrders { order_ids } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
+            let result = cancel_journaled(
+                "cancel-orders",
+                &json!({"order_ids": ids}),
+                client.cancel_orders(&ids),
+            )
+            .await?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelAll => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = cancel_journaled(
+                "cancel-all",
+                &json!({"all": true}),
+                client.cancel_all_orders(),
+            )
+            .await?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelMarket { market, asset } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = CancelMarketOrderRequest::builder()
+                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
+                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
+                .build();
+            let result = cancel_journaled(
+                "cancel-market",
+                &request,
+                client.cancel_market_orders(&request),
+            )
+            .await?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelWhere {
+            side,
+            price_above,
+            price_below,
+            older_than,
+            token,
+            event,
+            not_scoring,
+            dry_run,
+            force,
+        } => {
+            super::ensure_can_confirm(output, force, dry_run)?;
+            let parse_price = |p: String| {
+                p.parse::<Decimal>()
+                    .map_err(|_| anyhow::anyhow!("Invalid price: {p}"))
+            };
+            let older_than = older_than.as_deref().map(parse_duration).transpose()?;
+            let markets = match event {
+                Some(slug) => {
+                    let request = EventBySlugRequest::builder().slug(slug).build();
+                    let event = gamma::Client::default().event_by_slug(&request).await?;
+                    Some(
+                        event
+                            .markets
+                            .iter()
+                            .flatten()
+                            .filter_map(|m| m.condition_id)
+                            .collect(),
+                    )
+                }
+                None => None,
+            };
+            let filter = OrderFilter {
+                side: side.map(Side::from),
+                price_above: price_above.map(parse_price).transpose()?,
+                price_below: price_below.map(parse_price).transpose()?,
+                placed_before: older_than
+                    .map(|d| {
+                        chrono::TimeDelta::from_std(d)
+                            .ok()
+                            .and_then(|d| chrono::Utc::now().checked_sub_signed(d))
+                            .ok_or_else(|| {
+                                anyhow::anyhow!("--older-than too large: {}s", d.as_secs())
+                            })
+                    })
+                    .transpose()?,
+                token: token.as_deref().map(parse_token_id).transpose()?,
+                markets,
+            };
+
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = OrdersRequest::builder()
+                .maybe_asset_id(filter.token)
+                .build();
+            let mut matches: Vec<OpenOrderResponse> = all_open_orders(&client, &request)
+                .await?
+                .into_iter()
+                .filter(|o| filter.matches(o))
+                .collect();
+            if not_scoring && !matches.is_empty() {
+                let mut scoring = HashMap::new();
+                for chunk in matches.chunks(ORDER_ID_CHUNK) {
+                    let ids: Vec<&str> = chunk.iter().map(|o| o.id.as_str()).collect();
+                    scoring.extend(client.are_orders_scoring(&ids).await?);
+                }
+                matches.retain(|o| !scoring.get(&o.id).copied().unwrap_or(false));
+            }
+
+            let count = matches.len() as u64;
+            let preview = Page::builder()
+                .data(matches)
+                .next_cursor(END_CURSOR)
+                .limit(count)
+                .count(count)
+                .build();
+            // JSON prints one document: the matches, or the cancel result.
+            if matches!(output, OutputFormat::Table) || dry_run || preview.data.is_empty() {
+                print_orders(&preview, output)?;
+            }
+            let prompt = format!("Cancel {count} order(s)?");
+            if preview.data.is_empty() || !super::confirm(&prompt, output, force, dry_run)? {
+                return Ok(());
+            }
+
+            let mut result = CancelOrdersResponse::default();
+            for chunk in preview.data.chunks(ORDER_ID_CHUNK) {
+                let ids: Vec<&str> = chunk.iter().map(|o| o.id.as_str()).collect();
+                let canceled = cancel_journaled(
+                    "cancel-where",
+                    &json!({"order_ids": ids}),
+                    client.cancel_orders(&ids),
+                )
+                .await?;
+                result.canceled.extend(canceled.canceled);
+                result.not_canceled.extend(canceled.not_canceled);
+            }
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::Heartbeat {
+            touch_file,
+            interval,
+            timeout,
+            markets,
+            log,
+        } => {
+            let interval = parse_duration(&interval)?;
+            let timeout = parse_duration(&timeout)?;
+            let markets = markets
+                .map(|m| {
+                    m.split(',')
+                        .map(|c| parse_condition_id(c.trim()))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?;
+            let log = match log {
+                Some(path) => path,
+                None => config::heartbeat_log_path()?,
+            };
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            run_heartbeat(&client, &touch_file, interval, timeout, markets, &log).await?;
+        }
+
+        ClobCommand::Trades {
+            command: Some(command),
+            ..
+        } => export_trades(command, private_key, signature_type).await?,
+
+        ClobCommand::Trades {
+            command: None,
+            market,
+            asset,
+            cursor,
+            pages,
+        } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = TradesRequest::builder()
+                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
+                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
+                .build();
+            let fetch = |c| client.trades(&request, c);
+            print_paged(pages, cursor, output, trade_to_json, fetch, print_trades).await?;
+        }
+
+        ClobCommand::Balance {
+            asset_type,
+            token,
+            by_outcome,
+        } => {
+            let is_collateral = matches!(asset_type, CliAssetType::Collateral);
+            let token_id = if token.is_some() || by_outcome.market.is_some() {
+                Some(resolve_token(token.as_deref(), &by_outcome).await?)
+            } else {
+                None
+            };
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = BalanceAllowanceRequest::builder()
+                .asset_type(AssetType::from(asset_type))
+                .maybe_token_id(token_id)
+                .build();
+            let result = client.balance_allowance(request).await?;
+            print_balance(&result, is_collateral, output)?;
+        }
+
+        ClobCommand::Balances { dry_run, force } => {
+            let table = matches!(output, OutputFormat::Table);
+            super::ensure_can_confirm(output, force, dry_run)?;
+            let signer = auth::resolve_signer(private_key)?;
+            let owner = auth::wallet_address(signer.address(), signature_type);
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+
+            let mut held: Vec<Position> = all_positions(owner)
+                .await?
+                .into_iter()
+                .filter(|p| p.size > Decimal::ZERO)
+                .collect();
+            held.sort_by(|a, b| {
+                a.title
+                    .cmp(&b.title)
+                    .then(a.outcome_index.cmp(&b.outcome_index))
+            });
+            held.dedup_by_key(|p| p.asset);
+            let token_ids: Vec<U256> = held.iter().map(|p| p.asset).collect();
+
+            let on_chain = super::ctf::token_balances(owner, &token_ids).await?;
+            let cached = conditional_balances(&client, &token_ids).await?;
+            let scale = Decimal::from(10u64.pow(SHARE_SCALE));
+            let balances: Vec<TokenBalance> = held
+                .into_iter()
+                .zip(on_chain)
+                .zip(cached)
+                .map(|((p, raw), cached)| {
+                    let on_chain = Decimal::from_str(&raw.to_string()).with_context(|| {
+                        format!(
+                            "On-chain balance {raw} of token {} is out of range",
+                            p.asset
+                        )
+                    })?;
+                    Ok(TokenBalance {
+                        token_id: p.asset,
+                        market: p.title,
+                        outcome: p.outcome,
+                        clob: cached.balance / scale,
+                        on_chain: on_chain / scale,
+                        approved: allowances_granted(&cached.allowances),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            if table {
+                print_token_balances(&balances, 0, output)?;
+            }
+
+            let stale: Vec<U256> = balances
+                .iter()
+                .filter(|b| b.needs_update())
+                .map(|b| b.token_id)
+                .collect();
+            if dry_run || stale.is_empty() {
+                if !table {
+                    print_token_balances(&balances, 0, output)?;
+                }
+                return Ok(());
+            }
+            let prompt = format!("Refresh {} balance allowance(s)?", stale.len());
+            if !super::confirm(&prompt, output, force, dry_run)? {
+                return Ok(());
+            }
+            refresh_conditional_balances(&client, &stale).await?;
+            if table {
+                println!("Refreshed {} balance allowance(s).", stale.len());
+            } else {
+                print_token_balances(&balances, stale.len(), output)?;
+            }
+        }
+
+        ClobCommand::UpdateBalance { asset_type, token } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let request = BalanceAllowanceRequest::builder()
+                .asset_type(AssetType::from(asset_type))
+                .maybe_token_id(token.map(|t| parse_token_id(&t)).transpose()?)
+                .build();
+            client.update_balance_allowance(request).await?;
+            match output {
+                OutputFormat::Table => println!("Balance allowance updated."),
+                OutputFormat::Json => {
+                    println!("{}", serde_json::json!({"success": true}));
+                }
+            }
+        }
+
+        ClobCommand::Notifications => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.notifications().await?;
+            print_notifications(&result, output)?;
+        }
+
+        ClobCommand::DeleteNotifications { ids } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let notification_ids: Vec<String> =
+                ids.split(',').map(|s| s.trim().to_string()).collect();
+            let request = DeleteNotificationsRequest::builder()
+                .notification_ids(notification_ids)
+                .build();
+            client.delete_notifications(&request).await?;
+            match output {
+                OutputFormat::Table => println!("Notifications deleted."),
+                OutputFormat::Json => {
+                    println!("{}", serde_json::json!({"success": true}));
+                }
+            }
+        }
+
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+async fn execute_rewards(
+    command: ClobCommand,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match command {
+        ClobCommand::Rewards {
+            date,
+            range,
+            cursor,
+            pages,
+        } => {
+            let days = range.days()?;
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            if let Some(days) = days {
+                let mut rows = Vec::new();
+                for &day in &days {
+                    let mut cursor = None;
+                    loop {
+                        let page = client.earnings_for_user_for_day(day, cursor).await?;
+                        rows.extend(page.data.iter().map(|e| EarningRow {
+                            date: e.date,
+                            condition_id: Some(e.condition_id),
+                            question: String::new(),
+                            earnings: e.earnings,
+                        }));
+                        if page.next_cursor.is_empty() || page.next_cursor == END_CURSOR {
+                            break;
+                        }
+                        cursor = Some(page.next_cursor);
+                    }
+                }
+                return range.report(&days, rows, output);
+            }
+            let date = parse_date(date.as_deref().unwrap_or_default())?;
+            let fetch = |c| client.earnings_for_user_for_day(date, c);
+            print_paged(
+                pages,
+                cursor,
+                output,
+                user_earning_to_json,
+                fetch,
+                print_rewards,
+            )
+            .await?;
+        }
+
+        ClobCommand::Earnings { date, range } => {
+            let days = range.days()?;
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            if let Some(days) = days {
+                let mut rows = Vec::new();
+                for &day in &days {
+                    let totals = client.total_earnings_for_user_for_day(day).await?;
+                    rows.extend(totals.iter().map(|e| EarningRow {
+                        date: e.date,
+                        condition_id: None,
+                        question: String::new(),
+                        earnings: e.earnings,
+                    }));
+                }
+                return range.report(&days, rows, output);
+            }
+            let result = client
+                .total_earnings_for_user_for_day(parse_date(date.as_deref().unwrap_or_default())?)
+                .await?;
+            print_earnings(&result, output)?;
+        }
+
+        ClobCommand::EarningsMarkets {
+            date,
+            range,
+            cursor,
+        } => {
+            let days = range.days()?;
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            if let Some(days) = days {
+                let mut rows = Vec::new();
+                for &day in &days {
+                    let request = UserRewardsEarningRequest::builder().date(day).build();
+                    let markets = client
+                        .user_earnings_and_markets_config(&request, None)
+                        .await?;
+                    rows.extend(markets.iter().filter_map(|m| {
+                        let earnings: Decimal = m.earnings.iter().map(|e| e.earnings).sum();
+                        (!earnings.is_zero()).then(|| EarningRow {
+                            date: day,
+                            condition_id: Some(m.condition_id),
+                            question: m.question.clone(),
+                            earnings,
+                        })
+                    }));
+                }
+                return range.report(&days, rows, output);
+            }
+            let request = UserRewardsEarningRequest::builder()
+                .date(parse_date(date.as_deref().unwrap_or_default())?)
+                .build();
+            let result = client
+                .user_earnings_and_markets_config(&request, cursor)
+                .await?;
+            print_user_earnings_markets(&result, output)?;
+        }
+
+        ClobCommand::RewardPercentages => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.reward_percentages().await?;
+            print_reward_percentages(&result, output)?;
+        }
+
+        ClobCommand::CurrentRewards { cursor, pages } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let fetch = |c| client.current_rewards(c);
+            let to_json = current_reward_to_json;
+            print_paged(pages, cursor, output, to_json, fetch, print_current_rewards).await?;
+        }
+
+        ClobCommand::MarketReward {
+            condition_id,
+            cursor,
+            pages,
+        } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let fetch = |c| client.raw_rewards_for_market(&condition_id, c);
+            let to_json = market_reward_to_json;
+            print_paged(pages, cursor, output, to_json, fetch, print_market_reward).await?;
+        }
+
+        ClobCommand::OrderScoring { order_id } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.is_order_scoring(&order_id).await?;
+            print_order_scoring(&result, output)?;
+        }
+
+        ClobCommand::OrdersScoring { order_ids } => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
+            let result = client.are_orders_scoring(&ids).await?;
+            print_orders_scoring(&result, output)?;
+        }
+
+        ClobCommand::RewardCheck {
+            token,
+            by_outcome,
+            side,
+            price,
+            size,
+        } => {
+            let price =
+                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
+            let size =
+                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            anyhow::ensure!(
+                price > Decimal::ZERO && price < Decimal::ONE,
+                "--price must be between 0 and 1"
+            );
+            anyhow::ensure!(size > Decimal::ZERO, "--size must be positive");
+            let token_id = resolve_token(token.as_deref(), &by_outcome).await?;
+
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let book = client
+                .order_book(
+                    &OrderBookSummaryRequest::builder()
+                        .token_id(token_id)
+                        .build(),
+                )
+                .await?;
+            let midpoint = client
+                .midpoint(&MidpointRequest::builder().token_id(token_id).build())
+                .await?
+                .mid;
+            let reward = client
+                .raw_rewards_for_market(&book.market.to_string(), None)
+                .await?
+                .data
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Market {} has no liquidity rewards", book.market)
+                })?;
+
+            // Resting liquidity on the other outcome competes for the same pool.
+            let complement = match market_outcomes(&book.market.to_string()).await?[..] {
+                [(_, a), (_, b)] if a == token_id || b == token_id => {
+                    let other = if a == token_id { b } else { a };
+                    let request = OrderBookSummaryRequest::builder().token_id(other).build();
+                    Some(client.order_book(&request).await?)
+                }
+                _ => None,
+            };
+
+            let usdc =
+                polymarket_client_sdk::contract_config(polymarket_client_sdk::POLYGON, false)
+                    .context("No contract config for Polygon")?
+                    .collateral;
+            let today = chrono::Utc::now().date_naive();
+            let mut check = RewardCheck {
+                question: reward.question,
+                side: side.into(),
+                price,
+                size,
+                midpoint,
+                max_spread: reward.rewards_max_spread / Decimal::ONE_HUNDRED,
+                min_size: reward.rewards_min_size,
+                daily_rate: usdc_daily_rate(&reward.rewards_config, usdc, today),
+                blocker: None,
+                score: Decimal::ZERO,
+                competing: Decimal::ZERO,
+            };
+            let complement = complement
+                .as_ref()
+                .map_or((&[][..], &[][..]), |b| (&b.bids[..], &b.asks[..]));
+            score_order(&mut check, &book.bids, &book.asks, complement);
+            print_reward_check(&check, output)?;
+        }
+
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+async fn execute_account(
+    command: ClobCommand,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match command {
+        ClobCommand::ApiKeys => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.api_keys().await?;
+            print_api_keys(&result, output)?;
+        }
+
+        ClobCommand::DeleteApiKey => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.delete_api_key().await?;
+            print_delete_api_key(&result, output)?;
+        }
+
+        ClobCommand::CreateApiKey => {
+            let signer = auth::resolve_signer(private_key)?;
+            let client = clob::Client::default();
+            let result = client.create_or_derive_api_key(&signer, None).await?;
+            print_create_api_key(&result, output)?;
+        }
+
+        ClobCommand::AccountStatus => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = client.closed_only_mode().await?;
+            print_account_status(&result, output)?;
+        }
+
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+/// Load the paper account, first filling resting orders that fresh book
+/// snapshots now cross.
+async fn paper_account() -> Result<paper::Account> {
+    let mut account = paper::load()?;
+    let tokens: HashSet<U256> = account
+        .orders
+        .iter()
+        .filter(|o| o.is_live())
+        .map(|o| o.token_id)
+        .collect();
+    let client = clob::Client::default();
+    for token_id in tokens {
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_id)
+            .build();
+        account.match_resting(&client.order_book(&request).await?);
+    }
+    paper::save(&account)?;
+    Ok(account)
+}
+
+/// `--paper` versions of the trading commands, run against the local account.
+async fn execute_paper(command: ClobCommand, output: &OutputFormat) -> Result<()> {
+    match command {
+        ClobCommand::Orders { market, asset, .. } => {
+            let market = market.map(|m| parse_condition_id(&m)).transpose()?;
+            let asset = asset.map(|a| parse_token_id(&a)).transpose()?;
+            let account = paper_account().await?;
+            let orders: Vec<OpenOrderResponse> = account
+                .orders
+                .iter()
+                .filter(|o| o.is_live())
+                .filter(|o| market.is_none_or(|m| o.market == m))
+                .filter(|o| asset.is_none_or(|a| o.token_id == a))
+                .map(|o| o.to_response(&account.trades))
+                .collect();
+            print_orders(&single_page(orders), output)?;
+        }
+
+        ClobCommand::Order { order_id } => {
+            let account = paper_account().await?;
+            let order = account
+                .orders
+                .iter()
+                .find(|o| o.id == order_id)
+                .ok_or_else(|| anyhow::anyhow!("No paper order {order_id}"))?;
+            print_order_detail(&order.to_response(&account.trades), output)?;
+        }
+
+        ClobCommand::CreateOrder {
+            token,
+            by_outcome,
+            side,
+            price,
+            size,
+            order_type,
+            post_only,
+            round,
+            wait,
+            ..
+        } => {
+            anyhow::ensure!(!wait, "--wait is not supported with --paper");
+            let token_id = resolve_order_token(token.as_deref(), &by_outcome).await?;
+            let price_dec =
+                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
+            let size_dec =
+                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            let (price_dec, size_dec) = OrderRulesCache::default()
+                .check(token_id, price_dec, size_dec, round)
+                .await?;
+
+            let mut account = paper_account().await?;
+            let request = OrderBookSummaryRequest::builder()
+                .token_id(token_id)
+                .build();
+            let book = clob::Client::default().order_book(&request).await?;
+            let result = account.place_limit(
+                &book,
+                Side::from(side),
+                price_dec,
+                size_dec,
+                OrderType::from(order_type),
+                post_only,
+            )?;
+            paper::save(&account)?;
+            print_post_order_result(&result, output)?;
+        }
+
+        ClobCommand::MarketOrder {
+            token,
+            by_outcome,
+            side,
+            amount,
+            order_type,
+            max_price,
+            min_price,
+            max_slippage_bps,
+            allow_partial,
+            wait,
+            ..
+        } => {
+            anyhow::ensure!(!wait, "--wait is not supported with --paper");
+            let sdk_side = Side::from(side);
+            let bound = market_order_bound(sdk_side, max_price, min_price)?;
+            let token_id = resolve_order_token(token.as_deref(), &by_outcome).await?;
+            let amount_dec = Decimal::from_str(&amount)
+                .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
+            let (book, estimate) = market_order_estimate(
+                token_id,
+                sdk_side,
+                amount_dec,
+                bound,
+                max_slippage_bps,
+                allow_partial,
+            )
+            .await?;
+
+            let mut account = paper_account().await?;
+            let result = account.place_market(
+                &book,
+                sdk_side,
+                &estimate.fills,
+                estimate.complete,
+                OrderType::from(order_type),
+            )?;
+            paper::save(&account)?;
+            print_market_order_result(&estimate, &result, output)?;
+        }
+
+        ClobCommand::Cancel { order_id } => {
+            let mut account = paper_account().await?;
+            let result = account.cancel_ids(&[&order_id]);
+            paper::save(&account)?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelOrders { order_ids } => {
+            let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
+            let mut account = paper_account().await?;
+            let result = account.cancel_ids(&ids);
+            paper::save(&account)?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelAll => {
+            let mut account = paper_account().await?;
+            let result = account.cancel_where(|_| true);
+            paper::save(&account)?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::CancelMarket { market, asset } => {
+            let market = market.map(|m| parse_condition_id(&m)).transpose()?;
+            let asset = asset.map(|a| parse_token_id(&a)).transpose()?;
+            let mut account = paper_account().await?;
+            let result = account.cancel_where(|o| {
+                market.is_none_or(|m| o.market == m) && asset.is_none_or(|a| o.token_id == a)
+            });
+            paper::save(&account)?;
+            print_cancel_result(&result, output)?;
+        }
+
+        ClobCommand::Trades {
+            command,
+            market,
+            asset,
+            ..
+        } => {
+            anyhow::ensure!(
+                command.is_none(),
+                "trades export is not supported with --paper"
+            );
+            let market = market.map(|m| parse_condition_id(&m)).transpose()?;
+            let asset = asset.map(|a| parse_token_id(&a)).transpose()?;
+            let account = paper_account().await?;
+            let trades: Vec<TradeResponse> = account
+                .trades
+                .iter()
+                .rev()
+                .filter(|t| market.is_none_or(|m| t.market == m))
+                .filter(|t| asset.is_none_or(|a| t.token_id == a))
+                .map(paper::PaperTrade::to_response)
+                .collect();
+            print_trades(&single_page(trades), output)?;
+        }
+
+        ClobCommand::Balance {
+            asset_type,
+            token,
+            by_outcome,
+        } => {
+            let is_collateral = matches!(asset_type, CliAssetType::Collateral);
+            let account = paper_account().await?;
+            let balance = if is_collateral {
+                account.cash
+            } else {
+                let token_id = resolve_token(token.as_deref(), &by_outcome).await?;
+                account.position(token_id)
+            };
+            let result = BalanceAllowanceResponse::builder()
+                .balance(balance * Decimal::from(1_000_000))
+                .build();
+            print_balance(&result, is_collateral, output)?;
+        }
+
+        _ => anyhow::bail!(
+            "--paper supports create-order, market-order, cancel, cancel-orders, cancel-all, \
+             cancel-market, orders, order, trades and balance"
+        ),
+    }
+    Ok(())
+}
+
+/// Prints a cursor-paged listing: the page at `cursor`, or with `--all` every
+/// page from there on.
+async fn print_paged<T, Fut>(
+    pages: AllPages,
+    cursor: Option<String>,
+    output: &OutputFormat,
+    to_json: fn(&T) -> serde_json::Value,
+    mut fetch: impl FnMut(Option<String>) -> Fut,
+    print: impl Fn(&Page<T>, &OutputFormat) -> Result<()>,
+) -> Result<()>
+where
+    Fut: Future<Output = polymarket_client_sdk::Result<Page<T>>>,
+{
+    if !pages.enabled() {
+        return print(&fetch(cursor).await?, output);
+    }
+    let fetch = |c| {
+        let page = fetch(c);
+        async move { Ok(page.await?) }
+    };
+    if let Some(items) = pages.by_cursor(*output, cursor, to_json, fetch).await? {
+        print(&single_page(items), output)?;
+    }
+    Ok(())
+}
+
+/// Wrap locally built results in a final page so the API printers can show them.
+fn single_page<T>(data: Vec<T>) -> Page<T> {
+    let count = data.len() as u64;
+    Page::builder()
+        .data(data)
+        .next_cursor(END_CURSOR)
+        .limit(count)
+        .count(count)
+        .build()
+}
+
+/// Journal entries paired with their 1-based position in the file.
+fn numbered(entries: &[Entry]) -> impl Iterator<Item = (usize, &Entry)> {
+    entries.iter().enumerate().map(|(i, e)| (i + 1, e))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn journal_csv(entries: &[Entry]) -> String {
+    let mut csv = String::from("entry,at,action,command,order_ids,request,response\n");
+    for (n, e) in numbered(entries) {
+        let fields = [
+            n.to_string(),
+            e.at.clone(),
+            e.action.to_string(),
+            e.command.clone(),
+            e.order_ids.join(" "),
+            e.request.to_string(),
+            e.response.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// One of our own fills within a trade, labelled for bookkeeping.
+#[derive(Debug)]
+struct ExportedFill {
+    time: DateTime<Utc>,
+    trade_id: String,
+    order_id: String,
+    role: &'static str,
+    market: B256,
+    question: String,
+    outcome: String,
+    asset_id: U256,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    fee_rate_bps: Decimal,
+    status: String,
+    transaction_hash: B256,
+}
+
+impl ExportedFill {
+    fn notional(&self) -> Decimal {
+        self.price * self.size
+    }
+
+    /// Fee in USDC, charged on the cheaper of the share and its complement.
+    fn fee(&self) -> Decimal {
+        let rate = self.fee_rate_bps / Decimal::from(10_000);
+        (rate * self.price.min(Decimal::ONE - self.price) * self.size).round_dp(6)
+    }
+
+    fn fields(&self) -> [String; 16] {
+        [
+            self.time.to_rfc3339(),
+            self.trade_id.clone(),
+            self.order_id.clone(),
+            self.role.to_string(),
+            self.market.to_string(),
+            self.question.clone(),
+            self.outcome.clone(),
+            self.asset_id.to_string(),
+            self.side.to_string(),
+            self.price.to_string(),
+            self.size.to_string(),
+            self.notional().to_string(),
+            self.fee_rate_bps.to_string(),
+            self.fee().to_string(),
+            self.status.clone(),
+            self.transaction_hash.to_string(),
+        ]
+    }
+}
+
+const FILL_COLUMNS: [&str; 16] = [
+    "time",
+    "trade_id",
+    "order_id",
+    "role",
+    "market",
+    "question",
+    "outcome",
+    "asset_id",
+    "side",
+    "price",
+    "size",
+    "notional",
+    "fee_rate_bps",
+    "fee",
+    "status",
+    "transaction_hash",
+];
+
+/// Our fills in `trade`: the taker order, or each of our maker orders it
+/// matched. As maker the top-level fields describe the taker's order.
+fn our_fills(trade: &TradeResponse, wallet: Address) -> Vec<ExportedFill> {
+    let status = trade.status.to_string();
+    if trade.trader_side == TraderSide::Taker {
+        return vec![ExportedFill {
+            time: trade.match_time,
+            trade_id: trade.id.clone(),
+            order_id: trade.taker_order_id.clone(),
+            role: "taker",
+            market: trade.market,
+            question: String::new(),
+            outcome: trade.outcome.clone(),
+            asset_id: trade.asset_id,
+            side: trade.side,
+            price: trade.price,
+            size: trade.size,
+            fee_rate_bps: trade.fee_rate_bps,
+            status,
+            transaction_hash: trade.transaction_hash,
+        }];
+    }
+    trade
+        .maker_orders
+        .iter()
+        .filter(|m| m.owner == trade.owner || m.maker_address == wallet)
+        .map(|m| ExportedFill {
+            time: trade.match_time,
+            trade_id: trade.id.clone(),
+            order_id: m.order_id.clone(),
+            role: "maker",
+            market: trade.market,
+            question: String::new(),
+            outcome: m.outcome.clone(),
+            asset_id: m.asset_id,
+            side: m.side,
+            price: m.price,
+            size: m.matched_amount,
+            fee_rate_bps: m.fee_rate_bps,
+            status: status.clone(),
+            transaction_hash: trade.transaction_hash,
+        })
+        .collect()
+}
+
+/// Renders `rows` under `columns` as CSV, or as a JSON array of objects.
+fn export_rows<const N: usize>(
+    format: ReportFormat,
+    columns: [&str; N],
+    rows: &[[String; N]],
+) -> serde_json::Result<String> {
+    match format {
+        ReportFormat::Csv => {
+            let mut csv = columns.join(",") + "\n";
+            for row in rows {
+                let fields: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            Ok(csv)
+        }
+        ReportFormat::Json => {
+            let objects: Vec<serde_json::Map<String, serde_json::Value>> = rows
+                .iter()
+                .map(|row| {
+                    columns
+                        .iter()
+                        .zip(row)
+                        .map(|(k, v)| ((*k).to_string(), v.as_str().into()))
+                        .collect()
+                })
+                .collect();
+            serde_json::to_string_pretty(&objects).map(|json| json + "\n")
+        }
+    }
+}
+
+/// Writes an export to `file`, or to stdout when there is none.
+fn write_export(data: &str, file: Option<&Path>, count: usize, noun: &str) -> Result<()> {
+    match file {
+        Some(path) => {
+            std::fs::write(path, data)?;
+            eprintln!("Exported {count} {noun} to {}", path.display());
+        }
+        None => print!("{data}"),
+    }
+    Ok(())
+}
+
+/// Every trade matching `request`, following cursors to the end.
+async fn all_trades(
+    client: &clob::Client<Authenticated<Normal>>,
+    request: &TradesRequest,
+) -> Result<Vec<TradeResponse>> {
+    let mut trades = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client.trades(request, cursor).await?;
+        trades.extend(page.data);
+        if page.next_cursor.is_empty() || page.next_cursor == END_CURSOR {
+            return Ok(trades);
+        }
+        cursor = Some(page.next_cursor);
+    }
+}
+
+async fn export_trades(
+    command: TradesCommand,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let TradesCommand::Export {
+        since,
+        until,
+        market,
+        format,
+        file,
+    } = command;
+    let since = since.as_deref().map(parse_time_bound).transpose()?;
+    let until = until.as_deref().map(parse_time_bound).transpose()?;
+    if let (Some(since), Some(until)) = (since, until) {
+        anyhow::ensure!(since < until, "--since must be before --until");
+    }
+    let market = market.map(|m| parse_condition_id(&m)).transpose()?;
+
+    let signer = auth::resolve_signer(private_key)?;
+    let wallet = auth::wallet_address(signer.address(), signature_type);
+    let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+    let request = TradesRequest::builder()
+        .maybe_market(market)
+        .maybe_after(since.map(|t| t.timestamp()))
+        .maybe_before(until.map(|t| t.timestamp()))
+        .build();
+    let trades = all_trades(&client, &request).await?;
+
+    let mut fills: Vec<ExportedFill> = trades
+        .iter()
+        .filter(|t| since.is_none_or(|s| t.match_time >= s))
+        .filter(|t| until.is_none_or(|u| t.match_time < u))
+        .flat_map(|t| our_fills(t, wallet))
+        .collect();
+    fills.sort_by_key(|f| f.time);
+
+    let mut questions: HashMap<B256, String> = HashMap::new();
+    for fill in &mut fills {
+        let question = match questions.entry(fill.market) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                let id = fill.market.to_string();
+                let market = client
+                    .market(&id)
+                    .await
+                    .with_context(|| format!("Failed to look up market {id}"))?;
+                e.insert(market.question)
+            }
+        };
+        fill.question.clone_from(question);
+    }
+
+    let rows: Vec<_> = fills.iter().map(ExportedFill::fields).collect();
+    let data = export_rows(format, FILL_COLUMNS, &rows)?;
+    write_export(&data, file.as_deref(), fills.len(), "fills")
+}
+
+/// Trades in `trades` that involve a posted order from the journal and have
+/// not been journaled as fills yet.
+fn new_fills<'a>(
+    entries: &[Entry],
+    trades: &'a [TradeResponse],
+) -> Vec<(&'a TradeResponse, Vec<String>)> {
+    let posted: HashSet<&str> = entries
+        .iter()
+        .filter(|e| e.action == Action::Post)
+        .flat_map(|e| e.order_ids.iter().map(String::as_str))
+        .collect();
+    let recorded: HashSet<&str> = entries
+        .iter()
+        .filter(|e| e.action == Action::Fill)
+        .filter_map(|e| e.response["id"].as_str())
+        .collect();
+    trades
+        .iter()
+        .filter(|t| !recorded.contains(t.id.as_str()))
+        .filter_map(|t| {
+            let ids: Vec<String> = std::iter::once(&t.taker_order_id)
+                .chain(t.maker_orders.iter().map(|m| &m.order_id))
+                .filter(|id| posted.contains(id.as_str()))
+                .cloned()
+                .collect();
+            (!ids.is_empty()).then_some((t, ids))
+        })
+        .collect()
+}
+
+async fn execute_journal(
+    command: JournalCommand,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let entries = journal::load()?;
+    match command {
+        JournalCommand::List {
+            order,
+            action,
+            limit,
+        } => {
+            let mut matching: Vec<(usize, &Entry)> = numbered(&entries)
+                .filter(|(_, e)| action.is_none_or(|a| e.action == a))
+                .filter(|(_, e)| order.as_ref().is_none_or(|id| e.order_ids.contains(id)))
+                .collect();
+            if let Some(limit) = limit {
+                matching.drain(..matching.len().saturating_sub(limit));
+            }
+            print_journal(&matching, output)?;
+        }
+
+        JournalCommand::Show { entry } => {
+            let found = entry
+                .checked_sub(1)
+                .and_then(|i| entries.get(i))
+                .ok_or_else(|| anyhow::anyhow!("No journal entry #{entry}"))?;
+            print_journal_entry(entry, found, output)?;
+        }
+
+        JournalCommand::Export { format, file } => {
+            let data = match format {
+                ExportFormat::Jsonl => entries
+                    .iter()
+                    .map(|e| serde_json::to_string(e).map(|line| line + "\n"))
+                    .collect::<serde_json::Result<String>>()?,
+                ExportFormat::Csv => journal_csv(&entries),
+            };
+            write_export(&data, file.as_deref(), entries.len(), "entries")?;
+        }
+
+        JournalCommand::Sync => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let trades = all_trades(&client, &TradesRequest::builder().build()).await?;
+            let fills = new_fills(&entries, &trades);
+            for (trade, order_ids) in &fills {
+                journal::record(&Entry::new(
+                    Action::Fill,
+                    "journal sync",
+                    order_ids.clone(),
+                    serde_json::Value::Null,
+                    trade_to_json(trade),
+                ));
+            }
+            print_journal_synced(fills.len(), output)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn parse_token_id_valid_numeric() {
+        let id = parse_token_id("12345").unwrap();
+        assert_eq!(id, U256::from(12345u64));
+    }
+
+    #[test]
+    fn parse_token_id_large_number() {
+        let id = parse_token_id(
+            "48331043336612883890938759509493159234755048973583954730006854632066573",
+        )
+        .unwrap();
+        assert!(id > U256::ZERO);
+    }
+
+    #[test]
+    fn parse_token_id_zero() {
+        let id = parse_token_id("0").unwrap();
+        assert_eq!(id, U256::ZERO);
+    }
+
+    #[test]
+    fn parse_token_id_invalid() {
+        assert!(parse_token_id("abc").is_err());
+        assert!(parse_token_id("12.34").is_err());
+        assert!(parse_token_id("-1").is_err());
+    }
+
+    #[test]
+    fn parse_token_ids_single() {
+        let ids = parse_token_ids("100").unwrap();
+        assert_eq!(ids, vec![U256::from(100u64)]);
+    }
+
+    #[test]
+    fn parse_token_ids_multiple() {
+        let ids = parse_token_ids("1,2,3").unwrap();
+        assert_eq!(
+            ids,
+            vec![U256::from(1u64), U256::from(2u64), U256::from(3u64)]
+        );
+    }
+
+    #[test]
+    fn parse_token_ids_with_spaces() {
+        let ids = parse_token_ids("1, 2, 3").unwrap();
+        assert_eq!(
+            ids,
+            vec![U256::from(1u64), U256::from(2u64), U256::from(3u64)]
+        );
+    }
+
+    #[test]
+    fn parse_token_ids_invalid_entry() {
+        assert!(parse_token_ids("1,abc,3").is_err());
+    }
+
+    #[test]
+    fn parse_date_valid() {
+        let d = parse_date("2024-06-15").unwrap();
+        assert_eq!(d.to_string(), "2024-06-15");
+    }
+
+    #[test]
+    fn parse_date_leap_day() {
+        let d = parse_date("2024-02-29").unwrap();
+        assert_eq!(d.to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn parse_date_invalid_format() {
+        assert!(parse_date("06/15/2024").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+        assert!(parse_date("not-a-date").is_err());
+        assert!(parse_date("").is_err());
+    }
+
+    #[test]
+    fn ladder_flat_spreads_evenly() {
+        let ladder = ladder_levels(
+            dec!(0.40),
+            dec!(0.48),
+            5,
+            dec!(500),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        assert_eq!(
+            ladder,
+            vec![
+                (dec!(0.40), dec!(100)),
+                (dec!(0.42), dec!(100)),
+                (dec!(0.44), dec!(100)),
+                (dec!(0.46), dec!(100)),
+                (dec!(0.48), dec!(100)),
+            ]
+        );
+    }
+
+    #[test]
+    fn ladder_linear_weights_grow_toward_last_level() {
+        let ladder = ladder_levels(
+            dec!(0.40),
+            dec!(0.42),
+            3,
+            dec!(60),
+            Distribution::Linear,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        let sizes: Vec<Decimal> = ladder.iter().map(|(_, s)| *s).collect();
+        assert_eq!(sizes, vec![dec!(10), dec!(20), dec!(30)]);
+    }
+
+    #[test]
+    fn ladder_geometric_uses_ratio() {
+        let ladder = ladder_levels(
+            dec!(0.50),
+            dec!(0.52),
+            3,
+            dec!(70),
+            Distribution::Geometric,
+            dec!(2),
+            dec!(0.01),
+        )
+        .unwrap();
+        let sizes: Vec<Decimal> = ladder.iter().map(|(_, s)| *s).collect();
+        assert_eq!(sizes, vec![dec!(10), dec!(20), dec!(40)]);
+    }
+
+    #[test]
+    fn ladder_remainder_goes_to_last_level() {
+        let ladder = ladder_levels(
+            dec!(0.40),
+            dec!(0.42),
+            3,
+            dec!(100),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        let sizes: Vec<Decimal> = ladder.iter().map(|(_, s)| *s).collect();
+        assert_eq!(sizes, vec![dec!(33.33), dec!(33.33), dec!(33.34)]);
+    }
+
+    #[test]
+    fn ladder_snaps_prices_to_tick() {
+        let ladder = ladder_levels(
+            dec!(0.40),
+            dec!(0.45),
+            3,
+            dec!(30),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        let prices: Vec<Decimal> = ladder.iter().map(|(p, _)| *p).collect();
+        assert_eq!(prices, vec![dec!(0.40), dec!(0.43), dec!(0.45)]);
+    }
+
+    #[test]
+    fn ladder_descending_range() {
+        let ladder = ladder_levels(
+            dec!(0.60),
+            dec!(0.50),
+            3,
+            dec!(30),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        let prices: Vec<Decimal> = ladder.iter().map(|(p, _)| *p).collect();
+        assert_eq!(prices, vec![dec!(0.60), dec!(0.55), dec!(0.50)]);
+    }
+
+    #[test]
+    fn ladder_rejects_levels_that_collide_on_tick_grid() {
+        let err = ladder_levels(
+            dec!(0.40),
+            dec!(0.42),
+            5,
+            dec!(50),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("tick grid"), "got: {err}");
+    }
+
+    #[test]
+    fn ladder_rejects_prices_outside_unit_interval() {
+        assert!(
+            ladder_levels(
+                dec!(0.90),
+                dec!(1.10),
+                3,
+                dec!(30),
+                Distribution::Flat,
+                dec!(1.5),
+                dec!(0.01),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn ladder_rejects_size_too_small_for_levels() {
+        assert!(
+            ladder_levels(
+                dec!(0.40),
+                dec!(0.44),
+                5,
+                dec!(0.02),
+                Distribution::Flat,
+                dec!(1.5),
+                dec!(0.01),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn ladder_single_level_uses_from_price() {
+        let ladder = ladder_levels(
+            dec!(0.40),
+            dec!(0.48),
+            1,
+            dec!(25),
+            Distribution::Flat,
+            dec!(1.5),
+            dec!(0.01),
+        )
+        .unwrap();
+        assert_eq!(ladder, vec![(dec!(0.40), dec!(25))]);
+    }
+
+    #[test]
+    fn twap_slice_spreads_remainder_evenly() {
+        assert_eq!(twap_slice(dec!(1000), 10), dec!(100));
+        assert_eq!(twap_slice(dec!(100), 3), dec!(33.33));
+    }
+
+    #[test]
+    fn twap_slice_last_takes_everything() {
+        assert_eq!(twap_slice(dec!(33.34), 1), dec!(33.34));
+    }
+
+    fn report(side: Side, filled: Decimal, notional: Decimal) -> ExecutionReport {
+        ExecutionReport {
+            algo: "twap",
+            side,
+            target: dec!(100),
+            filled,
+            notional,
+            arrival_mid: dec!(0.50),
+            orders: 1,
+            interrupted: false,
+        }
+    }
+
+    #[test]
+    fn execution_report_slippage_buy_above_mid_is_positive() {
+        let r = report(Side::Buy, dec!(100), dec!(51));
+        assert_eq!(r.average_price(), Some(dec!(0.51)));
+        assert_eq!(r.slippage_bps(), Some(dec!(200)));
+    }
+
+    #[test]
+    fn execution_report_slippage_sell_above_mid_is_negative() {
+        let r = report(Side::Sell, dec!(100), dec!(51));
+        assert_eq!(r.slippage_bps(), Some(dec!(-200)));
+    }
+
+    #[test]
+    fn execution_report_without_fills_has_no_average() {
+        let r = report(Side::Buy, Decimal::ZERO, Decimal::ZERO);
+        assert_eq!(r.average_price(), None);
+        assert_eq!(r.slippage_bps(), None);
+    }
+
+    fn level(price: Decimal, size: Decimal) -> OrderSummary {
+        OrderSummary::builder().price(price).size(size).build()
+    }
+
+    #[test]
+    fn estimate_fill_buy_walks_asks_best_last() {
+        // Asks as returned by the book: worst first, best last.
+        let asks = [level(dec!(0.55), dec!(100)), level(dec!(0.50), dec!(100))];
+        let e = estimate_fill(&asks, Side::Buy, dec!(77.5), QuoteUnit::Usdc).unwrap();
+        assert_eq!(e.best_price, dec!(0.50));
+        assert_eq!(e.worst_price, dec!(0.55));
+        assert_eq!(e.shares, dec!(150));
+        assert_eq!(e.notional, dec!(77.5));
+        assert!(e.complete);
+        assert_eq!(e.slippage_bps(), dec!(333.3));
+    }
+
+    #[test]
+    fn estimate_fill_sell_sized_in_shares() {
+        let bids = [level(dec!(0.40), dec!(50)), level(dec!(0.45), dec!(20))];
+        let e = estimate_fill(&bids, Side::Sell, dec!(30), QuoteUnit::Shares).unwrap();
+        assert_eq!(e.shares, dec!(30));
+        assert_eq!(e.notional, dec!(13));
+        assert_eq!(e.worst_price, dec!(0.40));
+        assert!(e.complete);
+    }
+
+    #[test]
+    fn estimate_fill_single_level_has_no_slippage() {
+        let asks = [level(dec!(0.50), dec!(100))];
+        let e = estimate_fill(&asks, Side::Buy, dec!(10), QuoteUnit::Usdc).unwrap();
+        assert_eq!(e.average_price(), dec!(0.50));
+        assert_eq!(e.slippage_bps(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn estimate_fill_flags_thin_book() {
+        let bids = [level(dec!(0.45), dec!(20))];
+        let e = estimate_fill(&bids, Side::Sell, dec!(30), QuoteUnit::Shares).unwrap();
+        assert_eq!(e.shares, dec!(20));
+        assert!(!e.complete);
+    }
+
+    #[test]
+    fn ensure_fillable_refuses_thin_book() {
+        let bids = [level(dec!(0.45), dec!(20))];
+        let thin = estimate_fill(&bids, Side::Sell, dec!(30), QuoteUnit::Shares).unwrap();
+        let err = ensure_fillable(&thin, dec!(30), QuoteUnit::Shares).unwrap_err();
+        assert!(err.to_string().contains("only 20 of 30 shares"), "{err}");
+        assert!(err.to_string().contains("--allow-partial"), "{err}");
+
+        let deep = estimate_fill(&bids, Side::Sell, dec!(15), QuoteUnit::Shares).unwrap();
+        assert!(ensure_fillable(&deep, dec!(15), QuoteUnit::Shares).is_ok());
+    }
+
+    #[test]
+    fn estimate_fill_empty_book_errors() {
+        assert!(estimate_fill(&[], Side::Buy, dec!(10), QuoteUnit::Usdc).is_err());
+    }
+
+    #[test]
+    fn estimate_fill_buy_in_shares() {
+        let asks = [level(dec!(0.55), dec!(100)), level(dec!(0.50), dec!(100))];
+        let e = estimate_fill(&asks, Side::Buy, dec!(120), QuoteUnit::Shares).unwrap();
+        assert_eq!(e.shares, dec!(120));
+        assert_eq!(e.notional, dec!(61));
+        assert_eq!(
+            e.fills,
+            vec![(dec!(0.50), dec!(100)), (dec!(0.55), dec!(20))]
+        );
+    }
+
+    #[test]
+    fn fill_estimate_fee_uses_cheaper_side_of_price() {
+        let asks = [level(dec!(0.80), dec!(100)), level(dec!(0.40), dec!(100))];
+        let e = estimate_fill(&asks, Side::Buy, dec!(200), QuoteUnit::Shares).unwrap();
+        // 100 @ 0.40 -> 0.40, 100 @ 0.80 -> 0.20, at 100 bps
+        assert_eq!(e.fee(100), dec!(0.6));
+        assert_eq!(e.fee(0), Decimal::ZERO);
+    }
+
+    #[test]
+    fn quote_book_impact_against_midpoint() {
+        let book = OrderBookSummaryResponse::builder()
+            .market(polymarket_client_sdk::types::B256::ZERO)
+            .asset_id(U256::from(7))
+            .timestamp(chrono::Utc::now())
+            .bids(vec![level(dec!(0.48), dec!(100))])
+            .asks(vec![
+                level(dec!(0.55), dec!(100)),
+                level(dec!(0.52), dec!(100)),
+            ])
+            .min_order_size(dec!(5))
+            .neg_risk(false)
+            .tick_size(polymarket_client_sdk::clob::types::TickSize::Hundredth)
+            .build();
+        let q = quote_book(&book, Side::Buy, dec!(150), QuoteUnit::Shares, 0).unwrap();
+        assert_eq!(q.token_id, U256::from(7));
+        assert_eq!(q.midpoint, Some(dec!(0.50)));
+        assert_eq!(q.estimate.fills.len(), 2);
+        // avg 0.53 vs mid 0.50
+        assert_eq!(q.impact_bps(), Some(dec!(600)));
+    }
+
+    fn outcomes(names: &[&str]) -> Vec<(String, U256)> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| ((*n).to_string(), U256::from(i + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn pick_outcome_exact_match_is_case_insensitive() {
+        let o = outcomes(&["Yes", "No"]);
+        assert_eq!(pick_outcome(&o, "yes", false).unwrap(), U256::from(1));
+        assert_eq!(pick_outcome(&o, "NO", false).unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn pick_outcome_exact_match_beats_prefix() {
+        let o = outcomes(&["Over 2.5", "Over"]);
+        assert_eq!(pick_outcome(&o, "over", true).unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn pick_outcome_unique_prefix() {
+        let o = outcomes(&["Lakers", "Celtics"]);
+        assert_eq!(pick_outcome(&o, "cel", true).unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn pick_outcome_for_orders_rejects_prefix() {
+        let o = outcomes(&["Yes", "No"]);
+        let err = pick_outcome(&o, "N", false).unwrap_err().to_string();
+        assert!(err.contains("full outcome name"), "got: {err}");
+        assert_eq!(pick_outcome(&o, "n", true).unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn pick_outcome_ambiguous_prefix_errors() {
+        let o = outcomes(&["Manchester United", "Manchester City"]);
+        let err = pick_outcome(&o, "manchester", true)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ambiguous"), "got: {err}");
+        assert!(err.contains("Manchester City"), "got: {err}");
+    }
+
+    #[test]
+    fn pick_outcome_unknown_lists_available() {
+        let o = outcomes(&["Yes", "No"]);
+        let err = pick_outcome(&o, "Maybe", true).unwrap_err().to_string();
+        assert!(err.contains("available: Yes, No"), "got: {err}");
+    }
+
+    #[test]
+    fn touch_age_counts_from_last_touch() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let touched = started + Duration::from_secs(50);
+        let now = started + Duration::from_secs(80);
+        assert_eq!(
+            touch_age(Some(touched), started, now),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn touch_age_gives_grace_from_start() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let stale = started - Duration::from_secs(3_600);
+        let now = started + Duration::from_secs(5);
+        assert_eq!(touch_age(Some(stale), started, now), Duration::from_secs(5));
+        assert_eq!(touch_age(None, started, now), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn parse_ticks_accepts_ticks_and_prices() {
+        assert_eq!(parse_ticks("2t", dec!(0.01)).unwrap(), dec!(0.02));
+        assert_eq!(parse_ticks("0.03", dec!(0.01)).unwrap(), dec!(0.03));
+        assert!(parse_ticks("twot", dec!(0.01)).is_err());
+    }
+
+    #[test]
+    fn make_quotes_symmetric_around_mid() {
+        let q = make_quotes(
+            dec!(0.50),
+            dec!(0.01),
+            dec!(0.02),
+            dec!(100),
+            dec!(200),
+            dec!(500),
+            false,
+        );
+        let (bid, _) = q.bid.unwrap();
+        let (ask, _) = q.ask.unwrap();
+        assert_eq!(dec!(0.50) - bid, ask - dec!(0.50));
+        assert_eq!((bid, ask), (dec!(0.48), dec!(0.52)));
+    }
+
+    #[test]
+    fn make_quotes_bids_only_without_inventory() {
+        let q = make_quotes(
+            dec!(0.515),
+            dec!(0.01),
+            dec!(0.01),
+            dec!(100),
+            Decimal::ZERO,
+            dec!(500),
+            false,
+        );
+        assert_eq!(q.bid, Some((dec!(0.50), dec!(100))));
+        // Nothing held, so nothing to offer.
+        assert_eq!(q.ask, None);
+    }
+
+    #[test]
+    fn make_quotes_caps_sizes_by_inventory() {
+        let q = make_quotes(
+            dec!(0.50),
+            dec!(0.01),
+            dec!(0.01),
+            dec!(100),
+            dec!(450),
+            dec!(500),
+            false,
+        );
+        assert_eq!(q.bid, Some((dec!(0.49), dec!(50))));
+        assert_eq!(q.ask, Some((dec!(0.51), dec!(100))));
+
+        let full = make_quotes(
+            dec!(0.50),
+            dec!(0.01),
+            dec!(0.01),
+            dec!(100),
+            dec!(500),
+            dec!(500),
+            false,
+        );
+        assert_eq!(full.bid, None);
+    }
+
+    #[test]
+    fn make_quotes_skew_leans_against_inventory() {
+        let q = make_quotes(
+            dec!(0.50),
+            dec!(0.01),
+            dec!(0.02),
+            dec!(100),
+            dec!(250),
+            dec!(500),
+            true,
+        );
+        // Half way to the cap shifts both quotes down by half the half-spread.
+        assert_eq!(q.bid, Some((dec!(0.47), dec!(100))));
+        assert_eq!(q.ask, Some((dec!(0.51), dec!(100))));
+    }
+
+    #[test]
+    fn make_quotes_stay_inside_price_bounds() {
+        let q = make_quotes(
+            dec!(0.995),
+            dec!(0.01),
+            dec!(0.02),
+            dec!(10),
+            dec!(10),
+            dec!(500),
+            false,
+        );
+        assert_eq!(q.ask, Some((dec!(0.99), dec!(10))));
+        assert_eq!(q.bid, Some((dec!(0.97), dec!(10))));
+    }
+
+    #[test]
+    fn close_size_full_position_truncates_dust() {
+        assert_eq!(close_size(dec!(123.456789), dec!(100)), dec!(123.45));
+    }
+
+    #[test]
+    fn close_size_fraction() {
+        assert_eq!(close_size(dec!(101), dec!(50)), dec!(50.5));
+        assert_eq!(close_size(dec!(0.01), dec!(50)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn slippage_floor_rounds_up_to_tick() {
+        assert_eq!(slippage_floor(dec!(0.62), 500, dec!(0.01)), dec!(0.59));
+        assert_eq!(slippage_floor(dec!(0.5), 0, dec!(0.001)), dec!(0.5));
+    }
+
+    #[test]
+    fn slippage_floor_never_below_tick() {
+        assert_eq!(slippage_floor(dec!(0.02), 10_000, dec!(0.01)), dec!(0.01));
+    }
+
+    fn open_order(side: Side, price: Decimal, age_secs: i64) -> OpenOrderResponse {
+        OpenOrderResponse::builder()
+            .id("0xorder")
+            .status(OrderStatusType::Live)
+            .owner(Default::default())
+            .maker_address(Address::ZERO)
+            .market(B256::ZERO)
+            .asset_id(U256::from(7))
+            .side(side)
+            .original_size(dec!(10))
+            .size_matched(Decimal::ZERO)
+            .price(price)
+            .associate_trades(Vec::new())
+            .outcome("Yes")
+            .created_at(chrono::Utc::now() - chrono::Duration::seconds(age_secs))
+            .expiration(chrono::Utc::now())
+            .order_type(OrderType::GTC)
+            .build()
+    }
+
+    #[test]
+    fn wait_outcome_live_order_keeps_waiting() {
+        let order = open_order(Side::Buy, dec!(0.5), 0);
+        assert_eq!(wait_outcome(&order, WaitUntil::Filled), None);
+        assert_eq!(wait_outcome(&order, WaitUntil::Partial), None);
+    }
 
-        ClobCommand::Balance { asset_type, token } => {
-            let is_collateral = matches!(asset_type, CliAssetType::Collateral);
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = BalanceAllowanceRequest::builder()
-                .asset_type(AssetType::from(asset_type))
-                .maybe_token_id(token.map(|t| parse_token_id(&t)).transpose()?)
-                .build();
-            let result = client.balance_allowance(request).await?;
-            print_balance(&result, is_collateral, output)?;
-        }
+    #[test]
+    fn wait_outcome_partial_fill_only_stops_partial() {
+        let mut order = open_order(Side::Buy, dec!(0.5), 0);
+        order.size_matched = dec!(4);
+        assert_eq!(wait_outcome(&order, WaitUntil::Filled), None);
+        assert_eq!(
+            wait_outcome(&order, WaitUntil::Partial),
+            Some(WaitOutcome::PartiallyFilled)
+        );
+    }
 
-        ClobCommand::UpdateBalance { asset_type, token } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = BalanceAllowanceRequest::builder()
-                .asset_type(AssetType::from(asset_type))
-                .maybe_token_id(token.map(|t| parse_token_id(&t)).transpose()?)
-                .build();
-            client.update_balance_allowance(request).await?;
-            match output {
-                OutputFormat::Table => println!("Balance allowance updated."),
-                OutputFormat::Json => {
-                    println!("{}", serde_json::json!({"success": true}));
-                }
-            }
-        }
+    #[test]
+    fn wait_until_cancelled_stops_on_fill_with_exit_six() {
+        let mut order = open_order(Side::Buy, dec!(0.5), 0);
+        order.size_matched = order.original_size;
+        let outcome = wait_outcome(&order, WaitUntil::Cancelled).unwrap();
+        assert_eq!(outcome, WaitOutcome::Filled);
+        let err = wait_exit(outcome, WaitUntil::Cancelled).unwrap_err();
+        assert_eq!(err.downcast::<ExitStatus>().unwrap().0, 6);
+    }
 
-        ClobCommand::Notifications => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.notifications().await?;
-            print_notifications(&result, output)?;
-        }
+    #[test]
+    fn wait_exit_succeeds_only_at_target() {
+        assert!(wait_exit(WaitOutcome::Filled, WaitUntil::Filled).is_ok());
+        assert!(wait_exit(WaitOutcome::Filled, WaitUntil::Partial).is_ok());
+        assert!(wait_exit(WaitOutcome::PartiallyFilled, WaitUntil::Partial).is_ok());
+        assert!(wait_exit(WaitOutcome::Canceled, WaitUntil::Cancelled).is_ok());
+
+        let code = |outcome, until| {
+            wait_exit(outcome, until)
+                .unwrap_err()
+                .downcast::<ExitStatus>()
+                .unwrap()
+                .0
+        };
+        assert_eq!(code(WaitOutcome::Filled, WaitUntil::Cancelled), 6);
+        assert_eq!(code(WaitOutcome::Canceled, WaitUntil::Filled), 4);
+        assert_eq!(code(WaitOutcome::PartiallyFilled, WaitUntil::Filled), 3);
+        assert_eq!(code(WaitOutcome::TimedOut, WaitUntil::Cancelled), 5);
+    }
 
-        ClobCommand::DeleteNotifications { ids } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let notification_ids: Vec<String> =
-                ids.split(',').map(|s| s.trim().to_string()).collect();
-            let request = DeleteNotificationsRequest::builder()
-                .notification_ids(notification_ids)
-                .build();
-            client.delete_notifications(&request).await?;
-            match output {
-                OutputFormat::Table => println!("Notifications deleted."),
-                OutputFormat::Json => {
-                    println!("{}", serde_json::json!({"success": true}));
-                }
-            }
-        }
+    #[test]
+    fn wait_outcome_terminal_states() {
+        let mut order = open_order(Side::Buy, dec!(0.5), 0);
+        order.size_matched = dec!(10);
+        assert_eq!(
+            wait_outcome(&order, WaitUntil::Cancelled),
+            Some(WaitOutcome::Filled)
+        );
+        order.size_matched = dec!(4);
+        order.status = OrderStatusType::Canceled;
+        assert_eq!(
+            wait_outcome(&order, WaitUntil::Partial),
+            Some(WaitOutcome::Canceled)
+        );
+    }
 
-        _ => unreachable!(),
+    fn trade(id: &str, taker_order_id: &str) -> TradeResponse {
+        TradeResponse::builder()
+            .id(id)
+            .taker_order_id(taker_order_id)
+            .market(B256::ZERO)
+            .asset_id(U256::from(7))
+            .side(Side::Buy)
+            .size(dec!(10))
+            .fee_rate_bps(Decimal::ZERO)
+            .price(dec!(0.5))
+            .status(polymarket_client_sdk::clob::types::TradeStatusType::Confirmed)
+            .match_time(chrono::Utc::now())
+            .last_update(chrono::Utc::now())
+            .outcome("Yes")
+            .bucket_index(0)
+            .owner(Default::default())
+            .maker_address(Address::ZERO)
+            .maker_orders(Vec::new())
+            .transaction_hash(B256::ZERO)
+            .trader_side(polymarket_client_sdk::clob::types::TraderSide::Taker)
+            .build()
     }
 
-    Ok(())
-}
+    #[test]
+    fn order_fills_prices_at_trade_not_limit() {
+        let mut order = open_order(Side::Buy, dec!(0.5), 0);
+        order.size_matched = dec!(12);
+        let mut t = trade("t1", "0xorder");
+        t.price = dec!(0.45);
+        // 10 shares matched at 0.45; the other 2 aren't in the trades feed yet.
+        let (size, notional) = order_fills(&order, &[t, trade("t2", "0xother")]);
+        assert_eq!(size, dec!(12));
+        assert_eq!(notional, dec!(5.5));
+    }
 
-async fn execute_rewards(
-    command: ClobCommand,
-    output: &OutputFormat,
-    private_key: Option<&str>,
-    signature_type: Option<&str>,
-) -> Result<()> {
-    match command {
-        ClobCommand::Rewards { date, cursor } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client
-                .earnings_for_user_for_day(parse_date(&date)?, cursor)
-                .await?;
-            print_rewards(&result, output)?;
-        }
+    #[test]
+    fn new_fills_matches_posted_orders_once() {
+        let null = serde_json::Value::Null;
+        let entries = vec![
+            Entry::new(
+                Action::Post,
+                "create-order",
+                vec!["0xa".into()],
+                null.clone(),
+                null.clone(),
+            ),
+            Entry::new(
+                Action::Fill,
+                "journal sync",
+                vec!["0xa".into()],
+                null,
+                json!({"id": "t1"}),
+            ),
+        ];
+        let trades = [
+            trade("t1", "0xa"),
+            trade("t2", "0xa"),
+            trade("t3", "0xother"),
+        ];
+        let fills = new_fills(&entries, &trades);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].0.id, "t2");
+        assert_eq!(fills[0].1, vec!["0xa".to_string()]);
+    }
 
-        ClobCommand::Earnings { date } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client
-                .total_earnings_for_user_for_day(parse_date(&date)?)
-                .await?;
-            print_earnings(&result, output)?;
-        }
+    #[test]
+    fn journal_csv_quotes_json_fields() {
+        let entries = vec![Entry::new(
+            Action::Cancel,
+            "cancel",
+            vec!["0xa".into()],
+            json!({"order_id": "0xa"}),
+            json!({"canceled": ["0xa"]}),
+        )];
+        let csv = journal_csv(&entries);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("entry,at,action,command,order_ids,request,response")
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("1,"));
+        assert!(row.ends_with(
+            r#",cancel,cancel,0xa,"{""order_id"":""0xa""}","{""canceled"":[""0xa""]}""#
+        ));
+    }
 
-        ClobCommand::EarningsMarkets { date, cursor } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let request = UserRewardsEarningRequest::builder()
-                .date(parse_date(&date)?)
-                .build();
-            let result = client
-                .user_earnings_and_markets_config(&request, cursor)
-                .await?;
-            print_user_earnings_markets(&result, output)?;
-        }
+    #[test]
+    fn our_fills_as_taker_charges_fee_on_cheaper_side() {
+        let mut t = trade("t1", "0xa");
+        t.price = dec!(0.8);
+        t.fee_rate_bps = dec!(200);
+        let fills = our_fills(&t, Address::ZERO);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].role, "taker");
+        assert_eq!(fills[0].order_id, "0xa");
+        assert_eq!(fills[0].notional(), dec!(8));
+        // 2% of min(0.8, 0.2) on 10 shares.
+        assert_eq!(fills[0].fee(), dec!(0.04));
+    }
 
-        ClobCommand::RewardPercentages => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.reward_percentages().await?;
-            print_reward_percentages(&result, output)?;
-        }
+    #[test]
+    fn our_fills_as_maker_keeps_only_our_orders() {
+        let wallet = Address::repeat_byte(1);
+        let maker = |id: &str, address: Address| {
+            polymarket_client_sdk::clob::types::response::MakerOrder::builder()
+                .order_id(id)
+                .owner(uuid_owner(address == wallet))
+                .maker_address(address)
+                .matched_amount(dec!(4))
+                .price(dec!(0.3))
+                .fee_rate_bps(Decimal::ZERO)
+                .asset_id(U256::from(9))
+                .outcome("No")
+                .side(Side::Sell)
+                .build()
+        };
+        let mut t = trade("t1", "0xtaker");
+        t.trader_side = TraderSide::Maker;
+        t.owner = uuid_owner(true);
+        t.maker_orders = vec![maker("0xours", wallet), maker("0xtheirs", Address::ZERO)];
+
+        let fills = our_fills(&t, wallet);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].role, "maker");
+        assert_eq!(fills[0].order_id, "0xours");
+        assert_eq!(fills[0].outcome, "No");
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].notional(), dec!(1.2));
+    }
 
-        ClobCommand::CurrentRewards { cursor } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.current_rewards(cursor).await?;
-            print_current_rewards(&result, output)?;
-        }
+    fn uuid_owner(ours: bool) -> polymarket_client_sdk::auth::ApiKey {
+        polymarket_client_sdk::auth::ApiKey::from_u128(u128::from(ours))
+    }
 
-        ClobCommand::MarketReward {
-            condition_id,
-            cursor,
-        } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.raw_rewards_for_market(&condition_id, cursor).await?;
-            print_market_reward(&result, output)?;
-        }
+    #[test]
+    fn export_rows_quotes_csv_fields() {
+        let mut fills = our_fills(&trade("t1", "0xa"), Address::ZERO);
+        fills[0].question = "Will it rain, or not?".into();
+        let rows = [fills[0].fields()];
+        let csv = export_rows(ReportFormat::Csv, FILL_COLUMNS, &rows).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(FILL_COLUMNS.join(",").as_str()));
+        let row = lines.next().unwrap();
+        assert!(row.contains(",taker,"));
+        assert!(row.contains(r#","Will it rain, or not?",Yes,7,BUY,0.5,10,5.0,0,0,CONFIRMED,"#));
+    }
 
-        ClobCommand::OrderScoring { order_id } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.is_order_scoring(&order_id).await?;
-            print_order_scoring(&result, output)?;
+    fn reward_check(side: Side, price: Decimal, size: Decimal) -> RewardCheck {
+        RewardCheck {
+            question: String::new(),
+            side,
+            price,
+            size,
+            midpoint: dec!(0.50),
+            max_spread: dec!(0.03),
+            min_size: dec!(20),
+            daily_rate: dec!(100),
+            blocker: None,
+            score: Decimal::ZERO,
+            competing: Decimal::ZERO,
         }
+    }
 
-        ClobCommand::OrdersScoring { order_ids } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
-            let result = client.are_orders_scoring(&ids).await?;
-            print_orders_scoring(&result, output)?;
-        }
+    #[test]
+    fn reward_score_decays_quadratically_to_the_max_spread() {
+        assert_eq!(reward_score(dec!(0), dec!(0.03), dec!(100)), dec!(100));
+        assert_eq!(reward_score(dec!(0.015), dec!(0.03), dec!(100)), dec!(25));
+        assert_eq!(
+            reward_score(dec!(0.03), dec!(0.03), dec!(100)),
+            Decimal::ZERO
+        );
+    }
 
-        _ => unreachable!(),
+    #[test]
+    fn two_sided_score_discounts_one_sided_liquidity_inside_the_band() {
+        assert_eq!(two_sided_score(dec!(0.5), dec!(30), dec!(0)), dec!(10));
+        assert_eq!(two_sided_score(dec!(0.5), dec!(30), dec!(20)), dec!(20));
+        assert_eq!(
+            two_sided_score(dec!(0.95), dec!(30), dec!(0)),
+            Decimal::ZERO
+        );
     }
 
-    Ok(())
-}
+    #[test]
+    fn score_order_shares_the_pool_with_resting_liquidity() {
+        let bids = [level(dec!(0.49), dec!(90))];
+        let asks = [level(dec!(0.51), dec!(90))];
+        let mut check = reward_check(Side::Buy, dec!(0.50), dec!(30));
+        score_order(&mut check, &bids, &asks, (&[], &[]));
+
+        assert_eq!(check.blocker, None);
+        // 30 at the midpoint, one-sided: 30 / 3.
+        assert_eq!(check.score, dec!(10));
+        // Each side rests 0.01 away: (0.02 / 0.03)^2 * 90 = 40.
+        assert_eq!(check.competing.round_dp(6), dec!(40));
+        assert_eq!(check.share().round_dp(2), dec!(0.20));
+        assert_eq!(check.est_daily().round_dp(0), dec!(20));
+    }
 
-async fn execute_account(
-    command: ClobCommand,
-    output: &OutputFormat,
-    private_key: Option<&str>,
-    signature_type: Option<&str>,
-) -> Result<()> {
-    match command {
-        ClobCommand::ApiKeys => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.api_keys().await?;
-            print_api_keys(&result, output)?;
-        }
+    #[test]
+    fn score_order_reports_why_an_order_would_not_score() {
+        let bids = [level(dec!(0.49), dec!(90))];
+        let asks = [level(dec!(0.51), dec!(90))];
+        let blocker = |side, price, size| {
+            let mut check = reward_check(side, price, size);
+            score_order(&mut check, &bids, &asks, (&[], &[]));
+            assert!(check.score.is_zero());
+            check.blocker.unwrap()
+        };
+        assert!(blocker(Side::Buy, dec!(0.51), dec!(30)).contains("crosses"));
+        assert!(blocker(Side::Sell, dec!(0.52), dec!(10)).contains("minimum"));
+        assert!(blocker(Side::Buy, dec!(0.45), dec!(30)).contains("midpoint"));
+    }
 
-        ClobCommand::DeleteApiKey => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.delete_api_key().await?;
-            print_delete_api_key(&result, output)?;
-        }
+    #[test]
+    fn score_order_counts_complement_book_and_skips_small_levels() {
+        let bids = [level(dec!(0.49), dec!(90)), level(dec!(0.50), dec!(5))];
+        let asks = [level(dec!(0.51), dec!(90))];
+        // A NO ask at 0.51 is a YES bid at 0.49; a NO bid at 0.49 a YES ask at 0.51.
+        let other_bids = [level(dec!(0.49), dec!(90))];
+        let other_asks = [level(dec!(0.51), dec!(90))];
+        let mut check = reward_check(Side::Buy, dec!(0.50), dec!(30));
+        score_order(&mut check, &bids, &asks, (&other_bids, &other_asks));
+
+        // The 5-share bid is under the 20-share minimum and doesn't count;
+        // each side is twice the one-book case.
+        assert_eq!(check.competing.round_dp(6), dec!(80));
+    }
 
-        ClobCommand::CreateApiKey => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = clob::Client::default();
-            let result = client.create_or_derive_api_key(&signer, None).await?;
-            print_create_api_key(&result, output)?;
-        }
+    #[test]
+    fn usdc_daily_rate_ignores_other_assets_and_inactive_configs() {
+        let usdc = Address::repeat_byte(1);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let config = |asset, start, end, rate| {
+            MarketRewardsConfig::builder()
+                .id("1")
+                .asset_address(asset)
+                .start_date(start)
+                .end_date(end)
+                .rate_per_day(rate)
+                .total_rewards(Decimal::ZERO)
+                .total_days(Decimal::ZERO)
+                .build()
+        };
+        let configs = [
+            config(usdc, day(1), day(31), dec!(50)),
+            config(Address::repeat_byte(2), day(1), day(31), dec!(1000)),
+            config(usdc, day(20), day(31), dec!(25)),
+        ];
+        assert_eq!(usdc_daily_rate(&configs, usdc, day(10)), dec!(50));
+    }
 
-        ClobCommand::AccountStatus => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.closed_only_mode().await?;
-            print_account_status(&result, output)?;
+    fn date_range(from: &str, to: &str) -> DateRange {
+        DateRange {
+            from: Some(from.into()),
+            to: Some(to.into()),
+            export: None,
+            file: None,
         }
-
-        _ => unreachable!(),
     }
 
-    Ok(())
-}
+    #[test]
+    fn date_range_walks_every_day_inclusive() {
+        let days = date_range("2024-02-27", "2024-03-01")
+            .days()
+            .unwrap()
+            .unwrap();
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            days,
+            ["2024-02-27", "2024-02-28", "2024-02-29", "2024-03-01"]
+        );
+        assert!(date_range("2024-03-01", "2024-02-27").days().is_err());
+        assert!(date_range("2024-01-01", "2025-06-01").days().is_err());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn earnings_range_aggregates_by_day_and_market() {
+        let day = |d: &str| parse_date(d).unwrap();
+        let row = |date: &str, market: u8, earnings| EarningRow {
+            date: day(date),
+            condition_id: Some(B256::repeat_byte(market)),
+            question: format!("Market {market}"),
+            earnings,
+        };
+        let range = EarningsRange {
+            from: day("2024-06-01"),
+            to: day("2024-06-04"),
+            rows: vec![
+                row("2024-06-01", 1, dec!(2)),
+                row("2024-06-01", 2, dec!(5)),
+                row("2024-06-03", 1, dec!(3)),
+                row("2024-06-03", 1, dec!(0)),
+                row("2024-06-03", 2, dec!(0)),
+            ],
+        };
+
+        assert_eq!(range.total(), dec!(10));
+        assert_eq!(range.daily_average(), dec!(2.5));
+        let by_day: Vec<Decimal> = range.by_day().into_iter().map(|(_, t)| t).collect();
+        assert_eq!(by_day, [dec!(7), dec!(0), dec!(3), dec!(0)]);
+        let markets = range.by_market();
+        assert_eq!(markets[0].question, "Market 1");
+        assert_eq!((markets[0].total, markets[0].days), (dec!(5), 2));
+        assert_eq!((markets[1].total, markets[1].days), (dec!(5), 1));
+    }
 
     #[test]
-    fn parse_token_id_valid_numeric() {
-        let id = parse_token_id("12345").unwrap();
-        assert_eq!(id, U256::from(12345u64));
+    fn earnings_range_counts_distinct_paying_days_per_market() {
+        let day = |d: &str| parse_date(d).unwrap();
+        let row = |date: &str, earnings| EarningRow {
+            date: day(date),
+            condition_id: Some(B256::repeat_byte(1)),
+            question: "Market 1".to_string(),
+            earnings,
+        };
+        // Several rows per market and day, one per asset.
+        let range = EarningsRange {
+            from: day("2024-06-01"),
+            to: day("2024-06-02"),
+            rows: vec![
+                row("2024-06-01", dec!(1)),
+                row("2024-06-01", dec!(2)),
+                row("2024-06-02", dec!(1)),
+                row("2024-06-02", dec!(4)),
+            ],
+        };
+
+        let markets = range.by_market();
+        assert_eq!((markets[0].total, markets[0].days), (dec!(8), 2));
     }
 
     #[test]
-    fn parse_token_id_large_number() {
-        let id = parse_token_id(
-            "48331043336612883890938759509493159234755048973583954730006854632066573",
-        )
-        .unwrap();
-        assert!(id > U256::ZERO);
+    fn allowance_granted_needs_a_positive_amount() {
+        assert!(allowance_granted(
+            "115792089237316195423570985008687907853269984665640564039457"
+        ));
+        assert!(allowance_granted(" 1 "));
+        assert!(!allowance_granted("0"));
+        assert!(!allowance_granted(""));
     }
 
     #[test]
-    fn parse_token_id_zero() {
-        let id = parse_token_id("0").unwrap();
-        assert_eq!(id, U256::ZERO);
+    fn allowances_granted_needs_at_least_one() {
+        assert!(!allowances_granted(&HashMap::new()));
+        let mut allowances = HashMap::from([(Address::ZERO, "5".to_string())]);
+        assert!(allowances_granted(&allowances));
+        allowances.insert(Address::repeat_byte(1), "0".to_string());
+        assert!(!allowances_granted(&allowances));
     }
 
     #[test]
-    fn parse_token_id_invalid() {
-        assert!(parse_token_id("abc").is_err());
-        assert!(parse_token_id("12.34").is_err());
-        assert!(parse_token_id("-1").is_err());
+    fn token_balance_flags_stale_or_unapproved() {
+        let balance = |clob, on_chain, approved| TokenBalance {
+            token_id: U256::from(1),
+            market: String::new(),
+            outcome: String::new(),
+            clob,
+            on_chain,
+            approved,
+        };
+        assert!(!balance(dec!(5), dec!(5), true).needs_update());
+        assert!(balance(dec!(0), dec!(5), true).needs_update());
+        assert!(balance(dec!(5), dec!(5), false).needs_update());
     }
 
     #[test]
-    fn parse_token_ids_single() {
-        let ids = parse_token_ids("100").unwrap();
-        assert_eq!(ids, vec![U256::from(100u64)]);
+    fn parse_time_bound_accepts_dates_and_timestamps() {
+        let day = parse_time_bound("2026-09-01").unwrap();
+        assert_eq!(day.to_rfc3339(), "2026-09-01T00:00:00+00:00");
+        let time = parse_time_bound("2026-09-01T12:00:00+02:00").unwrap();
+        assert_eq!(time.to_rfc3339(), "2026-09-01T10:00:00+00:00");
+        assert!(parse_time_bound("September").is_err());
     }
 
+    const RULES: OrderRules = OrderRules {
+        tick: dec!(0.01),
+        min_size: dec!(5),
+    };
+
     #[test]
-    fn parse_token_ids_multiple() {
-        let ids = parse_token_ids("1,2,3").unwrap();
+    fn order_rules_accept_valid_order() {
         assert_eq!(
-            ids,
-            vec![U256::from(1u64), U256::from(2u64), U256::from(3u64)]
+            RULES.apply(dec!(0.45), dec!(10), None).unwrap(),
+            (dec!(0.45), dec!(10))
         );
     }
 
     #[test]
-    fn parse_token_ids_with_spaces() {
-        let ids = parse_token_ids("1, 2, 3").unwrap();
+    fn order_rules_reject_off_grid_without_round() {
+        let err = RULES.apply(dec!(0.455), dec!(10), None).unwrap_err();
+        assert!(err.to_string().contains("--round"));
+        assert!(RULES.apply(dec!(1), dec!(10), None).is_err());
+        assert!(RULES.apply(dec!(0.5), dec!(10.123), None).is_err());
+        assert!(RULES.apply(dec!(0.5), dec!(4), None).is_err());
+    }
+
+    #[test]
+    fn order_rules_round_price() {
+        let snap = |mode| RULES.apply(dec!(0.455), dec!(10), Some(mode)).unwrap().0;
+        assert_eq!(snap(RoundMode::Down), dec!(0.45));
+        assert_eq!(snap(RoundMode::Up), dec!(0.46));
+        assert_eq!(snap(RoundMode::Nearest), dec!(0.46));
+        let edge = RULES
+            .apply(dec!(0.999), dec!(10), Some(RoundMode::Up))
+            .unwrap();
+        assert_eq!(edge.0, dec!(0.99));
+    }
+
+    #[test]
+    fn order_rules_round_size() {
         assert_eq!(
-            ids,
-            vec![U256::from(1u64), U256::from(2u64), U256::from(3u64)]
+            RULES
+                .apply(dec!(0.5), dec!(10.129), Some(RoundMode::Down))
+                .unwrap()
+                .1,
+            dec!(10.12)
+        );
+        assert_eq!(
+            RULES
+                .apply(dec!(0.5), dec!(3), Some(RoundMode::Up))
+                .unwrap()
+                .1,
+            dec!(5)
+        );
+        assert!(
+            RULES
+                .apply(dec!(0.5), dec!(3), Some(RoundMode::Nearest))
+                .is_err()
         );
     }
 
     #[test]
-    fn parse_token_ids_invalid_entry() {
-        assert!(parse_token_ids("1,abc,3").is_err());
+    fn order_filter_default_matches_everything() {
+        assert!(OrderFilter::default().matches(&open_order(Side::Buy, dec!(0.5), 0)));
     }
 
     #[test]
-    fn parse_date_valid() {
-        let d = parse_date("2024-06-15").unwrap();
-        assert_eq!(d.to_string(), "2024-06-15");
+    fn order_filter_requires_every_condition() {
+        let filter = OrderFilter {
+            side: Some(Side::Buy),
+            price_below: Some(dec!(0.4)),
+            placed_before: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+            ..OrderFilter::default()
+        };
+        assert!(filter.matches(&open_order(Side::Buy, dec!(0.3), 7200)));
+        assert!(!filter.matches(&open_order(Side::Sell, dec!(0.3), 7200)));
+        assert!(!filter.matches(&open_order(Side::Buy, dec!(0.4), 7200)));
+        assert!(!filter.matches(&open_order(Side::Buy, dec!(0.3), 60)));
     }
 
     #[test]
-    fn parse_date_leap_day() {
-        let d = parse_date("2024-02-29").unwrap();
-        assert_eq!(d.to_string(), "2024-02-29");
+    fn order_filter_token_and_markets() {
+        let order = open_order(Side::Sell, dec!(0.5), 0);
+        let other_market = OrderFilter {
+            markets: Some(HashSet::from([B256::repeat_byte(1)])),
+            ..OrderFilter::default()
+        };
+        assert!(!other_market.matches(&order));
+        let same_token = OrderFilter {
+            token: Some(U256::from(7)),
+            price_above: Some(dec!(0.45)),
+            ..OrderFilter::default()
+        };
+        assert!(same_token.matches(&order));
+    }
+
+    fn ws_level(price: Decimal, size: Decimal) -> OrderBookLevel {
+        OrderBookLevel::builder().price(price).size(size).build()
+    }
+
+    fn ws_change(side: Side, price: Decimal, size: Option<Decimal>) -> PriceChangeBatchEntry {
+        PriceChangeBatchEntry::builder()
+            .asset_id(U256::from(1u64))
+            .side(side)
+            .price(price)
+            .maybe_size(size)
+            .build()
     }
 
     #[test]
-    fn parse_date_invalid_format() {
-        assert!(parse_date("06/15/2024").is_err());
-        assert!(parse_date("2024-13-01").is_err());
-        assert!(parse_date("not-a-date").is_err());
-        assert!(parse_date("").is_err());
+    fn local_book_applies_snapshot_then_changes() {
+        let mut book = LocalBook::default();
+        book.apply_snapshot(
+            &BookUpdate::builder()
+                .asset_id(U256::from(1u64))
+                .market(B256::ZERO)
+                .timestamp(1_000)
+                .bids(vec![
+                    ws_level(dec!(0.48), dec!(10)),
+                    ws_level(dec!(0.47), dec!(5)),
+                ])
+                .asks(vec![ws_level(dec!(0.52), dec!(8))])
+                .build(),
+        );
+        let row = book.row(U256::from(1u64));
+        assert_eq!(row.best_bid, Some((dec!(0.48), dec!(10))));
+        assert_eq!(row.best_ask, Some((dec!(0.52), dec!(8))));
+
+        book.apply_change(&ws_change(Side::Buy, dec!(0.49), Some(dec!(3))), 2_000);
+        book.apply_change(&ws_change(Side::Sell, dec!(0.52), Some(dec!(0))), 2_000);
+        book.apply_change(&ws_change(Side::Sell, dec!(0.51), None), 2_000);
+        let row = book.row(U256::from(1u64));
+        assert_eq!(row.best_bid, Some((dec!(0.49), dec!(3))));
+        assert_eq!(row.best_ask, None);
+        assert_eq!(row.updated, Some(2_000));
+    }
+
+    #[test]
+    fn local_book_skips_updates_older_than_its_snapshot() {
+        let snapshot = |timestamp, bid| {
+            BookUpdate::builder()
+                .asset_id(U256::from(1u64))
+                .market(B256::ZERO)
+                .timestamp(timestamp)
+                .bids(vec![ws_level(bid, dec!(10))])
+                .asks(Vec::new())
+                .build()
+        };
+        let mut books = HashMap::from([(U256::from(1u64), LocalBook::default())]);
+        let lines = MarketEvent::Book(snapshot(2_000, dec!(0.48))).apply(&mut books);
+        assert_eq!(lines.len(), 1);
+
+        // A change stamped before the snapshot arrives after it: the
+        // snapshot already includes it, so replaying it would corrupt the book.
+        let late = PriceChange::builder()
+            .market(B256::ZERO)
+            .timestamp(1_500)
+            .price_changes(vec![ws_change(Side::Buy, dec!(0.48), Some(dec!(0)))])
+            .build();
+        assert!(MarketEvent::Price(late).apply(&mut books).is_empty());
+        let book = &books[&U256::from(1u64)];
+        assert_eq!(
+            book.row(U256::from(1u64)).best_bid,
+            Some((dec!(0.48), dec!(10)))
+        );
+        assert_eq!(book.updated, Some(2_000));
+
+        // An older snapshot is skipped too; later changes still apply.
+        assert!(
+            MarketEvent::Book(snapshot(1_000, dec!(0.30)))
+                .apply(&mut books)
+                .is_empty()
+        );
+        let book = books.get_mut(&U256::from(1u64)).unwrap();
+        assert!(book.apply_change(&ws_change(Side::Buy, dec!(0.49), Some(dec!(2))), 2_000));
+        assert_eq!(
+            book.row(U256::from(1u64)).best_bid,
+            Some((dec!(0.49), dec!(2)))
+        );
+    }
+
+    #[test]
+    fn market_event_ignores_unrequested_tokens() {
+        let mut books = HashMap::from([(U256::from(1u64), LocalBook::default())]);
+        let change = PriceChange::builder()
+            .market(B256::ZERO)
+            .timestamp(5)
+            .price_changes(vec![
+                ws_change(Side::Buy, dec!(0.40), Some(dec!(2))),
+                PriceChangeBatchEntry::builder()
+                    .asset_id(U256::from(2u64))
+                    .side(Side::Buy)
+                    .price(dec!(0.60))
+                    .size(dec!(1))
+                    .build(),
+            ])
+            .build();
+        let lines = MarketEvent::Price(change).apply(&mut books);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["event"], "price_change");
+        assert_eq!(lines[0]["price"], "0.40");
+        assert_eq!(books.len(), 1);
     }
 }
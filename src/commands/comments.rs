@@ -1,6 +1,6 @@
-use super::parse_address;
+use super::{AllPages, parse_address};
 use crate::output::comments::{print_comment_detail, print_comments_table};
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use polymarket_client_sdk::gamma::{
@@ -37,6 +37,9 @@ pub enum CommentsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field
         #[arg(long)]
         order: Option<String>,
@@ -65,6 +68,9 @@ pub enum CommentsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field
         #[arg(long)]
         order: Option<String>,
@@ -103,23 +109,33 @@ pub async fn execute(
             entity_id,
             limit,
             offset,
+            pages,
             order,
             ascending,
         } => {
-            let request = CommentsRequest::builder()
-                .parent_entity_type(ParentEntityType::from(entity_type))
-                .parent_entity_id(entity_id)
-                .limit(limit)
-                .maybe_offset(offset)
-                .maybe_order(order)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .build();
-
-            let comments = client.comments(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = CommentsRequest::builder()
+                    .parent_entity_type(ParentEntityType::from(entity_type.clone()))
+                    .parent_entity_id(entity_id.clone())
+                    .limit(limit)
+                    .maybe_offset(offset)
+                    .maybe_order(order.clone())
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .build();
+                async move { anyhow::Ok(client.comments(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_comments_table(&comments),
-                OutputFormat::Json => print_json(&comments)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(comments) = all.await? {
+                    print_comments_table(&comments);
+                }
+            } else {
+                let comments = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_comments_table(&comments),
+                    OutputFormat::Json => print_json(&comments)?,
+                }
             }
         }
 
@@ -141,23 +157,33 @@ pub async fn execute(
             address,
             limit,
             offset,
+            pages,
             order,
             ascending,
         } => {
             let addr = parse_address(&address)?;
-            let request = CommentsByUserAddressRequest::builder()
-                .user_address(addr)
-                .limit(limit)
-                .maybe_offset(offset)
-                .maybe_order(order)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .build();
-
-            let comments = client.comments_by_user_address(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = CommentsByUserAddressRequest::builder()
+                    .user_address(addr)
+                    .limit(limit)
+                    .maybe_offset(offset)
+                    .maybe_order(order.clone())
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .build();
+                async move { anyhow::Ok(client.comments_by_user_address(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_comments_table(&comments),
-                OutputFormat::Json => print_json(&comments)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(comments) = all.await? {
+                    print_comments_table(&comments);
+                }
+            } else {
+                let comments = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_comments_table(&comments),
+                    OutputFormat::Json => print_json(&comments)?,
+                }
             }
         }
     }
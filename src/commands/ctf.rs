@@ -1,20 +1,45 @@
+#![allow(clippy::exhaustive_enums, reason = "Generated by sol! macro")]
+#![allow(clippy::exhaustive_structs, reason = "Generated by sol! macro")]
+
 use alloy::primitives::U256;
+use alloy::sol;
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use polymarket_client_sdk::ctf::types::{
     CollectionIdRequest, ConditionIdRequest, MergePositionsRequest, PositionIdRequest,
     RedeemNegRiskRequest, RedeemPositionsRequest, SplitPositionRequest,
 };
+use polymarket_client_sdk::gamma::{
+    self,
+    types::request::{EventByIdRequest, MarketsRequest},
+    types::response::Event,
+};
 use polymarket_client_sdk::types::{Address, B256};
-use polymarket_client_sdk::{POLYGON, ctf};
+use polymarket_client_sdk::{POLYGON, contract_config, ctf};
 use rust_decimal::Decimal;
 
 use crate::auth;
 use crate::output::OutputFormat;
-use crate::output::ctf as ctf_output;
+use crate::output::ctf::{self as ctf_output, ConvertLeg, ConvertPreview};
 
 const USDC_DECIMALS: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
 
+// The SDK's adapter binding only covers redeemPositions.
+sol! {
+    #[sol(rpc)]
+    interface INegRiskAdapterCalls {
+        function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount) external;
+        function mergePositions(bytes32 conditionId, uint256 amount) external;
+    }
+
+    #[sol(rpc)]
+    interface IConditionalTokenBalances {
+        function balanceOfBatch(address[] owners, uint256[] ids) external view returns (uint256[] balances);
+    }
+}
+
+const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
 #[derive(Args)]
 pub struct CtfArgs {
     #[command(subcommand)]
@@ -83,6 +108,24 @@ pub enum CtfCommand {
         #[arg(long)]
         amounts: String,
     },
+    /// Convert NO tokens across a neg-risk event into USDC plus YES tokens of the other outcomes
+    ConvertNegRisk {
+        /// Neg-risk market ID of the event (0x-prefixed 32-byte hex)
+        #[arg(long)]
+        market_id: String,
+        /// Outcomes whose NO tokens to convert, as comma-separated question indices (e.g. "0,2")
+        #[arg(long)]
+        index_set: String,
+        /// NO shares to convert from each selected outcome
+        #[arg(long)]
+        amount: String,
+        /// Show what would be received without sending the transaction
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
     /// Calculate a condition ID from oracle, question, and outcome count
     ConditionId {
         /// Oracle address (0x-prefixed)
@@ -175,6 +218,186 @@ fn resolve_collateral(collateral: &str) -> Result<Address> {
     super::parse_address(collateral)
 }
 
+/// Parses question indices into a sorted, de-duplicated list.
+fn parse_question_indices(s: &str) -> Result<Vec<u8>> {
+    let mut indices = s
+        .split(',')
+        .map(|part| {
+            let trimmed = part.trim();
+            trimmed
+                .parse::<u8>()
+                .context(format!("Invalid question index: {trimmed}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+fn index_set_mask(indices: &[u8]) -> U256 {
+    indices.iter().fold(U256::ZERO, |mask, &i| {
+        mask | (U256::from(1) << usize::from(i))
+    })
+}
+
+/// Question IDs of a neg-risk market share its ID, with the question index in
+/// the last byte.
+fn question_index(market_id: B256, question_id: B256) -> Option<u8> {
+    (question_id[..31] == market_id[..31]).then(|| question_id[31])
+}
+
+/// Fee, YES shares per outcome and USDC received for converting `amount` NO
+/// shares from each of `no_count` outcomes, all in base units. The adapter
+/// takes its fee from `amount` before paying out.
+fn convert_amounts(amount: u64, fee_bips: u64, no_count: u64) -> Result<(u64, u64, u64)> {
+    let too_large = || anyhow::anyhow!("Amount too large to convert");
+    let fee = u64::try_from(u128::from(amount) * u128::from(fee_bips) / 10_000)
+        .map_err(|_| too_large())?;
+    let out = amount
+        .checked_sub(fee)
+        .ok_or_else(|| anyhow::anyhow!("Fee of {fee_bips} bips exceeds the amount"))?;
+    let collateral = out
+        .checked_mul(no_count.saturating_sub(1))
+        .ok_or_else(too_large)?;
+    Ok((fee, out, collateral))
+}
+
+fn raw_to_usdc(raw: u64) -> Decimal {
+    Decimal::from(raw) / USDC_DECIMALS
+}
+
+async fn neg_risk_event(market_id: B256) -> Result<Event> {
+    let client = gamma::Client::default();
+    let request = MarketsRequest::builder()
+        .question_ids(vec![market_id])
+        .build();
+    let markets = client.markets(&request).await?;
+    let event_id = markets
+        .into_iter()
+        .find_map(|m| m.events.and_then(|e| e.into_iter().next()))
+        .map(|e| e.id)
+        .ok_or_else(|| anyhow::anyhow!("No neg-risk event found for market {market_id}"))?;
+    let request = EventByIdRequest::builder().id(event_id).build();
+    Ok(client.event_by_id(&request).await?)
+}
+
+fn convert_preview(
+    event: Event,
+    market_id: B256,
+    indices: &[u8],
+    amount: u64,
+) -> Result<ConvertPreview> {
+    let mut legs: Vec<(ConvertLeg, bool)> = event
+        .markets
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|m| {
+            let index = question_index(market_id, m.question_id?)?;
+            let outcome = m
+                .group_item_title
+                .filter(|t| !t.is_empty())
+                .or(m.question)
+                .unwrap_or_else(|| format!("Question {index}"));
+            let token_id = m.clob_token_ids.and_then(|ids| ids.first().copied());
+            Some((
+                ConvertLeg {
+                    index,
+                    outcome,
+                    token_id,
+                },
+                indices.contains(&index),
+            ))
+        })
+        .collect();
+    legs.sort_by_key(|(leg, _)| leg.index);
+
+    anyhow::ensure!(
+        legs.len() > 1,
+        "Event has no convertible neg-risk outcomes for market {market_id}"
+    );
+    if let Some(&missing) = indices
+        .iter()
+        .find(|i| !legs.iter().any(|(leg, _)| leg.index == **i))
+    {
+        anyhow::bail!(
+            "Question index {missing} is not part of this market (valid: 0-{})",
+            legs.len() - 1
+        );
+    }
+
+    let fee_bips = event
+        .neg_risk_fee_bips
+        .and_then(|b| u64::try_from(b).ok())
+        .unwrap_or(0);
+    let (fee, out, collateral) = convert_amounts(amount, fee_bips, indices.len() as u64)?;
+    let (spent, received): (Vec<_>, Vec<_>) = legs.into_iter().partition(|(_, sel)| *sel);
+
+    Ok(ConvertPreview {
+        market_id,
+        event: event.title.unwrap_or(event.id),
+        spent: spent.into_iter().map(|(leg, _)| leg).collect(),
+        received: received.into_iter().map(|(leg, _)| leg).collect(),
+        amount: raw_to_usdc(amount),
+        fee: raw_to_usdc(fee),
+        shares_out: raw_to_usdc(out),
+        collateral_out: raw_to_usdc(collateral),
+    })
+}
+
+/// Raw on-chain balances of `owner` for each of `token_ids`, in the same order.
+pub async fn token_balances(owner: Address, token_ids: &[U256]) -> Result<Vec<U256>> {
+    let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
+    let provider = auth::create_readonly_provider().await?;
+    IConditionalTokenBalances::new(config.conditional_tokens, provider)
+        .balanceOfBatch(vec![owner; token_ids.len()], token_ids.to_vec())
+        .call()
+        .await
+        .context("Failed to read conditional token balances")
+}
+
+/// Merges `amount` full YES/NO sets of a binary market back into USDC,
+/// through the neg-risk adapter when the market is neg-risk. Returns the
+/// transaction hash and block number.
+pub async fn merge_binary_sets(
+    private_key: Option<&str>,
+    condition_id: B256,
+    neg_risk: bool,
+    amount: U256,
+) -> Result<(B256, u64)> {
+    let provider = auth::create_provider(private_key).await?;
+    if neg_risk {
+        let adapter = contract_config(POLYGON, true)
+            .and_then(|c| c.neg_risk_adapter)
+            .context("No neg-risk adapter configured for Polygon")?;
+        let receipt = INegRiskAdapterCalls::new(adapter, provider)
+            .mergePositions(condition_id, amount)
+            .send()
+            .await
+            .context("Failed to send neg-risk merge")?
+            .get_receipt()
+            .await
+            .context("Merge positions failed")?;
+        return Ok((
+            receipt.transaction_hash,
+            receipt.block_number.unwrap_or_default(),
+        ));
+    }
+
+    let client = ctf::Client::new(provider, POLYGON)?;
+    let req = MergePositionsRequest::builder()
+        .collateral_token(resolve_collateral(USDC_ADDRESS)?)
+        .parent_collection_id(B256::default())
+        .condition_id(condition_id)
+        .partition(default_partition())
+        .amount(amount)
+        .build();
+    let resp = client
+        .merge_positions(&req)
+        .await
+        .context("Merge positions failed")?;
+    Ok((resp.transaction_hash, resp.block_number))
+}
+
 fn default_partition() -> Vec<U256> {
     vec![U256::from(1), U256::from(2)]
 }
@@ -308,6 +531,49 @@ pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&s
                 &output,
             )
         }
+        CtfCommand::ConvertNegRisk {
+            market_id,
+            index_set,
+            amount,
+            dry_run,
+            force,
+        } => {
+            super::ensure_can_confirm(&output, force, dry_run)?;
+            let market_id = super::parse_condition_id(&market_id)?;
+            let indices = parse_question_indices(&index_set)?;
+            let raw_amount = u64::try_from(parse_usdc_amount(&amount)?)
+                .map_err(|_| anyhow::anyhow!("Amount too large: {amount}"))?;
+
+            let event = neg_risk_event(market_id).await?;
+            let preview = convert_preview(event, market_id, &indices, raw_amount)?;
+            // JSON prints one document: the preview, or the transaction.
+            if matches!(output, OutputFormat::Table) || dry_run {
+                ctf_output::print_convert_preview(&preview, &output)?;
+            }
+            if !super::confirm("Send conversion?", &output, force, dry_run)? {
+                return Ok(());
+            }
+
+            let adapter = contract_config(POLYGON, true)
+                .and_then(|c| c.neg_risk_adapter)
+                .context("No neg-risk adapter configured for Polygon")?;
+            let provider = auth::create_provider(private_key).await?;
+            let receipt = INegRiskAdapterCalls::new(adapter, provider)
+                .convertPositions(market_id, index_set_mask(&indices), U256::from(raw_amount))
+                .send()
+                .await
+                .context("Failed to send neg-risk conversion")?
+                .get_receipt()
+                .await
+                .context("Convert neg-risk positions failed")?;
+
+            ctf_output::print_tx_result(
+                "convert-neg-risk",
+                receipt.transaction_hash,
+                receipt.block_number.unwrap_or_default(),
+                &output,
+            )
+        }
         CtfCommand::ConditionId {
             oracle,
             question,
@@ -531,4 +797,96 @@ mod tests {
         let s = default_index_sets();
         assert_eq!(s, vec![U256::from(1u64), U256::from(2u64)]);
     }
+
+    #[test]
+    fn parse_question_indices_sorts_and_dedups() {
+        assert_eq!(parse_question_indices("2, 0,2").unwrap(), vec![0, 2]);
+        assert!(parse_question_indices("0,x").is_err());
+        assert!(parse_question_indices("300").is_err());
+    }
+
+    #[test]
+    fn index_set_mask_sets_one_bit_per_question() {
+        assert_eq!(index_set_mask(&[0, 2]), U256::from(0b101u64));
+    }
+
+    #[test]
+    fn question_index_reads_last_byte() {
+        let market: B256 = "0x2c53b7e4d0bf4c3d1de1a8c1c6f0c4c1a54b1e0f1d2c3b4a5968778695a4b300"
+            .parse()
+            .unwrap();
+        let mut question = market;
+        question[31] = 3;
+        assert_eq!(question_index(market, question), Some(3));
+        question[0] ^= 1;
+        assert_eq!(question_index(market, question), None);
+    }
+
+    #[test]
+    fn convert_amounts_pays_collateral_for_extra_no_sets() {
+        assert_eq!(
+            convert_amounts(10_000_000, 0, 3).unwrap(),
+            (0, 10_000_000, 20_000_000)
+        );
+        assert_eq!(
+            convert_amounts(10_000_000, 100, 1).unwrap(),
+            (100_000, 9_900_000, 0)
+        );
+    }
+
+    #[test]
+    fn convert_amounts_rejects_overflow() {
+        // $2B in base units times a 1% fee overflows a plain u64 product.
+        let amount = 2_000_000_000_000_000;
+        assert_eq!(
+            convert_amounts(amount, 100, 2).unwrap(),
+            (
+                20_000_000_000_000,
+                1_980_000_000_000_000,
+                1_980_000_000_000_000
+            )
+        );
+        assert!(convert_amounts(u64::MAX, 0, 3).is_err());
+        assert!(convert_amounts(100, 20_000, 2).is_err());
+    }
+
+    #[test]
+    fn convert_preview_splits_outcomes() {
+        let market = "0x2c53b7e4d0bf4c3d1de1a8c1c6f0c4c1a54b1e0f1d2c3b4a5968778695a4b300";
+        let question = |i: u8| format!("{}{i:02x}", &market[..64]);
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "title": "Who wins?",
+            "markets": [
+                {"id": "a", "questionID": question(0), "groupItemTitle": "Alice"},
+                {"id": "b", "questionID": question(1), "groupItemTitle": "Bob"},
+                {"id": "c", "questionID": question(2), "groupItemTitle": "Carol"},
+            ],
+        }))
+        .unwrap();
+
+        let preview = convert_preview(event, market.parse().unwrap(), &[0, 2], 5_000_000).unwrap();
+        assert_eq!(preview.event, "Who wins?");
+        assert_eq!(preview.spent.len(), 2);
+        assert_eq!(preview.received[0].outcome, "Bob");
+        assert_eq!(preview.shares_out, Decimal::from(5));
+        assert_eq!(preview.collateral_out, Decimal::from(5));
+    }
+
+    #[test]
+    fn convert_preview_rejects_unknown_index() {
+        let market = "0x2c53b7e4d0bf4c3d1de1a8c1c6f0c4c1a54b1e0f1d2c3b4a5968778695a4b300";
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "markets": [
+                {"id": "a", "questionID": format!("{}00", &market[..64])},
+                {"id": "b", "questionID": format!("{}01", &market[..64])},
+            ],
+        }))
+        .unwrap();
+        let err = convert_preview(event, market.parse().unwrap(), &[4], 1)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("index 4"), "got: {err}");
+    }
 }
@@ -1,9 +1,11 @@
-use super::{parse_address, parse_condition_id};
+use super::{AllPages, parse_address, parse_condition_id};
 use crate::output::OutputFormat;
 use crate::output::data::{
-    print_activity, print_builder_leaderboard, print_builder_volume, print_closed_positions,
-    print_holders, print_leaderboard, print_live_volume, print_open_interest, print_positions,
-    print_traded, print_trades, print_value,
+    activity_to_json, builder_leaderboard_entry_to_json, closed_position_to_json,
+    leaderboard_entry_to_json, position_to_json, print_activity, print_builder_leaderboard,
+    print_builder_volume, print_closed_positions, print_holders, print_leaderboard,
+    print_live_volume, print_open_interest, print_positions, print_traded, print_trades,
+    print_value, trade_to_json,
 };
 use anyhow::Result;
 use clap::{Args, Subcommand};
@@ -16,6 +18,11 @@ use polymarket_client_sdk::data::{
     },
 };
 
+/// Largest `offset` the Data API accepts for positions, trades and activity.
+const MAX_OFFSET: i32 = 10_000;
+const CLOSED_POSITIONS_MAX_OFFSET: i32 = 100_000;
+const LEADERBOARD_MAX_OFFSET: i32 = 1000;
+
 #[derive(Args)]
 pub struct DataArgs {
     #[command(subcommand)]
@@ -36,6 +43,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get closed positions for a wallet address
@@ -50,6 +60,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get total position value for a wallet address
@@ -76,6 +89,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get on-chain activity for a wallet address
@@ -90,6 +106,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Get top token holders for a market
@@ -131,6 +150,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Builder leaderboard
@@ -146,6 +168,9 @@ pub enum DataCommand {
         /// Pagination offset
         #[arg(long)]
         offset: Option<i32>,
+
+        #[command(flatten)]
+        pages: AllPages,
     },
 
     /// Builder volume time-series
@@ -224,30 +249,64 @@ async fn execute_user(
             address,
             limit,
             offset,
+            pages,
         } => {
-            let request = PositionsRequest::builder()
-                .user(parse_address(&address)?)
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let positions = client.positions(&request).await?;
-            print_positions(&positions, output)?;
+            let user = parse_address(&address)?;
+            let fetch = |offset: Option<i32>| async move {
+                let request = PositionsRequest::builder()
+                    .user(user)
+                    .limit(limit)?
+                    .maybe_offset(offset)?
+                    .build();
+                anyhow::Ok(client.positions(&request).await?)
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    position_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(positions) = all.await? {
+                    print_positions(&positions, output)?;
+                }
+            } else {
+                print_positions(&fetch(offset).await?, output)?;
+            }
         }
 
         DataCommand::ClosedPositions {
             address,
             limit,
             offset,
+            pages,
         } => {
-            let request = ClosedPositionsRequest::builder()
-                .user(parse_address(&address)?)
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let positions = client.closed_positions(&request).await?;
-            print_closed_positions(&positions, output)?;
+            let user = parse_address(&address)?;
+            let fetch = |offset: Option<i32>| async move {
+                let request = ClosedPositionsRequest::builder()
+                    .user(user)
+                    .limit(limit)?
+                    .maybe_offset(offset)?
+                    .build();
+                anyhow::Ok(client.closed_positions(&request).await?)
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(CLOSED_POSITIONS_MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    closed_position_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(positions) = all.await? {
+                    print_closed_positions(&positions, output)?;
+                }
+            } else {
+                print_closed_positions(&fetch(offset).await?, output)?;
+            }
         }
 
         DataCommand::Value { address } => {
@@ -272,30 +331,64 @@ async fn execute_user(
             address,
             limit,
             offset,
+            pages,
         } => {
-            let request = TradesRequest::builder()
-                .user(parse_address(&address)?)
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let trades = client.trades(&request).await?;
-            print_trades(&trades, output)?;
+            let user = parse_address(&address)?;
+            let fetch = |offset: Option<i32>| async move {
+                let request = TradesRequest::builder()
+                    .user(user)
+                    .limit(limit)?
+                    .maybe_offset(offset)?
+                    .build();
+                anyhow::Ok(client.trades(&request).await?)
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    trade_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(trades) = all.await? {
+                    print_trades(&trades, output)?;
+                }
+            } else {
+                print_trades(&fetch(offset).await?, output)?;
+            }
         }
 
         DataCommand::Activity {
             address,
             limit,
             offset,
+            pages,
         } => {
-            let request = ActivityRequest::builder()
-                .user(parse_address(&address)?)
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let activity = client.activity(&request).await?;
-            print_activity(&activity, output)?;
+            let user = parse_address(&address)?;
+            let fetch = |offset: Option<i32>| async move {
+                let request = ActivityRequest::builder()
+                    .user(user)
+                    .limit(limit)?
+                    .maybe_offset(offset)?
+                    .build();
+                anyhow::Ok(client.activity(&request).await?)
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    activity_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(activity) = all.await? {
+                    print_activity(&activity, output)?;
+                }
+            } else {
+                print_activity(&fetch(offset).await?, output)?;
+            }
         }
 
         _ => unreachable!(),
@@ -352,31 +445,70 @@ async fn execute_leaderboard(
             order_by,
             limit,
             offset,
+            pages,
         } => {
-            let request = TraderLeaderboardRequest::builder()
-                .maybe_time_period(period.map(Into::into))
-                .maybe_order_by(order_by.map(Into::into))
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let entries = client.leaderboard(&request).await?;
-            print_leaderboard(&entries, output)?;
+            let fetch = |offset: Option<i32>| {
+                let period = period.clone();
+                let order_by = order_by.clone();
+                async move {
+                    let request = TraderLeaderboardRequest::builder()
+                        .maybe_time_period(period.map(Into::into))
+                        .maybe_order_by(order_by.map(Into::into))
+                        .limit(limit)?
+                        .maybe_offset(offset)?
+                        .build();
+                    anyhow::Ok(client.leaderboard(&request).await?)
+                }
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(LEADERBOARD_MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    leaderboard_entry_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(entries) = all.await? {
+                    print_leaderboard(&entries, output)?;
+                }
+            } else {
+                print_leaderboard(&fetch(offset).await?, output)?;
+            }
         }
 
         DataCommand::BuilderLeaderboard {
             period,
             limit,
             offset,
+            pages,
         } => {
-            let request = BuilderLeaderboardRequest::builder()
-                .maybe_time_period(period.map(Into::into))
-                .limit(limit)?
-                .maybe_offset(offset)?
-                .build();
-
-            let entries = client.builder_leaderboard(&request).await?;
-            print_builder_leaderboard(&entries, output)?;
+            let fetch = |offset: Option<i32>| {
+                let period = period.clone();
+                async move {
+                    let request = BuilderLeaderboardRequest::builder()
+                        .maybe_time_period(period.map(Into::into))
+                        .limit(limit)?
+                        .maybe_offset(offset)?
+                        .build();
+                    anyhow::Ok(client.builder_leaderboard(&request).await?)
+                }
+            };
+
+            if pages.enabled() {
+                let all = pages.offset_cap(LEADERBOARD_MAX_OFFSET).by_offset(
+                    *output,
+                    offset,
+                    limit,
+                    builder_leaderboard_entry_to_json,
+                    |o| fetch(Some(o)),
+                );
+                if let Some(entries) = all.await? {
+                    print_builder_leaderboard(&entries, output)?;
+                }
+            } else {
+                print_builder_leaderboard(&fetch(offset).await?, output)?;
+            }
         }
 
         DataCommand::BuilderVolume { period } => {
@@ -5,10 +5,10 @@ use polymarket_client_sdk::gamma::{
     types::request::{EventByIdRequest, EventBySlugRequest, EventTagsRequest, EventsRequest},
 };
 
-use super::is_numeric_id;
+use super::{AllPages, is_numeric_id};
 use crate::output::events::{print_event_detail, print_events_table};
 use crate::output::tags::print_tags_table;
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 
 #[derive(Args)]
 pub struct EventsArgs {
@@ -36,6 +36,9 @@ pub enum EventsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field (e.g. volume, liquidity, `created_at`)
         #[arg(long)]
         order: Option<String>,
@@ -69,26 +72,36 @@ pub async fn execute(client: &gamma::Client, args: EventsArgs, output: OutputFor
             closed,
             limit,
             offset,
+            pages,
             order,
             ascending,
             tag,
         } => {
             let resolved_closed = closed.or_else(|| active.map(|a| !a));
 
-            let request = EventsRequest::builder()
-                .limit(limit)
-                .maybe_closed(resolved_closed)
-                .maybe_offset(offset)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .maybe_tag_slug(tag)
-                .order(order.into_iter().collect::<Vec<_>>())
-                .build();
-
-            let events = client.events(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = EventsRequest::builder()
+                    .limit(limit)
+                    .maybe_closed(resolved_closed)
+                    .maybe_offset(offset)
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .maybe_tag_slug(tag.clone())
+                    .order(order.clone().into_iter().collect::<Vec<_>>())
+                    .build();
+                async move { anyhow::Ok(client.events(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_events_table(&events),
-                OutputFormat::Json => print_json(&events)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(events) = all.await? {
+                    print_events_table(&events);
+                }
+            } else {
+                let events = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_events_table(&events),
+                    OutputFormat::Json => print_json(&events)?,
+                }
             }
         }
 
@@ -11,10 +11,10 @@ use polymarket_client_sdk::gamma::{
     },
 };
 
-use super::is_numeric_id;
+use super::{AllPages, is_numeric_id};
 use crate::output::markets::{print_market_detail, print_markets_table};
 use crate::output::tags::print_tags_table;
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 
 #[derive(Args)]
 pub struct MarketsArgs {
@@ -42,6 +42,9 @@ pub enum MarketsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field (e.g. `volume_num`, `liquidity_num`)
         #[arg(long)]
         order: Option<String>,
@@ -85,24 +88,34 @@ pub async fn execute(
             closed,
             limit,
             offset,
+            pages,
             order,
             ascending,
         } => {
             let resolved_closed = closed.or_else(|| active.map(|a| !a));
 
-            let request = MarketsRequest::builder()
-                .limit(limit)
-                .maybe_closed(resolved_closed)
-                .maybe_offset(offset)
-                .maybe_order(order)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .build();
-
-            let markets = client.markets(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = MarketsRequest::builder()
+                    .limit(limit)
+                    .maybe_closed(resolved_closed)
+                    .maybe_offset(offset)
+                    .maybe_order(order.clone())
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .build();
+                async move { anyhow::Ok(client.markets(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_markets_table(&markets),
-                OutputFormat::Json => print_json(&markets)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(markets) = all.await? {
+                    print_markets_table(&markets);
+                }
+            } else {
+                let markets = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_markets_table(&markets),
+                    OutputFormat::Json => print_json(&markets)?,
+                }
             }
         }
 
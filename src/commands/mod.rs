@@ -1,4 +1,11 @@
+use std::str::FromStr;
+
+use polymarket_client_sdk::clob::types::response::Page;
 use polymarket_client_sdk::types::{Address, B256};
+use rust_decimal::Decimal;
+
+use crate::output::clob::END_CURSOR;
+use crate::output::{ListSink, OutputFormat};
 
 pub mod approve;
 pub mod bridge;
@@ -9,6 +16,7 @@ pub mod data;
 pub mod events;
 pub mod markets;
 pub mod profiles;
+pub mod scan;
 pub mod series;
 pub mod setup;
 pub mod sports;
@@ -30,9 +38,345 @@ pub fn parse_condition_id(s: &str) -> anyhow::Result<B256> {
         .map_err(|_| anyhow::anyhow!("Invalid condition ID: must be a 0x-prefixed 32-byte hex"))
 }
 
+/// `--all` / `--max-items` for list commands, which otherwise return one page.
+#[derive(clap::Args, Clone, Copy, Debug, Default)]
+pub struct AllPages {
+    /// Keep fetching pages until the listing runs out
+    #[arg(long)]
+    pub all: bool,
+    /// Stop after this many items (implies --all)
+    #[arg(long)]
+    pub max_items: Option<usize>,
+    /// Largest offset the API accepts, set with [`AllPages::offset_cap`].
+    #[arg(skip)]
+    pub max_offset: Option<i32>,
+}
+
+impl AllPages {
+    pub fn enabled(self) -> bool {
+        self.all || self.max_items.is_some()
+    }
+
+    /// Stops [`AllPages::by_offset`] with a warning instead of requesting a
+    /// page past `max_offset`, which the API rejects.
+    pub fn offset_cap(mut self, max_offset: i32) -> Self {
+        self.max_offset = Some(max_offset);
+        self
+    }
+
+    /// Walks an offset-paged listing `limit` items at a time from `offset`.
+    /// Returns the items for table output; JSON is streamed as it arrives.
+    pub async fn by_offset<T, Fut>(
+        self,
+        output: OutputFormat,
+        offset: Option<i32>,
+        limit: i32,
+        to_json: fn(&T) -> serde_json::Value,
+        mut fetch: impl FnMut(i32) -> Fut,
+    ) -> anyhow::Result<Option<Vec<T>>>
+    where
+        Fut: Future<Output = anyhow::Result<Vec<T>>>,
+    {
+        anyhow::ensure!(limit > 0, "--limit must be positive with --all");
+        let max_offset = self.max_offset.unwrap_or(i32::MAX);
+        self.drain(output, offset.unwrap_or(0), to_json, |offset| {
+            let page = fetch(offset);
+            async move {
+                let items = page.await?;
+                let full = items.len() >= usize::try_from(limit).unwrap_or(usize::MAX);
+                let next = offset.saturating_add(limit);
+                if full && next > max_offset {
+                    eprintln!("Stopped at offset {max_offset}: the API does not page further");
+                    return Ok((items, None));
+                }
+                Ok((items, full.then_some(next)))
+            }
+        })
+        .await
+    }
+
+    /// Follows `next_cursor` from `cursor` until the API's end marker.
+    /// Returns the items for table output; JSON is streamed as it arrives.
+    pub async fn by_cursor<T, Fut>(
+        self,
+        output: OutputFormat,
+        cursor: Option<String>,
+        to_json: fn(&T) -> serde_json::Value,
+        mut fetch: impl FnMut(Option<String>) -> Fut,
+    ) -> anyhow::Result<Option<Vec<T>>>
+    where
+        Fut: Future<Output = anyhow::Result<Page<T>>>,
+    {
+        self.drain(output, cursor, to_json, |cursor| {
+            let page = fetch(cursor);
+            async move {
+                let page = page.await?;
+                let done = page.next_cursor.is_empty() || page.next_cursor == END_CURSOR;
+                Ok((page.data, (!done).then_some(Some(page.next_cursor))))
+            }
+        })
+        .await
+    }
+
+    async fn drain<T, S, Fut>(
+        self,
+        output: OutputFormat,
+        start: S,
+        to_json: fn(&T) -> serde_json::Value,
+        mut fetch: impl FnMut(S) -> Fut,
+    ) -> anyhow::Result<Option<Vec<T>>>
+    where
+        Fut: Future<Output = anyhow::Result<(Vec<T>, Option<S>)>>,
+    {
+        let mut sink = ListSink::new(output, to_json);
+        let mut remaining = self.max_items.unwrap_or(usize::MAX);
+        let mut next = Some(start);
+        while let Some(state) = next.take()
+            && remaining > 0
+        {
+            let (items, following) = match fetch(state).await {
+                Ok(page) => page,
+                Err(e) if sink.started() => {
+                    // Close the array so stdout stays one valid JSON document;
+                    // the error goes to stderr instead.
+                    sink.finish()?;
+                    eprintln!("Error: {e}");
+                    return Err(ExitStatus(1).into());
+                }
+                Err(e) => return Err(e),
+            };
+            for item in items.into_iter().take(remaining) {
+                sink.push(item)?;
+                remaining -= 1;
+            }
+            next = following;
+        }
+        sink.finish()
+    }
+}
+
+/// Ends the process with a specific exit status once output has been printed.
+///
+/// Returned as an error so it unwinds through `run`; `main` exits with the code
+/// instead of printing an error message.
+#[derive(Debug)]
+pub struct ExitStatus(pub u8);
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "exit status {}", self.0)
+    }
+}
+
+impl std::error::Error for ExitStatus {}
+
+/// Parse a duration like `90`, `45s`, `30m`, `2h` or `1d` (bare numbers are seconds).
+pub fn parse_duration(s: &str) -> anyhow::Result<std::time::Duration> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, unit) = s.split_at(split);
+    let n: u64 = digits
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid duration: {s} (expected e.g. 30s, 5m, 2h)"))?;
+    let scale: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        _ => anyhow::bail!("Invalid duration: {s} (expected e.g. 30s, 5m, 2h)"),
+    };
+    let secs = n
+        .checked_mul(scale)
+        .ok_or_else(|| anyhow::anyhow!("Duration too large: {s}"))?;
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Parses `0.5%` or `0.005` into a fraction.
+pub fn parse_edge(s: &str) -> anyhow::Result<Decimal> {
+    let trimmed = s.trim();
+    let value = match trimmed.strip_suffix('%') {
+        Some(pct) => Decimal::from_str(pct.trim()).map(|p| p / Decimal::ONE_HUNDRED),
+        None => Decimal::from_str(trimmed),
+    }
+    .map_err(|_| anyhow::anyhow!("Invalid edge: {s} (expected e.g. 0.5% or 0.005)"))?;
+    anyhow::ensure!(value >= Decimal::ZERO, "Edge must not be negative: {s}");
+    Ok(value)
+}
+
+/// Fails unless a confirmation could be answered: JSON output can't prompt,
+/// so it needs `--force` or `--dry-run`. Call before doing any work.
+pub fn ensure_can_confirm(output: &OutputFormat, force: bool, dry_run: bool) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        matches!(output, OutputFormat::Table) || force || dry_run,
+        "--force or --dry-run is required with -o json"
+    );
+    Ok(())
+}
+
+/// Asks `prompt` on stderr before a command acts; `--force` skips the
+/// question. Returns `false` for `--dry-run` or a declined prompt.
+pub fn confirm(
+    prompt: &str,
+    output: &OutputFormat,
+    force: bool,
+    dry_run: bool,
+) -> anyhow::Result<bool> {
+    use std::io::{self, BufRead, Write};
+
+    ensure_can_confirm(output, force, dry_run)?;
+    if dry_run || force {
+        return Ok(!dry_run);
+    }
+    eprint!("{prompt} [y/N] ");
+    io::stderr().flush()?;
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input)?;
+    let yes = matches!(input.trim().to_lowercase().as_str(), "y" | "yes");
+    if !yes {
+        eprintln!("Aborted.");
+    }
+    Ok(yes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn numbers(offset: i32, limit: i32, total: i32) -> anyhow::Result<Vec<i32>> {
+        Ok((offset..(offset + limit).min(total)).collect())
+    }
+
+    #[tokio::test]
+    async fn all_pages_by_offset_stops_on_short_page() {
+        let pages = AllPages {
+            all: true,
+            ..AllPages::default()
+        };
+        let mut calls = Vec::new();
+        let items = pages
+            .by_offset(
+                OutputFormat::Table,
+                Some(2),
+                3,
+                |n| json!(n),
+                |offset| {
+                    calls.push(offset);
+                    std::future::ready(numbers(offset, 3, 10))
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(items, Some((2..10).collect()));
+        assert_eq!(calls, [2, 5, 8]);
+    }
+
+    #[tokio::test]
+    async fn all_pages_by_offset_stops_at_offset_cap() {
+        let pages = AllPages {
+            all: true,
+            ..AllPages::default()
+        }
+        .offset_cap(10);
+        let mut calls = Vec::new();
+        let items = pages
+            .by_offset(
+                OutputFormat::Table,
+                None,
+                3,
+                |n| json!(n),
+                |offset| {
+                    calls.push(offset);
+                    std::future::ready(numbers(offset, 3, 100))
+                },
+            )
+            .await
+            .unwrap();
+        // Offset 9 is the last one allowed; 12 would be rejected.
+        assert_eq!(calls, [0, 3, 6, 9]);
+        assert_eq!(items, Some((0..12).collect()));
+    }
+
+    #[tokio::test]
+    async fn all_pages_json_error_after_streaming_exits_without_error_document() {
+        let pages = AllPages {
+            all: true,
+            ..AllPages::default()
+        };
+        let err = pages
+            .by_offset(
+                OutputFormat::Json,
+                None,
+                2,
+                |n| json!(n),
+                |offset| {
+                    std::future::ready(if offset == 0 {
+                        numbers(0, 2, 10)
+                    } else {
+                        Err(anyhow::anyhow!("boom"))
+                    })
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast::<ExitStatus>().unwrap().0, 1);
+    }
+
+    #[tokio::test]
+    async fn all_pages_max_items_stops_fetching() {
+        let pages = AllPages {
+            max_items: Some(4),
+            ..AllPages::default()
+        };
+        let mut calls = 0;
+        let items = pages
+            .by_offset(
+                OutputFormat::Table,
+                None,
+                3,
+                |n| json!(n),
+                |offset| {
+                    calls += 1;
+                    std::future::ready(numbers(offset, 3, 100))
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(items, Some(vec![0, 1, 2, 3]));
+        assert_eq!(calls, 2);
+    }
+
+    #[tokio::test]
+    async fn all_pages_by_cursor_follows_until_end_marker() {
+        let pages = AllPages {
+            all: true,
+            ..AllPages::default()
+        };
+        let items = pages
+            .by_cursor(
+                OutputFormat::Table,
+                None,
+                |n| json!(n),
+                |cursor| {
+                    let (data, next) = match cursor.as_deref() {
+                        None => (vec![1, 2], "MQ=="),
+                        Some("MQ==") => (vec![3], END_CURSOR),
+                        Some(other) => panic!("unexpected cursor {other}"),
+                    };
+                    let page = Page::builder()
+                        .data(data)
+                        .next_cursor(next.to_string())
+                        .limit(2)
+                        .count(2)
+                        .build();
+                    std::future::ready(anyhow::Ok(page))
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(items, Some(vec![1, 2, 3]));
+    }
 
     #[test]
     fn is_numeric_id_pure_digits() {
@@ -87,4 +431,43 @@ mod tests {
         let err = parse_condition_id("garbage").unwrap_err().to_string();
         assert!(err.contains("32-byte"), "got: {err}");
     }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90").unwrap().as_secs(), 90);
+        assert_eq!(parse_duration("45s").unwrap().as_secs(), 45);
+        assert_eq!(parse_duration("30m").unwrap().as_secs(), 1800);
+        assert_eq!(parse_duration("2h").unwrap().as_secs(), 7200);
+        assert_eq!(parse_duration("1d").unwrap().as_secs(), 86_400);
+        let err = parse_duration("999999999999999999d")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Duration too large"), "{err}");
+        assert!(parse_duration(&format!("{}s", u64::MAX)).is_ok());
+    }
+
+    #[test]
+    fn parse_duration_rejects_garbage() {
+        for bad in ["", "m", "10x", "1.5h", "-5m"] {
+            let err = parse_duration(bad).unwrap_err().to_string();
+            assert!(err.contains("Invalid duration"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn parse_edge_accepts_percent_and_fraction() {
+        assert_eq!(parse_edge("0.5%").unwrap(), dec!(0.005));
+        assert_eq!(parse_edge("0.005").unwrap(), dec!(0.005));
+        assert!(parse_edge("-1%").is_err());
+        assert!(parse_edge("abc").is_err());
+    }
+
+    #[test]
+    fn confirm_needs_force_or_dry_run_in_json() {
+        let json = OutputFormat::Json;
+        assert!(confirm("Go?", &json, false, false).is_err());
+        assert!(confirm("Go?", &json, true, false).unwrap());
+        assert!(!confirm("Go?", &json, false, true).unwrap());
+        assert!(!confirm("Go?", &OutputFormat::Table, true, true).unwrap());
+    }
 }
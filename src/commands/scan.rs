@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::clob::{
+    self,
+    types::{request::OrderBookSummaryRequest, response::OrderSummary},
+};
+use polymarket_client_sdk::gamma::{
+    self,
+    types::{request::EventsRequest, response::Event},
+};
+use polymarket_client_sdk::types::U256;
+use rust_decimal::Decimal;
+
+use super::parse_edge;
+use crate::output::OutputFormat;
+use crate::output::scan::{NegRiskHit, SetFill, SetSide, print_neg_risk_hits};
+
+#[derive(Args)]
+pub struct ScanArgs {
+    #[command(subcommand)]
+    pub command: ScanCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ScanCommand {
+    /// Find neg-risk events whose YES books sum away from 1
+    NegRisk {
+        /// Only scan events with this tag slug
+        #[arg(long)]
+        tag: Option<String>,
+        /// Minimum edge per $1 set after fees, as a percentage (0.5%) or fraction (0.005)
+        #[arg(long, default_value = "0")]
+        min_edge: String,
+        /// Max neg-risk events to scan, busiest first
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+}
+
+pub async fn execute(args: ScanArgs, output: OutputFormat) -> Result<()> {
+    match args.command {
+        ScanCommand::NegRisk {
+            tag,
+            min_edge,
+            limit,
+        } => {
+            let min_edge = parse_edge(&min_edge)?;
+            let events = neg_risk_events(tag.as_deref(), limit).await?;
+            let client = clob::Client::default();
+
+            let mut hits = Vec::new();
+            for event in events {
+                let tokens = yes_tokens(&event);
+                if tokens.len() < 2 {
+                    continue;
+                }
+                let requests: Vec<_> = tokens
+                    .iter()
+                    .map(|id| OrderBookSummaryRequest::builder().token_id(*id).build())
+                    .collect();
+                let books: HashMap<U256, _> = client
+                    .order_books(&requests)
+                    .await?
+                    .into_iter()
+                    .map(|b| (b.asset_id, b))
+                    .collect();
+                let Some(books) = tokens
+                    .iter()
+                    .map(|id| books.get(id))
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+
+                // Buying every listed outcome only pays out if one of them
+                // wins, which an augmented event does not guarantee.
+                let augmented = event.neg_risk_augmented == Some(true);
+                let mut sides = vec![SetSide::SellAll];
+                if !augmented {
+                    sides.insert(0, SetSide::BuyAll);
+                }
+                let mut fee_rates: Option<Vec<u32>> = None;
+                for side in sides {
+                    let levels: Vec<&[OrderSummary]> = books
+                        .iter()
+                        .map(|b| match side {
+                            SetSide::BuyAll => b.asks.as_slice(),
+                            SetSide::SellAll => b.bids.as_slice(),
+                        })
+                        .collect();
+                    // Fees only lower the edge, so skip fetching them for
+                    // events that do not clear the bar without.
+                    let zero = vec![0; levels.len()];
+                    if walk_sets(&levels, &zero, side, min_edge).is_none() {
+                        continue;
+                    }
+                    if fee_rates.is_none() {
+                        let mut rates = Vec::with_capacity(tokens.len());
+                        for id in &tokens {
+                            rates.push(client.fee_rate_bps(*id).await?.base_fee);
+                        }
+                        fee_rates = Some(rates);
+                    }
+                    let rates = fee_rates.as_deref().unwrap_or(&zero);
+                    if let Some(fill) = walk_sets(&levels, rates, side, min_edge) {
+                        hits.push(NegRiskHit {
+                            event: event.title.clone().unwrap_or_else(|| event.id.clone()),
+                            slug: event.slug.clone(),
+                            side,
+                            outcomes: tokens.len(),
+                            fill,
+                        });
+                    }
+                }
+            }
+
+            hits.sort_by_key(|h| std::cmp::Reverse(h.fill.profit));
+            print_neg_risk_hits(&hits, &output)
+        }
+    }
+}
+
+/// Active neg-risk events, busiest first, up to `limit`.
+async fn neg_risk_events(tag: Option<&str>, limit: usize) -> Result<Vec<Event>> {
+    const PAGE: i32 = 100;
+    let client = gamma::Client::default();
+    let mut events = Vec::new();
+    for page in 0.. {
+        let request = EventsRequest::builder()
+            .maybe_tag_slug(tag.map(str::to_string))
+            .active(true)
+            .closed(false)
+            .order(vec!["volume24hr".into()])
+            .ascending(false)
+            .limit(PAGE)
+            .offset(page * PAGE)
+            .build();
+        let batch = client.events(&request).await?;
+        let done = batch.len() < PAGE as usize;
+        events.extend(batch.into_iter().filter(|e| e.neg_risk == Some(true)));
+        if done || events.len() >= limit {
+            break;
+        }
+    }
+    events.truncate(limit);
+    Ok(events)
+}
+
+/// YES token of every outcome still trading in `event`.
+fn yes_tokens(event: &Event) -> Vec<U256> {
+    event
+        .markets
+        .iter()
+        .flatten()
+        .filter(|m| {
+            m.closed != Some(true) && m.active != Some(false) && m.accepting_orders != Some(false)
+        })
+        .filter_map(|m| m.clob_token_ids.as_ref()?.first().copied())
+        .collect()
+}
+
+/// Walks one book per outcome (best price last) a full set at a time while
+/// the per-set edge after taker fees stays positive and at least `min_edge`.
+pub fn walk_sets(
+    books: &[&[OrderSummary]],
+    fee_rates: &[u32],
+    side: SetSide,
+    min_edge: Decimal,
+) -> Option<SetFill> {
+    let mut depth = vec![0; books.len()];
+    let mut taken = vec![Decimal::ZERO; books.len()];
+    let mut fill: Option<SetFill> = None;
+    loop {
+        let levels: Option<Vec<&OrderSummary>> = books
+            .iter()
+            .zip(&depth)
+            .map(|(book, d)| book.len().checked_sub(d + 1).map(|i| &book[i]))
+            .collect();
+        let Some(levels) = levels else { break };
+
+        let price_sum: Decimal = levels.iter().map(|l| l.price).sum();
+        let fee: Decimal = levels
+            .iter()
+            .zip(fee_rates)
+            .map(|(l, rate)| {
+                Decimal::from(*rate) / Decimal::from(10_000) * l.price.min(Decimal::ONE - l.price)
+            })
+            .sum();
+        let edge = match side {
+            SetSide::BuyAll => Decimal::ONE - price_sum - fee,
+            SetSide::SellAll => price_sum - Decimal::ONE - fee,
+        };
+        if edge <= Decimal::ZERO || edge < min_edge {
+            break;
+        }
+
+        let sets = levels
+            .iter()
+            .zip(&taken)
+            .map(|(l, t)| l.size - t)
+            .min()
+            .unwrap_or_default();
+        let f = fill.get_or_insert(SetFill {
+            top_price_sum: price_sum,
+            top_edge: edge,
+            ..SetFill::default()
+        });
+        f.sets += sets;
+        f.notional += price_sum * sets;
+        f.fees += fee * sets;
+        f.profit += edge * sets;
+
+        for ((level, t), d) in levels.iter().zip(&mut taken).zip(&mut depth) {
+            *t += sets;
+            if *t >= level.size {
+                *t = Decimal::ZERO;
+                *d += 1;
+            }
+        }
+    }
+    fill
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, size: Decimal) -> OrderSummary {
+        OrderSummary::builder().price(price).size(size).build()
+    }
+
+    #[test]
+    fn walk_sets_buys_until_asks_sum_to_one() {
+        // Best level last, as the book endpoint returns them.
+        let a = [level(dec!(0.50), dec!(100)), level(dec!(0.40), dec!(30))];
+        let b = [level(dec!(0.55), dec!(100)), level(dec!(0.50), dec!(50))];
+        let fill = walk_sets(&[&a, &b], &[0, 0], SetSide::BuyAll, Decimal::ZERO).unwrap();
+
+        assert_eq!(fill.top_price_sum, dec!(0.90));
+        assert_eq!(fill.top_edge, dec!(0.10));
+        // 30 sets at 0.90, then 20 at 0.50 + 0.50 = 1.00 has no edge.
+        assert_eq!(fill.sets, dec!(30));
+        assert_eq!(fill.profit, dec!(3.0));
+    }
+
+    #[test]
+    fn walk_sets_sells_while_bids_exceed_one() {
+        let a = [level(dec!(0.55), dec!(10))];
+        let b = [level(dec!(0.50), dec!(40)), level(dec!(0.52), dec!(25))];
+        let fill = walk_sets(&[&a, &b], &[0, 0], SetSide::SellAll, Decimal::ZERO).unwrap();
+
+        assert_eq!(fill.sets, dec!(10));
+        assert_eq!(fill.profit, dec!(0.70));
+    }
+
+    #[test]
+    fn walk_sets_applies_fees_and_min_edge() {
+        let a = [level(dec!(0.48), dec!(10))];
+        let b = [level(dec!(0.49), dec!(10))];
+        let books: [&[OrderSummary]; 2] = [&a, &b];
+
+        assert!(walk_sets(&books, &[0, 0], SetSide::BuyAll, dec!(0.02)).is_some());
+        assert!(walk_sets(&books, &[0, 0], SetSide::BuyAll, dec!(0.05)).is_none());
+        // 1000 bps on min(p, 1 - p) costs ~0.097 per set, more than the 0.03 edge.
+        assert!(walk_sets(&books, &[1000, 1000], SetSide::BuyAll, Decimal::ZERO).is_none());
+    }
+
+    #[test]
+    fn walk_sets_needs_every_book() {
+        let a = [level(dec!(0.40), dec!(10))];
+        assert!(walk_sets(&[&a, &[]], &[0, 0], SetSide::BuyAll, Decimal::ZERO).is_none());
+    }
+}
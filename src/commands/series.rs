@@ -5,8 +5,9 @@ use polymarket_client_sdk::gamma::{
     types::request::{SeriesByIdRequest, SeriesListRequest},
 };
 
+use super::AllPages;
 use crate::output::series::{print_series_detail, print_series_table};
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 
 #[derive(Args)]
 pub struct SeriesArgs {
@@ -26,6 +27,9 @@ pub enum SeriesCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field (e.g. volume, liquidity)
         #[arg(long)]
         order: Option<String>,
@@ -51,23 +55,33 @@ pub async fn execute(client: &gamma::Client, args: SeriesArgs, output: OutputFor
         SeriesCommand::List {
             limit,
             offset,
+            pages,
             order,
             ascending,
             closed,
         } => {
-            let request = SeriesListRequest::builder()
-                .limit(limit)
-                .maybe_offset(offset)
-                .maybe_order(order)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .maybe_closed(closed)
-                .build();
-
-            let series = client.series(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = SeriesListRequest::builder()
+                    .limit(limit)
+                    .maybe_offset(offset)
+                    .maybe_order(order.clone())
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .maybe_closed(closed)
+                    .build();
+                async move { anyhow::Ok(client.series(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_series_table(&series),
-                OutputFormat::Json => print_json(&series)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(series) = all.await? {
+                    print_series_table(&series);
+                }
+            } else {
+                let series = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_series_table(&series),
+                    OutputFormat::Json => print_json(&series)?,
+                }
             }
         }
 
@@ -2,8 +2,9 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use polymarket_client_sdk::gamma::{self, types::request::TeamsRequest};
 
+use super::AllPages;
 use crate::output::sports::{print_sport_types, print_sports_table, print_teams_table};
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 
 #[derive(Args)]
 pub struct SportsArgs {
@@ -29,6 +30,9 @@ pub enum SportsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort field
         #[arg(long)]
         order: Option<String>,
@@ -66,23 +70,33 @@ pub async fn execute(client: &gamma::Client, args: SportsArgs, output: OutputFor
         SportsCommand::Teams {
             limit,
             offset,
+            pages,
             order,
             ascending,
             league,
         } => {
-            let request = TeamsRequest::builder()
-                .limit(limit)
-                .maybe_offset(offset)
-                .maybe_order(order)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .league(league.into_iter().collect::<Vec<_>>())
-                .build();
-
-            let teams = client.teams(&request).await?;
-
-            match output {
-                OutputFormat::Table => print_teams_table(&teams),
-                OutputFormat::Json => print_json(&teams)?,
+            let fetch = |offset: Option<i32>| {
+                let request = TeamsRequest::builder()
+                    .limit(limit)
+                    .maybe_offset(offset)
+                    .maybe_order(order.clone())
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .league(league.clone().into_iter().collect::<Vec<_>>())
+                    .build();
+                async move { anyhow::Ok(client.teams(&request).await?) }
+            };
+
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(teams) = all.await? {
+                    print_teams_table(&teams);
+                }
+            } else {
+                let teams = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_teams_table(&teams),
+                    OutputFormat::Json => print_json(&teams)?,
+                }
             }
         }
     }
@@ -8,9 +8,9 @@ use polymarket_client_sdk::gamma::{
     },
 };
 
-use super::is_numeric_id;
+use super::{AllPages, is_numeric_id};
 use crate::output::tags::{print_related_tags_table, print_tag_detail, print_tags_table};
-use crate::output::{OutputFormat, print_json};
+use crate::output::{OutputFormat, json_value, print_json};
 
 #[derive(Args)]
 pub struct TagsArgs {
@@ -30,6 +30,9 @@ pub enum TagsCommand {
         #[arg(long)]
         offset: Option<i32>,
 
+        #[command(flatten)]
+        pages: AllPages,
+
         /// Sort ascending instead of descending
         #[arg(long)]
         ascending: bool,
@@ -67,19 +70,29 @@ pub async fn execute(client: &gamma::Client, args: TagsArgs, output: OutputForma
         TagsCommand::List {
             limit,
             offset,
+            pages,
             ascending,
         } => {
-            let request = TagsRequest::builder()
-                .limit(limit)
-                .maybe_offset(offset)
-                .maybe_ascending(if ascending { Some(true) } else { None })
-                .build();
-
-            let tags = client.tags(&request).await?;
+            let fetch = |offset: Option<i32>| {
+                let request = TagsRequest::builder()
+                    .limit(limit)
+                    .maybe_offset(offset)
+                    .maybe_ascending(if ascending { Some(true) } else { None })
+                    .build();
+                async move { anyhow::Ok(client.tags(&request).await?) }
+            };
 
-            match output {
-                OutputFormat::Table => print_tags_table(&tags),
-                OutputFormat::Json => print_json(&tags)?,
+            if pages.enabled() {
+                let all = pages.by_offset(output, offset, limit, json_value, |o| fetch(Some(o)));
+                if let Some(tags) = all.await? {
+                    print_tags_table(&tags);
+                }
+            } else {
+                let tags = fetch(offset).await?;
+                match output {
+                    OutputFormat::Table => print_tags_table(&tags),
+                    OutputFormat::Json => print_json(&tags)?,
+                }
             }
         }
 
@@ -50,6 +50,22 @@ pub fn config_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("config.json"))
 }
 
+pub fn triggers_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("triggers.json"))
+}
+
+pub fn paper_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("paper.json"))
+}
+
+pub fn journal_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("journal.jsonl"))
+}
+
+pub fn heartbeat_log_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("heartbeat.log"))
+}
+
 pub fn config_exists() -> bool {
     config_path().is_ok_and(|p| p.exists())
 }
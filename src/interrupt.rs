@@ -0,0 +1,83 @@
+//! Ctrl-C handling for long-running commands.
+//!
+//! Foreground loops (TWAP, iceberg, ...) call [`install`] once and then check
+//! [`requested`] between steps so they can clean up resting orders before
+//! exiting. A second Ctrl-C, or any Ctrl-C while no guard is held, exits the
+//! process immediately.
+
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static ARMED: AtomicBool = AtomicBool::new(false);
+static LISTENER: Once = Once::new();
+
+/// Exit status of a process killed by SIGINT, as shells report it.
+const INTERRUPTED_EXIT: i32 = 130;
+
+/// How often sleeps check for Ctrl-C.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Routes Ctrl-C back to immediate exit when dropped.
+pub struct Guard(());
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        ARMED.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Route Ctrl-C to the interrupt flag instead of terminating the process,
+/// until the returned guard is dropped. Must be called inside the runtime.
+pub fn install() -> Guard {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+    ARMED.store(true, Ordering::SeqCst);
+    // Once tokio owns the signal it stays registered for the life of the
+    // process, so a single listener decides what each Ctrl-C means.
+    LISTENER.call_once(|| {
+        tokio::spawn(async {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if !ARMED.load(Ordering::SeqCst) || INTERRUPTED.swap(true, Ordering::SeqCst) {
+                    std::process::exit(INTERRUPTED_EXIT);
+                }
+            }
+        });
+    });
+    Guard(())
+}
+
+/// Whether Ctrl-C has been pressed since [`install`].
+pub fn requested() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Sleep for `duration`, waking early if Ctrl-C is pressed.
+///
+/// Returns `false` if the sleep was interrupted.
+pub async fn sleep(duration: Duration) -> bool {
+    match tokio::time::Instant::now().checked_add(duration) {
+        Some(deadline) => sleep_until(deadline).await,
+        // Too far out for the clock to represent: only Ctrl-C ends it.
+        None => {
+            while !requested() {
+                tokio::time::sleep(TICK).await;
+            }
+            false
+        }
+    }
+}
+
+/// Sleep until `deadline`, waking early if Ctrl-C is pressed.
+///
+/// Returns `false` if the sleep was interrupted.
+pub async fn sleep_until(deadline: tokio::time::Instant) -> bool {
+    while tokio::time::Instant::now() < deadline {
+        if requested() {
+            return false;
+        }
+        let left = deadline - tokio::time::Instant::now();
+        tokio::time::sleep(left.min(TICK)).await;
+    }
+    !requested()
+}
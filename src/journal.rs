@@ -0,0 +1,148 @@
+//! Append-only local journal of orders posted and canceled through the CLI.
+//!
+//! Each entry is one JSON line in `~/.config/polymarket/journal.jsonl`. Lines
+//! are never rewritten: fills found later by `clob journal sync` are appended as
+//! entries of their own.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Post,
+    Cancel,
+    Fill,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Post => "post",
+            Self::Cancel => "cancel",
+            Self::Fill => "fill",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: String,
+    pub action: Action,
+    /// CLI command that produced the entry, e.g. `create-order` or `twap`.
+    pub command: String,
+    pub order_ids: Vec<String>,
+    /// Signed order(s) or cancel parameters exactly as sent.
+    pub request: Value,
+    /// Raw API response, or `{"error": ...}` if the call failed.
+    pub response: Value,
+}
+
+impl Entry {
+    pub fn new(
+        action: Action,
+        command: &str,
+        order_ids: Vec<String>,
+        request: Value,
+        response: Value,
+    ) -> Self {
+        Self {
+            at: chrono::Utc::now().to_rfc3339(),
+            action,
+            command: command.to_string(),
+            order_ids,
+            request,
+            response,
+        }
+    }
+}
+
+/// Append `entry`, warning on stderr instead of failing: by the time an entry
+/// is written the order has already been sent.
+pub fn record(entry: &Entry) {
+    let result = config::journal_path().and_then(|path| append_to(&path, entry));
+    if let Err(e) = result {
+        eprintln!("Warning: failed to write order journal: {e:#}");
+    }
+}
+
+pub fn load() -> Result<Vec<Entry>> {
+    load_from(&config::journal_path()?)
+}
+
+fn append_to(path: &Path, entry: &Entry) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context("Failed to open journal")?;
+    writeln!(file, "{}", serde_json::to_string(entry)?).context("Failed to write journal")
+}
+
+fn load_from(path: &Path) -> Result<Vec<Entry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(path).context("Failed to read journal")?;
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Failed to parse journal line {}", i + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn append_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "polymarket-journal-test-{}.jsonl",
+            std::process::id()
+        ));
+        let post = Entry::new(
+            Action::Post,
+            "create-order",
+            vec!["0x1".into()],
+            json!({"order": {"side": "BUY"}}),
+            json!({"success": true}),
+        );
+        let cancel = Entry::new(
+            Action::Cancel,
+            "cancel",
+            vec!["0x1".into()],
+            json!({"order_ids": ["0x1"]}),
+            json!({"error": "not found"}),
+        );
+        append_to(&path, &post).unwrap();
+        append_to(&path, &cancel).unwrap();
+        let loaded = load_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].action, Action::Post);
+        assert_eq!(loaded[1].command, "cancel");
+        assert_eq!(loaded[1].response["error"], "not found");
+    }
+
+    #[test]
+    fn load_missing_file_is_empty() {
+        let path = std::env::temp_dir().join("polymarket-journal-does-not-exist.jsonl");
+        assert!(load_from(&path).unwrap().is_empty());
+    }
+}
@@ -1,8 +1,12 @@
 mod auth;
 mod commands;
 mod config;
+mod interrupt;
+mod journal;
 mod output;
+mod paper;
 mod shell;
+mod triggers;
 
 use std::process::ExitCode;
 
@@ -26,6 +30,10 @@ pub(crate) struct Cli {
     /// Signature type: eoa, proxy, or gnosis-safe
     #[arg(long, global = true)]
     signature_type: Option<String>,
+
+    /// Simulate CLOB trading against a local paper account instead of the exchange
+    #[arg(long, global = true)]
+    paper: bool,
 }
 
 #[derive(Subcommand)]
@@ -54,6 +62,8 @@ enum Commands {
     Clob(commands::clob::ClobArgs),
     /// CTF operations: split, merge, redeem positions
     Ctf(commands::ctf::CtfArgs),
+    /// Scan markets for pricing edges
+    Scan(commands::scan::ScanArgs),
     /// Query on-chain data (positions, trades, leaderboards)
     Data(commands::data::DataArgs),
     /// Bridge assets from other chains to Polymarket
@@ -72,6 +82,9 @@ async fn main() -> ExitCode {
     let output = cli.output;
 
     if let Err(e) = run(cli).await {
+        if let Some(commands::ExitStatus(code)) = e.downcast_ref() {
+            return ExitCode::from(*code);
+        }
         match output {
             OutputFormat::Json => {
                 println!("{}", serde_json::json!({"error": e.to_string()}));
@@ -88,6 +101,12 @@ async fn main() -> ExitCode {
 
 #[allow(clippy::too_many_lines)]
 pub(crate) async fn run(cli: Cli) -> anyhow::Result<()> {
+    // The flag is global so it can go anywhere on the command line, but only
+    // CLOB trading has a paper account; anything else would run for real.
+    anyhow::ensure!(
+        !cli.paper || matches!(cli.command, Commands::Clob(_)),
+        "--paper only applies to clob commands"
+    );
     match cli.command {
         Commands::Setup => commands::setup::execute(),
         Commands::Shell => {
@@ -159,12 +178,14 @@ pub(crate) async fn run(cli: Cli) -> anyhow::Result<()> {
                 cli.output,
                 cli.private_key.as_deref(),
                 cli.signature_type.as_deref(),
+                cli.paper,
             )
             .await
         }
         Commands::Ctf(args) => {
             commands::ctf::execute(args, cli.output, cli.private_key.as_deref()).await
         }
+        Commands::Scan(args) => commands::scan::execute(args, cli.output).await,
         Commands::Data(args) => {
             commands::data::execute(
                 &polymarket_client_sdk::data::Client::default(),
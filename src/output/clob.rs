@@ -1,6 +1,10 @@
 #![allow(clippy::items_after_statements)]
 
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
 use polymarket_client_sdk::auth::Credentials;
+use polymarket_client_sdk::clob::types::Side;
 use polymarket_client_sdk::clob::types::response::{
     ApiKeysResponse, BalanceAllowanceResponse, BanStatusResponse, CancelOrdersResponse,
     CurrentRewardResponse, FeeRateResponse, GeoblockResponse, LastTradePriceResponse,
@@ -11,15 +15,17 @@ use polymarket_client_sdk::clob::types::response::{
     SimplifiedMarketResponse, SpreadResponse, SpreadsResponse, TickSizeResponse,
     TotalUserEarningResponse, TradeResponse, UserEarningResponse, UserRewardsEarningResponse,
 };
-use polymarket_client_sdk::types::Decimal;
+use polymarket_client_sdk::types::{B256, Decimal, U256};
 use serde_json::json;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
 use super::{OutputFormat, format_decimal, truncate};
+use crate::journal::{Action, Entry};
+use crate::triggers::{Direction, PriceSource, Trigger};
 
 /// Base64-encoded empty cursor returned by the CLOB API when there are no more pages.
-const END_CURSOR: &str = "LTE=";
+pub const END_CURSOR: &str = "LTE=";
 
 pub fn print_ok(result: &str, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
@@ -305,6 +311,67 @@ pub fn print_order_books(
     Ok(())
 }
 
+/// Top of one locally maintained book in the `clob stream` view.
+pub struct StreamRow {
+    pub token_id: U256,
+    /// Best bid and ask as `(price, size)`.
+    pub best_bid: Option<(Decimal, Decimal)>,
+    pub best_ask: Option<(Decimal, Decimal)>,
+    pub last_trade: Option<(Decimal, Option<Side>)>,
+    /// Exchange timestamp of the latest event, in milliseconds.
+    pub updated: Option<i64>,
+}
+
+/// Redraws the `clob stream` table in place.
+pub fn print_stream_view(rows: &[StreamRow]) {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Token ID")]
+        token_id: String,
+        #[tabled(rename = "Bid")]
+        bid: String,
+        #[tabled(rename = "Ask")]
+        ask: String,
+        #[tabled(rename = "Spread")]
+        spread: String,
+        #[tabled(rename = "Last Trade")]
+        last: String,
+        #[tabled(rename = "Updated")]
+        updated: String,
+    }
+    let dash = || "—".to_string();
+    let level = |l: Option<(Decimal, Decimal)>| {
+        l.map_or_else(dash, |(price, size)| {
+            format!("{price} × {}", size.normalize())
+        })
+    };
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .map(|r| Row {
+            token_id: truncate(&r.token_id.to_string(), 20),
+            bid: level(r.best_bid),
+            ask: level(r.best_ask),
+            spread: match (r.best_bid, r.best_ask) {
+                (Some((bid, _)), Some((ask, _))) => (ask - bid).to_string(),
+                _ => dash(),
+            },
+            last: r.last_trade.map_or_else(dash, |(price, side)| match side {
+                Some(side) => format!("{price} ({side})"),
+                None => price.to_string(),
+            }),
+            updated: r
+                .updated
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map_or_else(dash, |t| t.format("%H:%M:%S%.3f").to_string()),
+        })
+        .collect();
+    let table = Table::new(table_rows).with(Style::rounded()).to_string();
+    // Clear the screen and home the cursor so the table redraws in place.
+    print!("\x1b[2J\x1b[H");
+    println!("{table}");
+    println!("Streaming {} token(s). Press Ctrl-C to stop.", rows.len());
+}
+
 pub fn print_last_trade(
     result: &LastTradePriceResponse,
     output: &OutputFormat,
@@ -629,6 +696,23 @@ pub fn print_geoblock(result: &GeoblockResponse, output: &OutputFormat) -> anyho
     Ok(())
 }
 
+pub fn open_order_to_json(o: &OpenOrderResponse) -> serde_json::Value {
+    json!({
+        "id": o.id,
+        "status": o.status.to_string(),
+        "market": o.market.to_string(),
+        "asset_id": o.asset_id.to_string(),
+        "side": o.side.to_string(),
+        "price": o.price.to_string(),
+        "original_size": o.original_size.to_string(),
+        "size_matched": o.size_matched.to_string(),
+        "outcome": o.outcome,
+        "order_type": o.order_type.to_string(),
+        "created_at": o.created_at.to_rfc3339(),
+        "expiration": o.expiration.to_rfc3339(),
+    })
+}
+
 pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
@@ -673,26 +757,7 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
-                .iter()
-                .map(|o| {
-                    json!({
-                        "id": o.id,
-                        "status": o.status.to_string(),
-                        "market": o.market.to_string(),
-                        "asset_id": o.asset_id.to_string(),
-                        "side": o.side.to_string(),
-                        "price": o.price.to_string(),
-                        "original_size": o.original_size.to_string(),
-                        "size_matched": o.size_matched.to_string(),
-                        "outcome": o.outcome,
-                        "order_type": o.order_type.to_string(),
-                        "created_at": o.created_at.to_rfc3339(),
-                        "expiration": o.expiration.to_rfc3339(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = result.data.iter().map(open_order_to_json).collect();
             let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
             super::print_json(&wrapper)?;
         }
@@ -700,6 +765,84 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
     Ok(())
 }
 
+/// How `clob wait` (or `--wait`) finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitOutcome {
+    Filled,
+    PartiallyFilled,
+    Canceled,
+    TimedOut,
+}
+
+impl WaitOutcome {
+    /// Process exit status reported when the wait ends here without reaching
+    /// the state it was waiting for.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::PartiallyFilled => 3,
+            Self::Canceled => 4,
+            Self::TimedOut => 5,
+            Self::Filled => 6,
+        }
+    }
+}
+
+impl std::fmt::Display for WaitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Filled => "filled",
+            Self::PartiallyFilled => "partially filled",
+            Self::Canceled => "canceled",
+            Self::TimedOut => "timed out",
+        })
+    }
+}
+
+pub fn print_wait_result(
+    order: &OpenOrderResponse,
+    outcome: WaitOutcome,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            println!(
+                "Order {} {outcome}: {} of {} matched",
+                order.id, order.size_matched, order.original_size
+            );
+        }
+        OutputFormat::Json => {
+            super::print_json(&wait_result_to_json(order, outcome))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn wait_result_to_json(order: &OpenOrderResponse, outcome: WaitOutcome) -> serde_json::Value {
+    let mut data = order_to_json(order);
+    data["outcome"] = json!(outcome.to_string());
+    data
+}
+
+fn order_to_json(result: &OpenOrderResponse) -> serde_json::Value {
+    json!({
+        "id": result.id,
+        "status": result.status.to_string(),
+        "owner": result.owner.to_string(),
+        "maker_address": result.maker_address.to_string(),
+        "market": result.market.to_string(),
+        "asset_id": result.asset_id.to_string(),
+        "side": result.side.to_string(),
+        "price": result.price.to_string(),
+        "original_size": result.original_size.to_string(),
+        "size_matched": result.size_matched.to_string(),
+        "outcome": result.outcome,
+        "order_type": result.order_type.to_string(),
+        "created_at": result.created_at.to_rfc3339(),
+        "expiration": result.expiration.to_rfc3339(),
+        "associate_trades": result.associate_trades,
+    })
+}
+
 pub fn print_order_detail(result: &OpenOrderResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
@@ -721,30 +864,13 @@ pub fn print_order_detail(result: &OpenOrderResponse, output: &OutputFormat) ->
             super::print_detail_table(rows);
         }
         OutputFormat::Json => {
-            let data = json!({
-                "id": result.id,
-                "status": result.status.to_string(),
-                "owner": result.owner.to_string(),
-                "maker_address": result.maker_address.to_string(),
-                "market": result.market.to_string(),
-                "asset_id": result.asset_id.to_string(),
-                "side": result.side.to_string(),
-                "price": result.price.to_string(),
-                "original_size": result.original_size.to_string(),
-                "size_matched": result.size_matched.to_string(),
-                "outcome": result.outcome,
-                "order_type": result.order_type.to_string(),
-                "created_at": result.created_at.to_rfc3339(),
-                "expiration": result.expiration.to_rfc3339(),
-                "associate_trades": result.associate_trades,
-            });
-            super::print_json(&data)?;
+            super::print_json(&order_to_json(result))?;
         }
     }
     Ok(())
 }
 
-fn post_order_to_json(r: &PostOrderResponse) -> serde_json::Value {
+pub fn post_order_to_json(r: &PostOrderResponse) -> serde_json::Value {
     let tx_hashes: Vec<_> = r
         .transaction_hashes
         .iter()
@@ -786,6 +912,231 @@ pub fn print_post_order_result(
     Ok(())
 }
 
+/// Expected result of sweeping the book with a market order.
+pub struct FillEstimate {
+    pub side: Side,
+    pub shares: Decimal,
+    pub notional: Decimal,
+    /// Price of the first level touched.
+    pub best_price: Decimal,
+    /// Price of the deepest level touched.
+    pub worst_price: Decimal,
+    /// Whether the visible book was deep enough for the whole amount.
+    pub complete: bool,
+    /// `(price, shares)` taken at each level, best first.
+    pub fills: Vec<(Decimal, Decimal)>,
+}
+
+impl FillEstimate {
+    pub fn average_price(&self) -> Decimal {
+        if self.shares.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.notional / self.shares
+    }
+
+    /// Distance of the average fill from the best price, in basis points.
+    pub fn slippage_bps(&self) -> Decimal {
+        if self.best_price.is_zero() {
+            return Decimal::ZERO;
+        }
+        let diff = match self.side {
+            Side::Sell => self.best_price - self.average_price(),
+            _ => self.average_price() - self.best_price,
+        };
+        (diff / self.best_price * Decimal::from(10_000)).round_dp(1)
+    }
+
+    /// Taker fee in USDC at `rate_bps`, charged on `min(price, 1 - price)` per share.
+    pub fn fee(&self, rate_bps: u32) -> Decimal {
+        let rate = Decimal::from(rate_bps) / Decimal::from(10_000);
+        self.fills
+            .iter()
+            .map(|(price, shares)| rate * (*price).min(Decimal::ONE - price) * shares)
+            .sum()
+    }
+}
+
+/// A simulated market order on one token.
+pub struct Quote {
+    pub token_id: U256,
+    pub estimate: FillEstimate,
+    pub midpoint: Option<Decimal>,
+    pub fee_rate_bps: u32,
+}
+
+impl Quote {
+    /// Distance of the average fill from the midpoint, in basis points.
+    pub fn impact_bps(&self) -> Option<Decimal> {
+        let mid = self.midpoint.filter(|m| !m.is_zero())?;
+        let avg = self.estimate.average_price();
+        let diff = match self.estimate.side {
+            Side::Sell => mid - avg,
+            _ => avg - mid,
+        };
+        Some((diff / mid * Decimal::from(10_000)).round_dp(1))
+    }
+}
+
+fn quote_to_json(q: &Quote) -> serde_json::Value {
+    let mut data = fill_estimate_to_json(&q.estimate);
+    data["token_id"] = json!(q.token_id.to_string());
+    data["levels"] = json!(q.estimate.fills.len());
+    data["midpoint"] = json!(q.midpoint.map(|m| m.to_string()));
+    data["impact_bps"] = json!(q.impact_bps().map(|i| i.to_string()));
+    data["fee_rate_bps"] = json!(q.fee_rate_bps);
+    data["fee"] = json!(q.estimate.fee(q.fee_rate_bps).round_dp(4).to_string());
+    data
+}
+
+pub fn print_quote(q: &Quote, output: &OutputFormat) -> anyhow::Result<()> {
+    let dash = || "—".to_string();
+    match output {
+        OutputFormat::Table => {
+            let e = &q.estimate;
+            let rows = vec![
+                ["Token ID".into(), q.token_id.to_string()],
+                ["Side".into(), e.side.to_string()],
+                ["Shares".into(), e.shares.round_dp(2).to_string()],
+                ["Cost".into(), format_decimal(e.notional)],
+                [
+                    "Avg Price".into(),
+                    e.average_price().round_dp(4).to_string(),
+                ],
+                ["Best Price".into(), e.best_price.to_string()],
+                ["Worst Price".into(), e.worst_price.to_string()],
+                ["Levels".into(), e.fills.len().to_string()],
+                [
+                    "Midpoint".into(),
+                    q.midpoint.map_or_else(dash, |m| m.to_string()),
+                ],
+                [
+                    "Impact".into(),
+                    q.impact_bps().map_or_else(dash, |i| format!("{i} bps")),
+                ],
+                [
+                    "Fee".into(),
+                    format!(
+                        "{} ({} bps)",
+                        e.fee(q.fee_rate_bps).round_dp(4),
+                        q.fee_rate_bps
+                    ),
+                ],
+            ];
+            super::print_detail_table(rows);
+            if !e.complete {
+                println!("Warning: the visible book is too thin to fill the full amount.");
+            }
+        }
+        OutputFormat::Json => {
+            super::print_json(&quote_to_json(q))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_quotes(quotes: &[Quote], output: &OutputFormat) -> anyhow::Result<()> {
+    let dash = || "—".to_string();
+    match output {
+        OutputFormat::Table => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Token ID")]
+                token_id: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Cost")]
+                cost: String,
+                #[tabled(rename = "Avg")]
+                avg: String,
+                #[tabled(rename = "Worst")]
+                worst: String,
+                #[tabled(rename = "Levels")]
+                levels: usize,
+                #[tabled(rename = "Impact (bps)")]
+                impact: String,
+                #[tabled(rename = "Fee")]
+                fee: String,
+                #[tabled(rename = "Full")]
+                complete: String,
+            }
+            let rows: Vec<Row> = quotes
+                .iter()
+                .map(|q| Row {
+                    token_id: truncate(&q.token_id.to_string(), 20),
+                    shares: q.estimate.shares.round_dp(2).to_string(),
+                    cost: format_decimal(q.estimate.notional),
+                    avg: q.estimate.average_price().round_dp(4).to_string(),
+                    worst: q.estimate.worst_price.to_string(),
+                    levels: q.estimate.fills.len(),
+                    impact: q.impact_bps().map_or_else(dash, |i| i.to_string()),
+                    fee: q.estimate.fee(q.fee_rate_bps).round_dp(4).to_string(),
+                    complete: if q.estimate.complete { "yes" } else { "no" }.into(),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = quotes.iter().map(quote_to_json).collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+fn fill_estimate_to_json(e: &FillEstimate) -> serde_json::Value {
+    json!({
+        "shares": e.shares.to_string(),
+        "notional": e.notional.to_string(),
+        "average_price": e.average_price().round_dp(4).to_string(),
+        "best_price": e.best_price.to_string(),
+        "worst_price": e.worst_price.to_string(),
+        "slippage_bps": e.slippage_bps().to_string(),
+        "complete": e.complete,
+    })
+}
+
+fn print_fill_estimate_lines(e: &FillEstimate) {
+    println!(
+        "Estimated: {} shares for {} (avg {}, worst {}, {} bps from best)",
+        e.shares.round_dp(2),
+        format_decimal(e.notional),
+        e.average_price().round_dp(4),
+        e.worst_price,
+        e.slippage_bps()
+    );
+    if !e.complete {
+        println!("Warning: the visible book is too thin to fill the full amount.");
+    }
+}
+
+pub fn print_market_order_result(
+    estimate: &FillEstimate,
+    result: &PostOrderResponse,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            print_fill_estimate_lines(estimate);
+            print_post_order_result(result, output)?;
+        }
+        OutputFormat::Json => {
+            super::print_json(&market_order_result_to_json(estimate, result))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn market_order_result_to_json(
+    estimate: &FillEstimate,
+    result: &PostOrderResponse,
+) -> serde_json::Value {
+    let mut data = post_order_to_json(result);
+    data["estimate"] = fill_estimate_to_json(estimate);
+    data
+}
+
 pub fn print_post_orders_result(
     results: &[PostOrderResponse],
     output: &OutputFormat,
@@ -807,153 +1158,909 @@ pub fn print_post_orders_result(
     Ok(())
 }
 
-pub fn print_cancel_result(
-    result: &CancelOrdersResponse,
+pub fn print_replace_result(
+    canceled_order_id: &str,
+    result: &PostOrderResponse,
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
-            if !result.canceled.is_empty() {
-                println!("Canceled: {}", result.canceled.join(", "));
-            }
-            if !result.not_canceled.is_empty() {
-                println!("Not canceled:");
-                for (id, reason) in &result.not_canceled {
-                    println!("  {id}: {reason}");
-                }
-            }
-            if result.canceled.is_empty() && result.not_canceled.is_empty() {
-                println!("No orders to cancel.");
+            println!("Canceled: {canceled_order_id}");
+            print_post_order_result(result, output)?;
+            if !result.success {
+                println!(
+                    "Warning: the original order is canceled and the replacement was rejected."
+                );
             }
         }
         OutputFormat::Json => {
-            let data = json!({
-                "canceled": result.canceled,
-                "not_canceled": result.not_canceled,
-            });
-            super::print_json(&data)?;
+            super::print_json(&json!({
+                "canceled_order_id": canceled_order_id,
+                "new_order": post_order_to_json(result),
+            }))?;
         }
     }
     Ok(())
 }
 
-pub fn print_trades(result: &Page<TradeResponse>, output: &OutputFormat) -> anyhow::Result<()> {
+pub fn print_ladder(levels: &[(Decimal, Decimal)], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
-            if result.data.is_empty() {
-                println!("No trades found.");
-                return Ok(());
-            }
             #[derive(Tabled)]
             struct Row {
-                #[tabled(rename = "ID")]
-                id: String,
-                #[tabled(rename = "Side")]
-                side: String,
+                #[tabled(rename = "#")]
+                level: usize,
                 #[tabled(rename = "Price")]
                 price: String,
                 #[tabled(rename = "Size")]
                 size: String,
-                #[tabled(rename = "Status")]
-                status: String,
-                #[tabled(rename = "Time")]
-                match_time: String,
+                #[tabled(rename = "Notional")]
+                notional: String,
             }
-            let rows: Vec<Row> = result
-                .data
+            let rows: Vec<Row> = levels
                 .iter()
-                .map(|t| Row {
-                    id: truncate(&t.id, 12),
-                    side: t.side.to_string(),
-                    price: t.price.to_string(),
-                    size: t.size.to_string(),
-                    status: t.status.to_string(),
-                    match_time: t.match_time.format("%Y-%m-%d %H:%M").to_string(),
+                .enumerate()
+                .map(|(i, (price, size))| Row {
+                    level: i + 1,
+                    price: price.to_string(),
+                    size: size.to_string(),
+                    notional: format_decimal(price * size),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
             println!("{table}");
-            if result.next_cursor != END_CURSOR {
-                println!("Next cursor: {}", result.next_cursor);
-            }
+            let total_size: Decimal = levels.iter().map(|(_, s)| s).sum();
+            let notional: Decimal = levels.iter().map(|(p, s)| p * s).sum();
+            println!("Total: {total_size} shares, {}", format_decimal(notional));
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
+            let data: Vec<_> = levels
                 .iter()
-                .map(|t| {
-                    json!({
-                        "id": t.id,
-                        "taker_order_id": t.taker_order_id,
-                        "market": t.market.to_string(),
-                        "asset_id": t.asset_id.to_string(),
-                        "side": t.side.to_string(),
-                        "size": t.size.to_string(),
-                        "price": t.price.to_string(),
-                        "fee_rate_bps": t.fee_rate_bps.to_string(),
-                        "status": t.status.to_string(),
-                        "match_time": t.match_time.to_rfc3339(),
-                        "outcome": t.outcome,
-                        "trader_side": format!("{:?}", t.trader_side),
-                        "transaction_hash": t.transaction_hash.to_string(),
-                    })
-                })
+                .map(|(price, size)| json!({"price": price.to_string(), "size": size.to_string()}))
                 .collect();
-            let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
-            super::print_json(&wrapper)?;
+            super::print_json(&data)?;
         }
     }
     Ok(())
 }
 
-/// USDC uses 6 decimal places on-chain.
-const USDC_DECIMALS: u32 = 6;
+/// Outcome of a TWAP or iceberg run.
+pub struct ExecutionReport {
+    pub algo: &'static str,
+    pub side: Side,
+    pub target: Decimal,
+    pub filled: Decimal,
+    pub notional: Decimal,
+    pub arrival_mid: Decimal,
+    pub orders: u32,
+    pub interrupted: bool,
+}
 
-pub fn print_balance(
-    result: &BalanceAllowanceResponse,
-    is_collateral: bool,
+impl ExecutionReport {
+    pub fn average_price(&self) -> Option<Decimal> {
+        (self.filled > Decimal::ZERO).then(|| self.notional / self.filled)
+    }
+
+    /// Slippage of the average fill against the arrival midpoint, in basis
+    /// points. Positive means the fills were worse than arrival.
+    pub fn slippage_bps(&self) -> Option<Decimal> {
+        let avg = self.average_price()?;
+        if self.arrival_mid.is_zero() {
+            return None;
+        }
+        let diff = match self.side {
+            Side::Sell => self.arrival_mid - avg,
+            _ => avg - self.arrival_mid,
+        };
+        Some((diff / self.arrival_mid * Decimal::from(10_000)).round_dp(1))
+    }
+}
+
+pub fn print_execution_report(
+    report: &ExecutionReport,
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
-    let divisor = Decimal::from(10u64.pow(USDC_DECIMALS));
-    let human_balance = result.balance / divisor;
+    let avg = report.average_price();
+    let slippage = report.slippage_bps();
     match output {
         OutputFormat::Table => {
-            if is_collateral {
-                println!("Balance: {}", format_decimal(human_balance));
+            let status = if report.interrupted {
+                "Interrupted"
             } else {
-                println!("Balance: {human_balance} shares");
-            }
-            if !result.allowances.is_empty() {
-                println!("Allowances:");
-                for (addr, allowance) in &result.allowances {
-                    println!("  {}: {allowance}", truncate(&addr.to_string(), 14));
-                }
-            }
+                "Completed"
+            };
+            let rows = vec![
+                ["Algorithm".into(), report.algo.to_uppercase()],
+                ["Status".into(), status.into()],
+                ["Side".into(), report.side.to_string()],
+                [
+                    "Filled".into(),
+                    format!("{} / {} shares", report.filled, report.target),
+                ],
+                ["Orders".into(), report.orders.to_string()],
+                [
+                    "Avg Price".into(),
+                    avg.map_or_else(|| "—".into(), |p| p.round_dp(4).to_string()),
+                ],
+                ["Arrival Mid".into(), report.arrival_mid.to_string()],
+                [
+                    "Slippage".into(),
+                    slippage.map_or_else(|| "—".into(), |s| format!("{s} bps")),
+                ],
+                ["Notional".into(), format_decimal(report.notional)],
+            ];
+            super::print_detail_table(rows);
         }
         OutputFormat::Json => {
-            let allowances: serde_json::Map<String, serde_json::Value> = result
-                .allowances
-                .iter()
-                .map(|(addr, val)| (addr.to_string(), json!(val)))
-                .collect();
-            let data = json!({
-                "balance": human_balance.to_string(),
-                "allowances": allowances,
-            });
-            super::print_json(&data)?;
+            super::print_json(&json!({
+                "algo": report.algo,
+                "interrupted": report.interrupted,
+                "side": report.side.to_string(),
+                "target": report.target.to_string(),
+                "filled": report.filled.to_string(),
+                "orders": report.orders,
+                "average_price": avg.map(|p| p.to_string()),
+                "arrival_mid": report.arrival_mid.to_string(),
+                "slippage_bps": slippage.map(|s| s.to_string()),
+                "notional": report.notional.to_string(),
+            }))?;
         }
     }
     Ok(())
 }
 
-pub fn print_notifications(
-    result: &[NotificationResponse],
-    output: &OutputFormat,
-) -> anyhow::Result<()> {
+fn trigger_to_json(t: &Trigger) -> serde_json::Value {
+    json!({
+        "id": t.id,
+        "token_id": t.token_id,
+        "side": t.side.to_string(),
+        "direction": match t.direction {
+            Direction::Below => "below",
+            Direction::Above => "above",
+        },
+        "trigger_price": t.trigger_price.to_string(),
+        "size": t.size.to_string(),
+        "limit_price": t.limit_price.map(|p| p.to_string()),
+        "source": match t.source {
+            PriceSource::Midpoint => "midpoint",
+            PriceSource::LastTrade => "last-trade",
+        },
+        "created_at": t.created_at,
+    })
+}
+
+pub fn print_trigger_saved(trigger: &Trigger, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
-            if result.is_empty() {
-                println!("No notifications.");
+            let op = match trigger.direction {
+                Direction::Below => "<=",
+                Direction::Above => ">=",
+            };
+            let order = trigger
+                .limit_price
+                .map_or_else(|| "market".to_string(), |p| format!("limit @ {p}"));
+            println!(
+                "Trigger #{} saved: {} {} ({order}) when price {op} {}",
+                trigger.id, trigger.side, trigger.size, trigger.trigger_price
+            );
+        }
+        OutputFormat::Json => {
+            super::print_json(&trigger_to_json(trigger))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_triggers(triggers: &[Trigger], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if triggers.is_empty() {
+                println!("No pending triggers.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: u64,
+                #[tabled(rename = "Token ID")]
+                token_id: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "When")]
+                when: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Order")]
+                order: String,
+            }
+            let rows: Vec<Row> = triggers
+                .iter()
+                .map(|t| {
+                    let source = match t.source {
+                        PriceSource::Midpoint => "mid",
+                        PriceSource::LastTrade => "last",
+                    };
+                    let op = match t.direction {
+                        Direction::Below => "<=",
+                        Direction::Above => ">=",
+                    };
+                    Row {
+                        id: t.id,
+                        token_id: truncate(&t.token_id, 20),
+                        side: t.side.to_string(),
+                        when: format!("{source} {op} {}", t.trigger_price),
+                        size: t.size.to_string(),
+                        order: t
+                            .limit_price
+                            .map_or_else(|| "market".into(), |p| format!("limit @ {p}")),
+                    }
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = triggers.iter().map(trigger_to_json).collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+/// One-line outcome of a journal entry, from its recorded response.
+fn journal_summary(entry: &Entry) -> String {
+    let r = &entry.response;
+    if let Some(err) = r["error"].as_str() {
+        return format!("error: {err}");
+    }
+    match entry.action {
+        Action::Post => {
+            let results = r.as_array().map_or_else(|| vec![r], |a| a.iter().collect());
+            let ok = results.iter().filter(|r| r["success"] == true).count();
+            if let [only] = results.as_slice() {
+                only["status"].as_str().unwrap_or("-").to_string()
+            } else {
+                format!("{ok}/{} accepted", results.len())
+            }
+        }
+        Action::Cancel => {
+            let canceled = r["canceled"].as_array().map_or(0, Vec::len);
+            let failed = r["not_canceled"]
+                .as_object()
+                .map_or(0, serde_json::Map::len);
+            if failed == 0 {
+                format!("{canceled} canceled")
+            } else {
+                format!("{canceled} canceled, {failed} not")
+            }
+        }
+        Action::Fill => format!(
+            "{} {} @ {}",
+            r["side"].as_str().unwrap_or("?"),
+            r["size"].as_str().unwrap_or("?"),
+            r["price"].as_str().unwrap_or("?")
+        ),
+    }
+}
+
+fn journal_entry_to_json(n: usize, entry: &Entry) -> serde_json::Value {
+    let mut data = serde_json::to_value(entry).unwrap_or_default();
+    data["entry"] = json!(n);
+    data
+}
+
+pub fn print_journal(entries: &[(usize, &Entry)], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if entries.is_empty() {
+                println!("No journal entries.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                n: usize,
+                #[tabled(rename = "Time")]
+                at: String,
+                #[tabled(rename = "Action")]
+                action: String,
+                #[tabled(rename = "Command")]
+                command: String,
+                #[tabled(rename = "Orders")]
+                orders: String,
+                #[tabled(rename = "Result")]
+                result: String,
+            }
+            let rows: Vec<Row> = entries
+                .iter()
+                .map(|(n, e)| Row {
+                    n: *n,
+                    at: e.at.get(..19).unwrap_or(&e.at).replace('T', " "),
+                    action: e.action.to_string(),
+                    command: e.command.clone(),
+                    orders: match e.order_ids.as_slice() {
+                        [] => "—".into(),
+                        [id] => truncate(id, 14),
+                        [id, rest @ ..] => format!("{} +{}", truncate(id, 14), rest.len()),
+                    },
+                    result: truncate(&journal_summary(e), 40),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = entries
+                .iter()
+                .map(|(n, e)| journal_entry_to_json(*n, e))
+                .collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_journal_entry(n: usize, entry: &Entry, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            let rows = vec![
+                ["Entry".into(), n.to_string()],
+                ["Time".into(), entry.at.clone()],
+                ["Action".into(), entry.action.to_string()],
+                ["Command".into(), entry.command.clone()],
+                ["Order IDs".into(), entry.order_ids.join("\n")],
+                ["Result".into(), journal_summary(entry)],
+            ];
+            super::print_detail_table(rows);
+            if !entry.request.is_null() {
+                println!(
+                    "Request:\n{}",
+                    serde_json::to_string_pretty(&entry.request)?
+                );
+            }
+            println!(
+                "Response:\n{}",
+                serde_json::to_string_pretty(&entry.response)?
+            );
+        }
+        OutputFormat::Json => {
+            super::print_json(&journal_entry_to_json(n, entry))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_journal_synced(recorded: usize, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => println!("Recorded {recorded} new fill(s)."),
+        OutputFormat::Json => {
+            super::print_json(&json!({"recorded": recorded}))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_trigger_canceled(id: u64, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => println!("Trigger #{id} canceled."),
+        OutputFormat::Json => {
+            super::print_json(&json!({"canceled": id}))?;
+        }
+    }
+    Ok(())
+}
+
+/// One side of a `clob arb-complement` trade.
+pub struct ComplementLeg {
+    pub outcome: String,
+    pub token_id: U256,
+    /// Price cap of the FOK buy: the deepest ask level the size reaches.
+    pub limit: Decimal,
+    pub est_cost: Decimal,
+    /// Shares received and USDC paid, once the order has filled.
+    pub filled: Option<(Decimal, Decimal)>,
+}
+
+pub struct ComplementReport {
+    pub condition_id: B256,
+    pub sets: Decimal,
+    /// YES ask + NO ask at the top of the book.
+    pub ask_sum: Decimal,
+    pub est_profit: Decimal,
+    pub legs: Vec<ComplementLeg>,
+    /// Sets merged back into USDC and the merge transaction.
+    pub merged: Option<(Decimal, B256)>,
+}
+
+impl ComplementReport {
+    /// USDC returned by the merge less what both legs cost.
+    pub fn realized(&self) -> Option<Decimal> {
+        let (merged, _) = self.merged?;
+        let cost: Decimal = self
+            .legs
+            .iter()
+            .map(|l| l.filled.map(|(_, usdc)| usdc))
+            .sum::<Option<Decimal>>()?;
+        Some(merged - cost)
+    }
+}
+
+pub fn print_complement_report(r: &ComplementReport, output: &OutputFormat) -> anyhow::Result<()> {
+    let dash = || "—".to_string();
+    match output {
+        OutputFormat::Table => {
+            let mut rows = vec![
+                ["Condition".into(), r.condition_id.to_string()],
+                ["Sets".into(), r.sets.to_string()],
+                ["Ask Sum".into(), r.ask_sum.to_string()],
+                ["Est. Profit".into(), format_decimal(r.est_profit)],
+            ];
+            for leg in &r.legs {
+                let value = match leg.filled {
+                    Some((shares, usdc)) => format!("{shares} for {}", format_decimal(usdc)),
+                    None => format!(
+                        "up to {} (est. {})",
+                        leg.limit,
+                        format_decimal(leg.est_cost)
+                    ),
+                };
+                rows.push([format!("Buy {}", leg.outcome), value]);
+            }
+            if let Some((merged, tx)) = r.merged {
+                rows.push(["Merged".into(), merged.to_string()]);
+                rows.push(["Merge Tx".into(), tx.to_string()]);
+            }
+            if let Some(realized) = r.realized() {
+                rows.push(["Realized".into(), format_decimal(realized)]);
+                let per_set = r.merged.map_or_else(dash, |(m, _)| {
+                    if m.is_zero() {
+                        dash()
+                    } else {
+                        format!("{}%", (realized / m * Decimal::ONE_HUNDRED).round_dp(2))
+                    }
+                });
+                rows.push(["Realized Edge".into(), per_set]);
+            }
+            super::print_detail_table(rows);
+        }
+        OutputFormat::Json => {
+            let legs: Vec<_> = r
+                .legs
+                .iter()
+                .map(|l| {
+                    json!({
+                        "outcome": l.outcome,
+                        "token_id": l.token_id.to_string(),
+                        "limit": l.limit.to_string(),
+                        "est_cost": l.est_cost.round_dp(6).to_string(),
+                        "shares": l.filled.map(|(s, _)| s.to_string()),
+                        "cost": l.filled.map(|(_, c)| c.to_string()),
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "condition_id": r.condition_id.to_string(),
+                "sets": r.sets.to_string(),
+                "ask_sum": r.ask_sum.to_string(),
+                "est_profit": r.est_profit.round_dp(6).to_string(),
+                "legs": legs,
+                "merged": r.merged.map(|(m, _)| m.to_string()),
+                "merge_tx": r.merged.map(|(_, tx)| tx.to_string()),
+                "realized": r.realized().map(|p| p.to_string()),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// How a prospective resting order would fare in a market's liquidity rewards.
+pub struct RewardCheck {
+    pub question: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub midpoint: Decimal,
+    /// Furthest an order may sit from the midpoint and still score, as a price.
+    pub max_spread: Decimal,
+    pub min_size: Decimal,
+    /// Reward pool paid out across the market per day.
+    pub daily_rate: Decimal,
+    /// Why the order would not score, when it would not.
+    pub blocker: Option<String>,
+    pub score: Decimal,
+    /// Score of the liquidity already resting inside the band.
+    pub competing: Decimal,
+}
+
+impl RewardCheck {
+    /// Fraction of the pool the order would earn against the current book.
+    pub fn share(&self) -> Decimal {
+        if self.score.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.score / (self.score + self.competing)
+    }
+
+    pub fn est_daily(&self) -> Decimal {
+        self.share() * self.daily_rate
+    }
+}
+
+pub fn print_reward_check(r: &RewardCheck, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            let rows = vec![
+                ["Market".into(), r.question.clone()],
+                [
+                    "Order".into(),
+                    format!("{} {} @ {}", r.side, r.size, r.price),
+                ],
+                ["Midpoint".into(), r.midpoint.to_string()],
+                ["Max Spread".into(), format!("±{}", r.max_spread)],
+                ["Min Size".into(), r.min_size.to_string()],
+                ["Daily Pool".into(), format_decimal(r.daily_rate)],
+                [
+                    "Scores".into(),
+                    r.blocker
+                        .as_ref()
+                        .map_or_else(|| "yes".into(), |b| format!("no ({b})")),
+                ],
+                ["Order Score".into(), r.score.round_dp(4).to_string()],
+                [
+                    "Competing Score".into(),
+                    r.competing.round_dp(4).to_string(),
+                ],
+                [
+                    "Pool Share".into(),
+                    format!("{}%", (r.share() * Decimal::ONE_HUNDRED).round_dp(2)),
+                ],
+                ["Est. Daily".into(), format_decimal(r.est_daily())],
+            ];
+            super::print_detail_table(rows);
+        }
+        OutputFormat::Json => {
+            super::print_json(&json!({
+                "question": r.question,
+                "side": r.side.to_string(),
+                "price": r.price.to_string(),
+                "size": r.size.to_string(),
+                "midpoint": r.midpoint.to_string(),
+                "max_spread": r.max_spread.to_string(),
+                "min_size": r.min_size.to_string(),
+                "daily_rate": r.daily_rate.to_string(),
+                "scores": r.blocker.is_none(),
+                "blocker": r.blocker,
+                "score": r.score.round_dp(6).to_string(),
+                "competing_score": r.competing.round_dp(6).to_string(),
+                "share": r.share().round_dp(6).to_string(),
+                "est_daily": r.est_daily().round_dp(6).to_string(),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// One position `clob flatten` plans to sell.
+pub struct FlattenItem {
+    pub title: String,
+    pub outcome: String,
+    pub token_id: U256,
+    pub shares: Decimal,
+    /// Lowest price the sell order may fill at.
+    pub floor: Decimal,
+    /// `None` when there are no bids to sell into.
+    pub estimate: Option<FillEstimate>,
+}
+
+pub fn print_flatten_plan(items: &[FlattenItem], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if items.is_empty() {
+                println!("No positions to flatten.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                title: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Best Bid")]
+                best: String,
+                #[tabled(rename = "Est. Avg")]
+                avg: String,
+                #[tabled(rename = "Floor")]
+                floor: String,
+                #[tabled(rename = "Est. Proceeds")]
+                proceeds: String,
+            }
+            let dash = || "—".to_string();
+            let rows: Vec<Row> = items
+                .iter()
+                .map(|i| Row {
+                    title: truncate(&i.title, 40),
+                    outcome: i.outcome.clone(),
+                    shares: i.shares.to_string(),
+                    best: i
+                        .estimate
+                        .as_ref()
+                        .map_or_else(dash, |e| e.best_price.to_string()),
+                    avg: i
+                        .estimate
+                        .as_ref()
+                        .map_or_else(dash, |e| e.average_price().round_dp(4).to_string()),
+                    floor: i.floor.to_string(),
+                    proceeds: i
+                        .estimate
+                        .as_ref()
+                        .map_or_else(|| "no bids".into(), |e| format_decimal(e.notional)),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+            let total: Decimal = items
+                .iter()
+                .filter_map(|i| i.estimate.as_ref())
+                .map(|e| e.notional)
+                .sum();
+            println!("Estimated proceeds: {}", format_decimal(total));
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = items
+                .iter()
+                .map(|i| {
+                    json!({
+                        "title": i.title,
+                        "outcome": i.outcome,
+                        "token_id": i.token_id.to_string(),
+                        "shares": i.shares.to_string(),
+                        "floor": i.floor.to_string(),
+                        "estimate": i.estimate.as_ref().map(fill_estimate_to_json),
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn cancel_result_to_json(result: &CancelOrdersResponse) -> serde_json::Value {
+    json!({
+        "canceled": result.canceled,
+        "not_canceled": result.not_canceled,
+    })
+}
+
+pub fn print_cancel_result(
+    result: &CancelOrdersResponse,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if !result.canceled.is_empty() {
+                println!("Canceled: {}", result.canceled.join(", "));
+            }
+            if !result.not_canceled.is_empty() {
+                println!("Not canceled:");
+                for (id, reason) in &result.not_canceled {
+                    println!("  {id}: {reason}");
+                }
+            }
+            if result.canceled.is_empty() && result.not_canceled.is_empty() {
+                println!("No orders to cancel.");
+            }
+        }
+        OutputFormat::Json => {
+            super::print_json(&cancel_result_to_json(result))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn trade_to_json(t: &TradeResponse) -> serde_json::Value {
+    json!({
+        "id": t.id,
+        "taker_order_id": t.taker_order_id,
+        "market": t.market.to_string(),
+        "asset_id": t.asset_id.to_string(),
+        "side": t.side.to_string(),
+        "size": t.size.to_string(),
+        "price": t.price.to_string(),
+        "fee_rate_bps": t.fee_rate_bps.to_string(),
+        "status": t.status.to_string(),
+        "match_time": t.match_time.to_rfc3339(),
+        "outcome": t.outcome,
+        "trader_side": format!("{:?}", t.trader_side),
+        "transaction_hash": t.transaction_hash.to_string(),
+    })
+}
+
+pub fn print_trades(result: &Page<TradeResponse>, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if result.data.is_empty() {
+                println!("No trades found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Status")]
+                status: String,
+                #[tabled(rename = "Time")]
+                match_time: String,
+            }
+            let rows: Vec<Row> = result
+                .data
+                .iter()
+                .map(|t| Row {
+                    id: truncate(&t.id, 12),
+                    side: t.side.to_string(),
+                    price: t.price.to_string(),
+                    size: t.size.to_string(),
+                    status: t.status.to_string(),
+                    match_time: t.match_time.format("%Y-%m-%d %H:%M").to_string(),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+            if result.next_cursor != END_CURSOR {
+                println!("Next cursor: {}", result.next_cursor);
+            }
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = result.data.iter().map(trade_to_json).collect();
+            let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
+            super::print_json(&wrapper)?;
+        }
+    }
+    Ok(())
+}
+
+/// USDC uses 6 decimal places on-chain.
+const USDC_DECIMALS: u32 = 6;
+
+pub fn print_balance(
+    result: &BalanceAllowanceResponse,
+    is_collateral: bool,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    let divisor = Decimal::from(10u64.pow(USDC_DECIMALS));
+    let human_balance = result.balance / divisor;
+    match output {
+        OutputFormat::Table => {
+            if is_collateral {
+                println!("Balance: {}", format_decimal(human_balance));
+            } else {
+                println!("Balance: {human_balance} shares");
+            }
+            if !result.allowances.is_empty() {
+                println!("Allowances:");
+                for (addr, allowance) in &result.allowances {
+                    println!("  {}: {allowance}", truncate(&addr.to_string(), 14));
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let allowances: serde_json::Map<String, serde_json::Value> = result
+                .allowances
+                .iter()
+                .map(|(addr, val)| (addr.to_string(), json!(val)))
+                .collect();
+            let data = json!({
+                "balance": human_balance.to_string(),
+                "allowances": allowances,
+            });
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+/// One held outcome token, as the CLOB sees it and as the chain does.
+pub struct TokenBalance {
+    pub token_id: U256,
+    pub market: String,
+    pub outcome: String,
+    /// Shares the CLOB has cached for trading.
+    pub clob: Decimal,
+    pub on_chain: Decimal,
+    /// Whether every exchange allowance the CLOB reports is non-zero.
+    pub approved: bool,
+}
+
+impl TokenBalance {
+    /// The CLOB's cached view is out of date; `update-balance` refreshes it.
+    pub fn needs_update(&self) -> bool {
+        self.clob != self.on_chain || !self.approved
+    }
+}
+
+/// `refreshed` counts the balances refreshed afterwards; only JSON includes it,
+/// since the table is printed before asking.
+pub fn print_token_balances(
+    balances: &[TokenBalance],
+    refreshed: usize,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if balances.is_empty() {
+                println!("No conditional token balances.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "CLOB")]
+                clob: String,
+                #[tabled(rename = "On-Chain")]
+                on_chain: String,
+                #[tabled(rename = "Status")]
+                status: String,
+                #[tabled(rename = "Token ID")]
+                token_id: String,
+            }
+            let rows: Vec<Row> = balances
+                .iter()
+                .map(|b| Row {
+                    market: truncate(&b.market, 40),
+                    outcome: b.outcome.clone(),
+                    clob: b.clob.normalize().to_string(),
+                    on_chain: b.on_chain.normalize().to_string(),
+                    status: if b.needs_update() {
+                        "update-balance".into()
+                    } else {
+                        "ok".into()
+                    },
+                    token_id: truncate(&b.token_id.to_string(), 20),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = balances
+                .iter()
+                .map(|b| {
+                    json!({
+                        "token_id": b.token_id.to_string(),
+                        "market": b.market,
+                        "outcome": b.outcome,
+                        "clob_balance": b.clob.to_string(),
+                        "on_chain_balance": b.on_chain.to_string(),
+                        "approved": b.approved,
+                        "needs_update": b.needs_update(),
+                    })
+                })
+                .collect();
+            super::print_json(&json!({"balances": data, "refreshed": refreshed}))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_notifications(
+    result: &[NotificationResponse],
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if result.is_empty() {
+                println!("No notifications.");
                 return Ok(());
             }
             #[derive(Tabled)]
@@ -1006,6 +2113,17 @@ pub fn print_notifications(
     Ok(())
 }
 
+pub fn user_earning_to_json(e: &UserEarningResponse) -> serde_json::Value {
+    json!({
+        "date": e.date.to_string(),
+        "condition_id": e.condition_id.to_string(),
+        "asset_address": e.asset_address.to_string(),
+        "maker_address": e.maker_address.to_string(),
+        "earnings": e.earnings.to_string(),
+        "asset_rate": e.asset_rate.to_string(),
+    })
+}
+
 pub fn print_rewards(
     result: &Page<UserEarningResponse>,
     output: &OutputFormat,
@@ -1044,22 +2162,205 @@ pub fn print_rewards(
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
+            let data: Vec<_> = result.data.iter().map(user_earning_to_json).collect();
+            let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
+            super::print_json(&wrapper)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reward earnings for one day, per market where the endpoint breaks them down.
+pub struct EarningRow {
+    pub date: NaiveDate,
+    pub condition_id: Option<B256>,
+    pub question: String,
+    pub earnings: Decimal,
+}
+
+pub const EARNING_COLUMNS: [&str; 4] = ["date", "condition_id", "question", "earnings"];
+
+impl EarningRow {
+    pub fn fields(&self) -> [String; 4] {
+        [
+            self.date.to_string(),
+            self.condition_id.map(|c| c.to_string()).unwrap_or_default(),
+            self.question.clone(),
+            self.earnings.to_string(),
+        ]
+    }
+}
+
+/// One market's earnings over a range.
+pub struct MarketEarnings {
+    pub condition_id: B256,
+    pub question: String,
+    pub total: Decimal,
+    /// Days in the range the market paid anything.
+    pub days: usize,
+}
+
+/// Earnings rows for every day from `from` through `to`.
+pub struct EarningsRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub rows: Vec<EarningRow>,
+}
+
+impl EarningsRange {
+    pub fn total(&self) -> Decimal {
+        self.rows.iter().map(|r| r.earnings).sum()
+    }
+
+    /// Average per calendar day, counting days that earned nothing.
+    pub fn daily_average(&self) -> Decimal {
+        let days = (self.to - self.from).num_days() + 1;
+        self.total() / Decimal::from(days)
+    }
+
+    /// Totals for every day in the range, zero-earning days included.
+    pub fn by_day(&self) -> Vec<(NaiveDate, Decimal)> {
+        self.from
+            .iter_days()
+            .take_while(|d| *d <= self.to)
+            .map(|day| {
+                let total = self
+                    .rows
+                    .iter()
+                    .filter(|r| r.date == day)
+                    .map(|r| r.earnings)
+                    .sum();
+                (day, total)
+            })
+            .collect()
+    }
+
+    /// Per-market totals, best first.
+    pub fn by_market(&self) -> Vec<MarketEarnings> {
+        let mut markets: Vec<MarketEarnings> = Vec::new();
+        // A market can have several rows per day (one per asset or maker),
+        // so paying days are counted as distinct dates.
+        let mut paid_days: Vec<BTreeSet<NaiveDate>> = Vec::new();
+        for row in &self.rows {
+            let Some(condition_id) = row.condition_id else {
+                continue;
+            };
+            let i = match markets.iter().position(|m| m.condition_id == condition_id) {
+                Some(i) => i,
+                None => {
+                    markets.push(MarketEarnings {
+                        condition_id,
+                        question: String::new(),
+                        total: Decimal::ZERO,
+                        days: 0,
+                    });
+                    paid_days.push(BTreeSet::new());
+                    markets.len() - 1
+                }
+            };
+            let market = &mut markets[i];
+            if market.question.is_empty() {
+                market.question.clone_from(&row.question);
+            }
+            market.total += row.earnings;
+            if !row.earnings.is_zero() {
+                paid_days[i].insert(row.date);
+                market.days = paid_days[i].len();
+            }
+        }
+        markets.sort_by_key(|m| std::cmp::Reverse(m.total));
+        markets
+    }
+}
+
+/// How many markets the range summary table lists.
+const BEST_MARKETS: usize = 10;
+
+pub fn print_earnings_range(range: &EarningsRange, output: &OutputFormat) -> anyhow::Result<()> {
+    let by_day = range.by_day();
+    let best_day = by_day.iter().max_by_key(|(_, total)| *total);
+    let markets = range.by_market();
+    match output {
+        OutputFormat::Table => {
+            #[derive(Tabled)]
+            struct DayRow {
+                #[tabled(rename = "Date")]
+                date: String,
+                #[tabled(rename = "Earnings")]
+                earnings: String,
+            }
+            let rows: Vec<DayRow> = by_day
                 .iter()
-                .map(|e| {
+                .map(|(date, total)| DayRow {
+                    date: date.to_string(),
+                    earnings: format_decimal(*total),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+
+            if !markets.is_empty() {
+                #[derive(Tabled)]
+                struct MarketRow {
+                    #[tabled(rename = "Market")]
+                    market: String,
+                    #[tabled(rename = "Days")]
+                    days: usize,
+                    #[tabled(rename = "Total")]
+                    total: String,
+                }
+                let rows: Vec<MarketRow> = markets
+                    .iter()
+                    .take(BEST_MARKETS)
+                    .map(|m| MarketRow {
+                        market: if m.question.is_empty() {
+                            truncate(&m.condition_id.to_string(), 14)
+                        } else {
+                            truncate(&m.question, 40)
+                        },
+                        days: m.days,
+                        total: format_decimal(m.total),
+                    })
+                    .collect();
+                println!("Best markets:");
+                println!("{}", Table::new(rows).with(Style::rounded()));
+            }
+
+            println!("Total: {}", format_decimal(range.total()));
+            println!(
+                "Daily average: {}",
+                format_decimal(range.daily_average().round_dp(2))
+            );
+            if let Some((date, total)) = best_day {
+                println!("Best day: {date} ({})", format_decimal(*total));
+            }
+        }
+        OutputFormat::Json => {
+            let days: Vec<_> = by_day
+                .iter()
+                .map(|(date, total)| json!({"date": date.to_string(), "earnings": total.to_string()}))
+                .collect();
+            let markets: Vec<_> = markets
+                .iter()
+                .map(|m| {
                     json!({
-                        "date": e.date.to_string(),
-                        "condition_id": e.condition_id.to_string(),
-                        "asset_address": e.asset_address.to_string(),
-                        "maker_address": e.maker_address.to_string(),
-                        "earnings": e.earnings.to_string(),
-                        "asset_rate": e.asset_rate.to_string(),
+                        "condition_id": m.condition_id.to_string(),
+                        "question": m.question,
+                        "days": m.days,
+                        "total": m.total.to_string(),
                     })
                 })
                 .collect();
-            let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
-            super::print_json(&wrapper)?;
+            super::print_json(&json!({
+                "from": range.from.to_string(),
+                "to": range.to.to_string(),
+                "total": range.total().to_string(),
+                "daily_average": range.daily_average().round_dp(6).to_string(),
+                "best_day": best_day.map(|(date, total)| {
+                    json!({"date": date.to_string(), "earnings": total.to_string()})
+                }),
+                "days": days,
+                "markets": markets,
+            }))?;
         }
     }
     Ok(())
@@ -1219,6 +2520,21 @@ pub fn print_reward_percentages(
     Ok(())
 }
 
+pub fn current_reward_to_json(r: &CurrentRewardResponse) -> serde_json::Value {
+    json!({
+        "condition_id": r.condition_id.to_string(),
+        "rewards_max_spread": r.rewards_max_spread.to_string(),
+        "rewards_min_size": r.rewards_min_size.to_string(),
+        "rewards_config": r.rewards_config.iter().map(|c| json!({
+            "asset_address": c.asset_address.to_string(),
+            "start_date": c.start_date.to_string(),
+            "end_date": c.end_date.to_string(),
+            "rate_per_day": c.rate_per_day.to_string(),
+            "total_rewards": c.total_rewards.to_string(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
 pub fn print_current_rewards(
     result: &Page<CurrentRewardResponse>,
     output: &OutputFormat,
@@ -1257,24 +2573,7 @@ pub fn print_current_rewards(
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
-                .iter()
-                .map(|r| {
-                    json!({
-                        "condition_id": r.condition_id.to_string(),
-                        "rewards_max_spread": r.rewards_max_spread.to_string(),
-                        "rewards_min_size": r.rewards_min_size.to_string(),
-                        "rewards_config": r.rewards_config.iter().map(|c| json!({
-                            "asset_address": c.asset_address.to_string(),
-                            "start_date": c.start_date.to_string(),
-                            "end_date": c.end_date.to_string(),
-                            "rate_per_day": c.rate_per_day.to_string(),
-                            "total_rewards": c.total_rewards.to_string(),
-                        })).collect::<Vec<_>>(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = result.data.iter().map(current_reward_to_json).collect();
             let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
             super::print_json(&wrapper)?;
         }
@@ -1282,6 +2581,33 @@ pub fn print_current_rewards(
     Ok(())
 }
 
+pub fn market_reward_to_json(r: &MarketRewardResponse) -> serde_json::Value {
+    json!({
+        "condition_id": r.condition_id.to_string(),
+        "question": r.question,
+        "market_slug": r.market_slug,
+        "event_slug": r.event_slug,
+        "rewards_max_spread": r.rewards_max_spread.to_string(),
+        "rewards_min_size": r.rewards_min_size.to_string(),
+        "market_competitiveness": r.market_competitiveness.to_string(),
+        "tokens": r.tokens.iter().map(|t| json!({
+            "token_id": t.token_id.to_string(),
+            "outcome": t.outcome,
+            "price": t.price.to_string(),
+            "winner": t.winner,
+        })).collect::<Vec<_>>(),
+        "rewards_config": r.rewards_config.iter().map(|c| json!({
+            "id": c.id,
+            "asset_address": c.asset_address.to_string(),
+            "start_date": c.start_date.to_string(),
+            "end_date": c.end_date.to_string(),
+            "rate_per_day": c.rate_per_day.to_string(),
+            "total_rewards": c.total_rewards.to_string(),
+            "total_days": c.total_days.to_string(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
 pub fn print_market_reward(
     result: &Page<MarketRewardResponse>,
     output: &OutputFormat,
@@ -1314,36 +2640,7 @@ pub fn print_market_reward(
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
-                .iter()
-                .map(|r| {
-                    json!({
-                        "condition_id": r.condition_id.to_string(),
-                        "question": r.question,
-                        "market_slug": r.market_slug,
-                        "event_slug": r.event_slug,
-                        "rewards_max_spread": r.rewards_max_spread.to_string(),
-                        "rewards_min_size": r.rewards_min_size.to_string(),
-                        "market_competitiveness": r.market_competitiveness.to_string(),
-                        "tokens": r.tokens.iter().map(|t| json!({
-                            "token_id": t.token_id.to_string(),
-                            "outcome": t.outcome,
-                            "price": t.price.to_string(),
-                            "winner": t.winner,
-                        })).collect::<Vec<_>>(),
-                        "rewards_config": r.rewards_config.iter().map(|c| json!({
-                            "id": c.id,
-                            "asset_address": c.asset_address.to_string(),
-                            "start_date": c.start_date.to_string(),
-                            "end_date": c.end_date.to_string(),
-                            "rate_per_day": c.rate_per_day.to_string(),
-                            "total_rewards": c.total_rewards.to_string(),
-                            "total_days": c.total_days.to_string(),
-                        })).collect::<Vec<_>>(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = result.data.iter().map(market_reward_to_json).collect();
             let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
             super::print_json(&wrapper)?;
         }
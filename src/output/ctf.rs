@@ -1,5 +1,6 @@
 use alloy::primitives::{B256, U256};
 use anyhow::Result;
+use rust_decimal::Decimal;
 
 use super::{OutputFormat, print_detail_table};
 
@@ -83,3 +84,92 @@ pub fn print_position_id(position_id: U256, output: &OutputFormat) -> Result<()>
         }
     }
 }
+
+/// One outcome of a neg-risk market touched by `convert-neg-risk`.
+#[derive(Debug)]
+pub struct ConvertLeg {
+    pub index: u8,
+    pub outcome: String,
+    pub token_id: Option<U256>,
+}
+
+#[derive(Debug)]
+pub struct ConvertPreview {
+    pub market_id: B256,
+    pub event: String,
+    /// Outcomes whose NO tokens are given up.
+    pub spent: Vec<ConvertLeg>,
+    /// Outcomes whose YES tokens are received.
+    pub received: Vec<ConvertLeg>,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    /// YES shares received per outcome in `received`.
+    pub shares_out: Decimal,
+    pub collateral_out: Decimal,
+}
+
+fn leg_to_json(leg: &ConvertLeg) -> serde_json::Value {
+    serde_json::json!({
+        "index": leg.index,
+        "outcome": leg.outcome,
+        "token_id": leg.token_id.map(|t| t.to_string()),
+    })
+}
+
+fn legs_summary(legs: &[ConvertLeg]) -> String {
+    if legs.is_empty() {
+        return "—".into();
+    }
+    legs.iter()
+        .map(|l| format!("[{}] {}", l.index, l.outcome))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn print_convert_preview(preview: &ConvertPreview, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "market_id": format!("{}", preview.market_id),
+                "event": preview.event,
+                "amount": preview.amount.to_string(),
+                "fee": preview.fee.to_string(),
+                "no_spent": preview.spent.iter().map(leg_to_json).collect::<Vec<_>>(),
+                "yes_received": preview.received.iter().map(leg_to_json).collect::<Vec<_>>(),
+                "yes_shares_each": preview.shares_out.to_string(),
+                "collateral_received": preview.collateral_out.to_string(),
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+            Ok(())
+        }
+        OutputFormat::Table => {
+            let rows = vec![
+                ["Event".into(), preview.event.clone()],
+                ["Market ID".into(), format!("{}", preview.market_id)],
+                [
+                    "NO Spent".into(),
+                    format!(
+                        "{} each: {}",
+                        preview.amount.normalize(),
+                        legs_summary(&preview.spent)
+                    ),
+                ],
+                [
+                    "YES Received".into(),
+                    format!(
+                        "{} each: {}",
+                        preview.shares_out.normalize(),
+                        legs_summary(&preview.received)
+                    ),
+                ],
+                [
+                    "USDC Received".into(),
+                    format!("${}", preview.collateral_out.normalize()),
+                ],
+                ["Fee".into(), preview.fee.normalize().to_string()],
+            ];
+            print_detail_table(rows);
+            Ok(())
+        }
+    }
+}
@@ -18,6 +18,27 @@ fn format_market(m: &Market) -> String {
     }
 }
 
+pub fn position_to_json(p: &Position) -> serde_json::Value {
+    json!({
+        "title": p.title,
+        "slug": p.slug,
+        "outcome": p.outcome,
+        "outcome_index": p.outcome_index,
+        "size": p.size.to_string(),
+        "avg_price": p.avg_price.to_string(),
+        "initial_value": p.initial_value.to_string(),
+        "current_value": p.current_value.to_string(),
+        "cash_pnl": p.cash_pnl.to_string(),
+        "percent_pnl": p.percent_pnl.to_string(),
+        "realized_pnl": p.realized_pnl.to_string(),
+        "cur_price": p.cur_price.to_string(),
+        "condition_id": p.condition_id.to_string(),
+        "proxy_wallet": p.proxy_wallet.to_string(),
+        "redeemable": p.redeemable,
+        "mergeable": p.mergeable,
+    })
+}
+
 pub fn print_positions(positions: &[Position], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
@@ -55,35 +76,29 @@ pub fn print_positions(positions: &[Position], output: &OutputFormat) -> anyhow:
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = positions
-                .iter()
-                .map(|p| {
-                    json!({
-                        "title": p.title,
-                        "slug": p.slug,
-                        "outcome": p.outcome,
-                        "outcome_index": p.outcome_index,
-                        "size": p.size.to_string(),
-                        "avg_price": p.avg_price.to_string(),
-                        "initial_value": p.initial_value.to_string(),
-                        "current_value": p.current_value.to_string(),
-                        "cash_pnl": p.cash_pnl.to_string(),
-                        "percent_pnl": p.percent_pnl.to_string(),
-                        "realized_pnl": p.realized_pnl.to_string(),
-                        "cur_price": p.cur_price.to_string(),
-                        "condition_id": p.condition_id.to_string(),
-                        "proxy_wallet": p.proxy_wallet.to_string(),
-                        "redeemable": p.redeemable,
-                        "mergeable": p.mergeable,
-                    })
-                })
-                .collect();
+            let data: Vec<_> = positions.iter().map(position_to_json).collect();
             super::print_json(&data)?;
         }
     }
     Ok(())
 }
 
+pub fn closed_position_to_json(p: &ClosedPosition) -> serde_json::Value {
+    json!({
+        "title": p.title,
+        "slug": p.slug,
+        "outcome": p.outcome,
+        "outcome_index": p.outcome_index,
+        "avg_price": p.avg_price.to_string(),
+        "total_bought": p.total_bought.to_string(),
+        "realized_pnl": p.realized_pnl.to_string(),
+        "cur_price": p.cur_price.to_string(),
+        "condition_id": p.condition_id.to_string(),
+        "proxy_wallet": p.proxy_wallet.to_string(),
+        "timestamp": p.timestamp,
+    })
+}
+
 pub fn print_closed_positions(
     positions: &[ClosedPosition],
     output: &OutputFormat,
@@ -118,24 +133,7 @@ pub fn print_closed_positions(
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = positions
-                .iter()
-                .map(|p| {
-                    json!({
-                        "title": p.title,
-                        "slug": p.slug,
-                        "outcome": p.outcome,
-                        "outcome_index": p.outcome_index,
-                        "avg_price": p.avg_price.to_string(),
-                        "total_bought": p.total_bought.to_string(),
-                        "realized_pnl": p.realized_pnl.to_string(),
-                        "cur_price": p.cur_price.to_string(),
-                        "condition_id": p.condition_id.to_string(),
-                        "proxy_wallet": p.proxy_wallet.to_string(),
-                        "timestamp": p.timestamp,
-                    })
-                })
-                .collect();
+            let data: Vec<_> = positions.iter().map(closed_position_to_json).collect();
             super::print_json(&data)?;
         }
     }
@@ -190,6 +188,22 @@ pub fn print_traded(t: &Traded, output: &OutputFormat) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn trade_to_json(t: &Trade) -> serde_json::Value {
+    json!({
+        "title": t.title,
+        "slug": t.slug,
+        "side": t.side.to_string(),
+        "outcome": t.outcome,
+        "outcome_index": t.outcome_index,
+        "size": t.size.to_string(),
+        "price": t.price.to_string(),
+        "timestamp": t.timestamp,
+        "condition_id": t.condition_id.to_string(),
+        "proxy_wallet": t.proxy_wallet.to_string(),
+        "transaction_hash": t.transaction_hash.to_string(),
+    })
+}
+
 pub fn print_trades(trades: &[Trade], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
@@ -224,30 +238,25 @@ pub fn print_trades(trades: &[Trade], output: &OutputFormat) -> anyhow::Result<(
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = trades
-                .iter()
-                .map(|t| {
-                    json!({
-                        "title": t.title,
-                        "slug": t.slug,
-                        "side": t.side.to_string(),
-                        "outcome": t.outcome,
-                        "outcome_index": t.outcome_index,
-                        "size": t.size.to_string(),
-                        "price": t.price.to_string(),
-                        "timestamp": t.timestamp,
-                        "condition_id": t.condition_id.to_string(),
-                        "proxy_wallet": t.proxy_wallet.to_string(),
-                        "transaction_hash": t.transaction_hash.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = trades.iter().map(trade_to_json).collect();
             super::print_json(&data)?;
         }
     }
     Ok(())
 }
 
+pub fn activity_to_json(a: &Activity) -> serde_json::Value {
+    json!({
+        "activity_type": a.activity_type.to_string(),
+        "title": a.title,
+        "size": a.size.to_string(),
+        "usdc_size": a.usdc_size.to_string(),
+        "timestamp": a.timestamp,
+        "transaction_hash": a.transaction_hash.to_string(),
+        "proxy_wallet": a.proxy_wallet.to_string(),
+    })
+}
+
 pub fn print_activity(activity: &[Activity], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
         OutputFormat::Table => {
@@ -282,20 +291,7 @@ pub fn print_activity(activity: &[Activity], output: &OutputFormat) -> anyhow::R
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = activity
-                .iter()
-                .map(|a| {
-                    json!({
-                        "activity_type": a.activity_type.to_string(),
-                        "title": a.title,
-                        "size": a.size.to_string(),
-                        "usdc_size": a.usdc_size.to_string(),
-                        "timestamp": a.timestamp,
-                        "transaction_hash": a.transaction_hash.to_string(),
-                        "proxy_wallet": a.proxy_wallet.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = activity.iter().map(activity_to_json).collect();
             super::print_json(&data)?;
         }
     }
@@ -446,6 +442,16 @@ pub fn print_live_volume(volume: &[LiveVolume], output: &OutputFormat) -> anyhow
     Ok(())
 }
 
+pub fn leaderboard_entry_to_json(e: &TraderLeaderboardEntry) -> serde_json::Value {
+    json!({
+        "rank": e.rank,
+        "proxy_wallet": e.proxy_wallet.to_string(),
+        "user_name": e.user_name,
+        "pnl": e.pnl.to_string(),
+        "volume": e.vol.to_string(),
+    })
+}
+
 pub fn print_leaderboard(
     entries: &[TraderLeaderboardEntry],
     output: &OutputFormat,
@@ -480,24 +486,23 @@ pub fn print_leaderboard(
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = entries
-                .iter()
-                .map(|e| {
-                    json!({
-                        "rank": e.rank,
-                        "proxy_wallet": e.proxy_wallet.to_string(),
-                        "user_name": e.user_name,
-                        "pnl": e.pnl.to_string(),
-                        "volume": e.vol.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = entries.iter().map(leaderboard_entry_to_json).collect();
             super::print_json(&data)?;
         }
     }
     Ok(())
 }
 
+pub fn builder_leaderboard_entry_to_json(e: &BuilderLeaderboardEntry) -> serde_json::Value {
+    json!({
+        "rank": e.rank,
+        "builder": e.builder,
+        "volume": e.volume.to_string(),
+        "active_users": e.active_users,
+        "verified": e.verified,
+    })
+}
+
 pub fn print_builder_leaderboard(
     entries: &[BuilderLeaderboardEntry],
     output: &OutputFormat,
@@ -534,15 +539,7 @@ pub fn print_builder_leaderboard(
         OutputFormat::Json => {
             let data: Vec<_> = entries
                 .iter()
-                .map(|e| {
-                    json!({
-                        "rank": e.rank,
-                        "builder": e.builder,
-                        "volume": e.volume.to_string(),
-                        "active_users": e.active_users,
-                        "verified": e.verified,
-                    })
-                })
+                .map(builder_leaderboard_entry_to_json)
                 .collect();
             super::print_json(&data)?;
         }
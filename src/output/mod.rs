@@ -7,10 +7,13 @@ pub mod data;
 pub mod events;
 pub mod markets;
 pub mod profiles;
+pub mod scan;
 pub mod series;
 pub mod sports;
 pub mod tags;
 
+use std::io::Write as _;
+
 use polymarket_client_sdk::types::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use tabled::Table;
@@ -48,6 +51,66 @@ pub fn print_json(data: &impl serde::Serialize) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Serializes a response as-is, for listings whose JSON output is the raw API shape.
+pub fn json_value<T: serde::Serialize>(data: &T) -> serde_json::Value {
+    serde_json::to_value(data).unwrap_or_default()
+}
+
+/// Receives the items of an `--all` listing. Table output is collected and
+/// printed once at the end; JSON is written straight away as an array with one
+/// element per line, so long listings can be consumed while they download.
+pub struct ListSink<T> {
+    output: OutputFormat,
+    to_json: fn(&T) -> serde_json::Value,
+    items: Vec<T>,
+    streamed: usize,
+}
+
+impl<T> ListSink<T> {
+    pub fn new(output: OutputFormat, to_json: fn(&T) -> serde_json::Value) -> Self {
+        Self {
+            output,
+            to_json,
+            items: Vec::new(),
+            streamed: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) -> anyhow::Result<()> {
+        match self.output {
+            OutputFormat::Table => self.items.push(item),
+            OutputFormat::Json => {
+                let mut out = std::io::stdout().lock();
+                let sep = if self.streamed == 0 { "[" } else { "," };
+                writeln!(
+                    out,
+                    "{sep}{}",
+                    serde_json::to_string(&(self.to_json)(&item))?
+                )?;
+                out.flush()?;
+                self.streamed += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether JSON output has already begun on stdout.
+    pub fn started(&self) -> bool {
+        self.streamed > 0
+    }
+
+    /// Closes the JSON array, or hands back the collected items for a table.
+    pub fn finish(self) -> anyhow::Result<Option<Vec<T>>> {
+        match self.output {
+            OutputFormat::Table => Ok(Some(self.items)),
+            OutputFormat::Json => {
+                println!("{}", if self.streamed == 0 { "[]" } else { "]" });
+                Ok(None)
+            }
+        }
+    }
+}
+
 pub fn print_detail_table(rows: Vec<[String; 2]>) {
     let table = Table::from_iter(rows)
         .with(Style::rounded())
@@ -0,0 +1,109 @@
+use rust_decimal::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, format_decimal, truncate};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetSide {
+    /// Buy the YES token of every outcome below $1 per set.
+    BuyAll,
+    /// Sell the YES token of every outcome above $1 per set.
+    SellAll,
+}
+
+impl std::fmt::Display for SetSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::BuyAll => "buy-all",
+            Self::SellAll => "sell-all",
+        })
+    }
+}
+
+/// Full outcome sets executable across every book while the edge holds.
+#[derive(Debug, Default)]
+pub struct SetFill {
+    /// Sum of the best prices before fees.
+    pub top_price_sum: Decimal,
+    /// Edge per set at the best prices, after fees.
+    pub top_edge: Decimal,
+    pub sets: Decimal,
+    pub notional: Decimal,
+    pub fees: Decimal,
+    pub profit: Decimal,
+}
+
+pub struct NegRiskHit {
+    pub event: String,
+    pub slug: Option<String>,
+    pub side: SetSide,
+    pub outcomes: usize,
+    pub fill: SetFill,
+}
+
+fn hit_to_json(h: &NegRiskHit) -> serde_json::Value {
+    json!({
+        "event": h.event,
+        "slug": h.slug,
+        "side": h.side.to_string(),
+        "outcomes": h.outcomes,
+        "price_sum": h.fill.top_price_sum.to_string(),
+        "edge": h.fill.top_edge.round_dp(6).to_string(),
+        "sets": h.fill.sets.to_string(),
+        "notional": h.fill.notional.round_dp(4).to_string(),
+        "fees": h.fill.fees.round_dp(4).to_string(),
+        "profit": h.fill.profit.round_dp(4).to_string(),
+    })
+}
+
+pub fn print_neg_risk_hits(hits: &[NegRiskHit], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if hits.is_empty() {
+                println!("No neg-risk edges found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Event")]
+                event: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Outcomes")]
+                outcomes: usize,
+                #[tabled(rename = "Price Sum")]
+                price_sum: String,
+                #[tabled(rename = "Edge")]
+                edge: String,
+                #[tabled(rename = "Sets")]
+                sets: String,
+                #[tabled(rename = "Fees")]
+                fees: String,
+                #[tabled(rename = "Profit")]
+                profit: String,
+            }
+            let rows: Vec<Row> = hits
+                .iter()
+                .map(|h| Row {
+                    event: truncate(&h.event, 40),
+                    side: h.side.to_string(),
+                    outcomes: h.outcomes,
+                    price_sum: h.fill.top_price_sum.to_string(),
+                    edge: format!("{}%", (h.fill.top_edge * Decimal::ONE_HUNDRED).round_dp(2)),
+                    sets: h.fill.sets.round_dp(2).to_string(),
+                    fees: format_decimal(h.fill.fees),
+                    profit: format_decimal(h.fill.profit),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = hits.iter().map(hit_to_json).collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,663 @@
+//! Simulated trading account for `--paper` mode.
+//!
+//! Orders are matched against live order book snapshots instead of being sent
+//! to the CLOB. Cash, positions, orders and fills persist in
+//! `~/.config/polymarket/paper.json`. Resting orders fill at their own price
+//! once a later snapshot crosses them; fees are not modelled.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::clob::types::response::{
+    CancelOrdersResponse, OpenOrderResponse, OrderBookSummaryResponse, OrderSummary,
+    PostOrderResponse, TradeResponse,
+};
+use polymarket_client_sdk::clob::types::{
+    OrderStatusType, OrderType, Side, TradeStatusType, TraderSide,
+};
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+/// Conditional token and USDC amounts are tracked to 6 decimals, as on-chain.
+const AMOUNT_DECIMALS: u32 = 6;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaperOrder {
+    pub id: String,
+    pub market: B256,
+    pub token_id: U256,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub matched: Decimal,
+    pub order_type: OrderType,
+    pub status: OrderStatusType,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaperOrder {
+    fn remaining(&self) -> Decimal {
+        self.size - self.matched
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.status == OrderStatusType::Live
+    }
+
+    pub fn to_response(&self, trades: &[PaperTrade]) -> OpenOrderResponse {
+        OpenOrderResponse::builder()
+            .id(self.id.clone())
+            .status(self.status.clone())
+            .owner(Default::default())
+            .maker_address(Address::ZERO)
+            .market(self.market)
+            .asset_id(self.token_id)
+            .side(self.side)
+            .original_size(self.size)
+            .size_matched(self.matched)
+            .price(self.price)
+            .associate_trades(
+                trades
+                    .iter()
+                    .filter(|t| t.order_id == self.id)
+                    .map(|t| t.id.clone())
+                    .collect(),
+            )
+            .outcome(String::new())
+            .created_at(self.created_at)
+            .expiration(DateTime::<Utc>::UNIX_EPOCH)
+            .order_type(self.order_type.clone())
+            .build()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaperTrade {
+    pub id: String,
+    pub order_id: String,
+    pub market: B256,
+    pub token_id: U256,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Whether the order took liquidity when placed or filled while resting.
+    pub taker: bool,
+    pub at: DateTime<Utc>,
+}
+
+impl PaperTrade {
+    pub fn to_response(&self) -> TradeResponse {
+        TradeResponse::builder()
+            .id(self.id.clone())
+            .taker_order_id(if self.taker {
+                self.order_id.clone()
+            } else {
+                String::new()
+            })
+            .market(self.market)
+            .asset_id(self.token_id)
+            .side(self.side)
+            .size(self.size)
+            .fee_rate_bps(Decimal::ZERO)
+            .price(self.price)
+            .status(TradeStatusType::Confirmed)
+            .match_time(self.at)
+            .last_update(self.at)
+            .outcome(String::new())
+            .bucket_index(0)
+            .owner(Default::default())
+            .maker_address(Address::ZERO)
+            .maker_orders(Vec::new())
+            .transaction_hash(B256::ZERO)
+            .trader_side(if self.taker {
+                TraderSide::Taker
+            } else {
+                TraderSide::Maker
+            })
+            .build()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub cash: Decimal,
+    pub positions: BTreeMap<U256, Decimal>,
+    pub orders: Vec<PaperOrder>,
+    pub trades: Vec<PaperTrade>,
+    next_id: u64,
+}
+
+impl Account {
+    pub fn new(cash: Decimal) -> Self {
+        Self {
+            cash,
+            positions: BTreeMap::new(),
+            orders: Vec::new(),
+            trades: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn position(&self, token_id: U256) -> Decimal {
+        self.positions.get(&token_id).copied().unwrap_or_default()
+    }
+
+    /// Cash not reserved by resting buy orders.
+    pub fn available_cash(&self) -> Decimal {
+        let reserved: Decimal = self
+            .orders
+            .iter()
+            .filter(|o| o.is_live() && o.side == Side::Buy)
+            .map(|o| o.price * o.remaining())
+            .sum();
+        self.cash - reserved
+    }
+
+    /// Shares of `token_id` not reserved by resting sell orders.
+    pub fn available_shares(&self, token_id: U256) -> Decimal {
+        let reserved: Decimal = self
+            .orders
+            .iter()
+            .filter(|o| o.is_live() && o.side == Side::Sell && o.token_id == token_id)
+            .map(PaperOrder::remaining)
+            .sum();
+        self.position(token_id) - reserved
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        let id = format!("paper-{prefix}-{}", self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn ensure_funds(
+        &self,
+        side: Side,
+        token_id: U256,
+        cost: Decimal,
+        shares: Decimal,
+    ) -> Result<()> {
+        match side {
+            Side::Sell => {
+                let available = self.available_shares(token_id);
+                anyhow::ensure!(
+                    available >= shares,
+                    "Insufficient paper shares: selling {shares}, {available} available"
+                );
+            }
+            _ => {
+                let available = self.available_cash();
+                anyhow::ensure!(
+                    available >= cost,
+                    "Insufficient paper cash: need {cost}, {available} available"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply one fill of `order` to cash, positions and the trade log,
+    /// returning the shares actually filled.
+    fn fill(
+        &mut self,
+        order: &PaperOrder,
+        price: Decimal,
+        shares: Decimal,
+        taker: bool,
+    ) -> Decimal {
+        let shares = shares.round_dp_with_strategy(AMOUNT_DECIMALS, RoundingStrategy::ToZero);
+        if shares <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let notional = (price * shares).round_dp(AMOUNT_DECIMALS);
+        let position = self.positions.entry(order.token_id).or_default();
+        match order.side {
+            Side::Sell => {
+                *position -= shares;
+                self.cash += notional;
+            }
+            _ => {
+                *position += shares;
+                self.cash -= notional;
+            }
+        }
+        if position.is_zero() {
+            self.positions.remove(&order.token_id);
+        }
+        let id = self.next_id("trade");
+        self.trades.push(PaperTrade {
+            id,
+            order_id: order.id.clone(),
+            market: order.market,
+            token_id: order.token_id,
+            side: order.side,
+            price,
+            size: shares,
+            taker,
+            at: Utc::now(),
+        });
+        shares
+    }
+
+    /// Place a limit order: take whatever the book offers at `price` or
+    /// better, then rest the remainder for GTC/GTD orders.
+    pub fn place_limit(
+        &mut self,
+        book: &OrderBookSummaryResponse,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+        post_only: bool,
+    ) -> Result<PostOrderResponse> {
+        self.ensure_funds(side, book.asset_id, price * size, size)?;
+        let fills = crossing(book, side, price, size);
+        anyhow::ensure!(
+            !post_only || fills.is_empty(),
+            "Post-only order at {price} would cross the book"
+        );
+        let filled: Decimal = fills.iter().map(|(_, s)| s).sum();
+        let mut order = PaperOrder {
+            id: self.next_id("order"),
+            market: book.market,
+            token_id: book.asset_id,
+            side,
+            price,
+            size,
+            matched: Decimal::ZERO,
+            order_type: order_type.clone(),
+            status: OrderStatusType::Live,
+            created_at: Utc::now(),
+        };
+        if order_type == OrderType::FOK && filled < size {
+            order.status = OrderStatusType::Unmatched;
+            let response = post_response(&order, &[]);
+            self.orders.push(order);
+            return Ok(response);
+        }
+        for &(p, s) in &fills {
+            order.matched += self.fill(&order, p, s, true);
+        }
+        if order.matched >= size {
+            order.status = OrderStatusType::Matched;
+        } else if matches!(order_type, OrderType::FOK | OrderType::FAK) {
+            order.status = if order.matched.is_zero() {
+                OrderStatusType::Unmatched
+            } else {
+                OrderStatusType::Matched
+            };
+        }
+        let response = post_response(&order, &fills);
+        self.orders.push(order);
+        Ok(response)
+    }
+
+    /// Fill a market order at the given book levels, already walked by the
+    /// caller (see `estimate_fill`). `complete` is false when the book could
+    /// not absorb the whole amount, which kills a FOK order.
+    pub fn place_market(
+        &mut self,
+        book: &OrderBookSummaryResponse,
+        side: Side,
+        fills: &[(Decimal, Decimal)],
+        complete: bool,
+        order_type: OrderType,
+    ) -> Result<PostOrderResponse> {
+        let shares: Decimal = fills.iter().map(|(_, s)| s).sum();
+        let notional: Decimal = fills.iter().map(|(p, s)| p * s).sum();
+        self.ensure_funds(side, book.asset_id, notional, shares)?;
+        let worst = fills.last().map_or(Decimal::ZERO, |(p, _)| *p);
+        let mut order = PaperOrder {
+            id: self.next_id("order"),
+            market: book.market,
+            token_id: book.asset_id,
+            side,
+            price: worst,
+            size: shares,
+            matched: Decimal::ZERO,
+            order_type: order_type.clone(),
+            status: OrderStatusType::Matched,
+            created_at: Utc::now(),
+        };
+        if fills.is_empty() || (order_type == OrderType::FOK && !complete) {
+            order.status = OrderStatusType::Unmatched;
+            let response = post_response(&order, &[]);
+            self.orders.push(order);
+            return Ok(response);
+        }
+        for &(p, s) in fills {
+            order.matched += self.fill(&order, p, s, true);
+        }
+        let response = post_response(&order, fills);
+        self.orders.push(order);
+        Ok(response)
+    }
+
+    /// Fill resting orders on the book's token that the snapshot now crosses.
+    pub fn match_resting(&mut self, book: &OrderBookSummaryResponse) {
+        let live: Vec<usize> = (0..self.orders.len())
+            .filter(|&i| self.orders[i].is_live() && self.orders[i].token_id == book.asset_id)
+            .collect();
+        for i in live {
+            let order = self.orders[i].clone();
+            let available: Decimal = crossing(book, order.side, order.price, order.remaining())
+                .iter()
+                .map(|(_, s)| s)
+                .sum();
+            if available.is_zero() {
+                continue;
+            }
+            let filled = self.fill(&order, order.price, available, false);
+            let order = &mut self.orders[i];
+            order.matched += filled;
+            if order.matched >= order.size {
+                order.status = OrderStatusType::Matched;
+            }
+        }
+    }
+
+    /// Cancel live orders matching `pred`.
+    pub fn cancel_where(&mut self, pred: impl Fn(&PaperOrder) -> bool) -> CancelOrdersResponse {
+        let mut result = CancelOrdersResponse::default();
+        for order in self.orders.iter_mut().filter(|o| o.is_live() && pred(o)) {
+            order.status = OrderStatusType::Canceled;
+            result.canceled.push(order.id.clone());
+        }
+        result
+    }
+
+    /// Cancel live orders by ID, reporting IDs that are unknown or not live.
+    pub fn cancel_ids(&mut self, ids: &[&str]) -> CancelOrdersResponse {
+        let mut result = self.cancel_where(|o| ids.contains(&o.id.as_str()));
+        for id in ids {
+            if !result.canceled.iter().any(|c| c == id) {
+                result
+                    .not_canceled
+                    .insert((*id).to_string(), "order not found or not live".into());
+            }
+        }
+        result
+    }
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Self::new(Decimal::ONE_THOUSAND)
+    }
+}
+
+/// Opposing liquidity at `price` or better, best level first, up to `size`.
+fn crossing(
+    book: &OrderBookSummaryResponse,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+) -> Vec<(Decimal, Decimal)> {
+    let levels: &[OrderSummary] = match side {
+        Side::Sell => &book.bids,
+        _ => &book.asks,
+    };
+    let mut fills = Vec::new();
+    let mut remaining = size;
+    for level in levels.iter().rev() {
+        let crosses = match side {
+            Side::Sell => level.price >= price,
+            _ => level.price <= price,
+        };
+        if !crosses || remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = level.size.min(remaining);
+        fills.push((level.price, take));
+        remaining -= take;
+    }
+    fills
+}
+
+fn post_response(order: &PaperOrder, fills: &[(Decimal, Decimal)]) -> PostOrderResponse {
+    let shares: Decimal = fills.iter().map(|(_, s)| s).sum();
+    let notional: Decimal = fills.iter().map(|(p, s)| p * s).sum();
+    let (making, taking) = match order.side {
+        Side::Sell => (shares, notional),
+        _ => (notional, shares),
+    };
+    let killed = order.status == OrderStatusType::Unmatched;
+    PostOrderResponse::builder()
+        .maybe_error_msg(killed.then(|| "order couldn't be filled against the book".to_string()))
+        .making_amount(making.round_dp(AMOUNT_DECIMALS))
+        .taking_amount(taking.round_dp(AMOUNT_DECIMALS))
+        .order_id(order.id.clone())
+        .status(order.status.clone())
+        .success(!killed)
+        .build()
+}
+
+pub fn load() -> Result<Account> {
+    load_from(&config::paper_path()?)
+}
+
+pub fn save(account: &Account) -> Result<()> {
+    save_to(&config::paper_path()?, account)
+}
+
+fn load_from(path: &Path) -> Result<Account> {
+    if !path.exists() {
+        return Ok(Account::default());
+    }
+    let data = fs::read_to_string(path).context("Failed to read paper account")?;
+    serde_json::from_str(&data).context("Failed to parse paper account")
+}
+
+fn save_to(path: &Path, account: &Account) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let json = serde_json::to_string_pretty(account)?;
+    fs::write(path, json).context("Failed to write paper account")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polymarket_client_sdk::clob::types::TickSize;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, size: Decimal) -> OrderSummary {
+        OrderSummary::builder().price(price).size(size).build()
+    }
+
+    /// Bids 0.48/0.47 and asks 0.52/0.53, 100 shares each.
+    fn book() -> OrderBookSummaryResponse {
+        OrderBookSummaryResponse::builder()
+            .market(B256::ZERO)
+            .asset_id(U256::from(7))
+            .timestamp(Utc::now())
+            .bids(vec![
+                level(dec!(0.47), dec!(100)),
+                level(dec!(0.48), dec!(100)),
+            ])
+            .asks(vec![
+                level(dec!(0.53), dec!(100)),
+                level(dec!(0.52), dec!(100)),
+            ])
+            .min_order_size(dec!(5))
+            .neg_risk(false)
+            .tick_size(TickSize::Hundredth)
+            .build()
+    }
+
+    #[test]
+    fn crossing_buy_takes_asks_up_to_limit() {
+        let fills = crossing(&book(), Side::Buy, dec!(0.52), dec!(150));
+        assert_eq!(fills, vec![(dec!(0.52), dec!(100))]);
+        let fills = crossing(&book(), Side::Buy, dec!(0.60), dec!(150));
+        assert_eq!(fills, vec![(dec!(0.52), dec!(100)), (dec!(0.53), dec!(50))]);
+    }
+
+    #[test]
+    fn limit_buy_fills_then_rests_remainder() {
+        let mut account = Account::default();
+        let result = account
+            .place_limit(
+                &book(),
+                Side::Buy,
+                dec!(0.52),
+                dec!(150),
+                OrderType::GTC,
+                false,
+            )
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.making_amount, dec!(52));
+        assert_eq!(account.position(U256::from(7)), dec!(100));
+        assert_eq!(account.cash, dec!(948));
+        // 50 shares rest at 0.52, reserving 26 USDC.
+        assert_eq!(account.available_cash(), dec!(922));
+        assert!(account.orders[0].is_live());
+    }
+
+    #[test]
+    fn fok_that_cannot_fill_changes_nothing() {
+        let mut account = Account::default();
+        let result = account
+            .place_limit(
+                &book(),
+                Side::Buy,
+                dec!(0.52),
+                dec!(150),
+                OrderType::FOK,
+                false,
+            )
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(account.cash, Decimal::ONE_THOUSAND);
+        assert!(account.positions.is_empty());
+    }
+
+    #[test]
+    fn post_only_rejects_crossing_order() {
+        let mut account = Account::default();
+        assert!(
+            account
+                .place_limit(
+                    &book(),
+                    Side::Buy,
+                    dec!(0.52),
+                    dec!(10),
+                    OrderType::GTC,
+                    true
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn sell_requires_shares() {
+        let mut account = Account::default();
+        let err = account
+            .place_limit(
+                &book(),
+                Side::Sell,
+                dec!(0.48),
+                dec!(10),
+                OrderType::GTC,
+                false,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Insufficient paper shares"));
+    }
+
+    #[test]
+    fn resting_order_fills_at_its_price_when_crossed() {
+        let mut account = Account::default();
+        account
+            .place_limit(
+                &book(),
+                Side::Buy,
+                dec!(0.50),
+                dec!(20),
+                OrderType::GTC,
+                false,
+            )
+            .unwrap();
+        let mut moved = book();
+        moved.asks.push(level(dec!(0.49), dec!(10)));
+        account.match_resting(&moved);
+
+        // Only the 10 shares offered at 0.49 cross; the rest keeps resting.
+        let order = &account.orders[0];
+        assert_eq!(order.matched, dec!(10));
+        assert!(order.is_live());
+        assert_eq!(account.cash, dec!(995));
+        assert!(
+            account
+                .trades
+                .iter()
+                .all(|t| t.price == dec!(0.50) && !t.taker)
+        );
+    }
+
+    #[test]
+    fn market_buy_applies_walked_fills() {
+        let mut account = Account::default();
+        let fills = [(dec!(0.52), dec!(100)), (dec!(0.53), dec!(10))];
+        let result = account
+            .place_market(&book(), Side::Buy, &fills, true, OrderType::FAK)
+            .unwrap();
+        assert_eq!(result.status, OrderStatusType::Matched);
+        assert_eq!(result.taking_amount, dec!(110));
+        assert_eq!(account.cash, dec!(942.7));
+        assert_eq!(account.position(U256::from(7)), dec!(110));
+        assert!(!account.orders[0].is_live());
+    }
+
+    #[test]
+    fn cancel_ids_reports_unknown() {
+        let mut account = Account::default();
+        account
+            .place_limit(
+                &book(),
+                Side::Buy,
+                dec!(0.40),
+                dec!(10),
+                OrderType::GTC,
+                false,
+            )
+            .unwrap();
+        let id = account.orders[0].id.clone();
+        let result = account.cancel_ids(&[&id, "nope"]);
+        assert_eq!(result.canceled, vec![id]);
+        assert!(result.not_canceled.contains_key("nope"));
+        assert_eq!(account.available_cash(), Decimal::ONE_THOUSAND);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("polymarket-paper-test-{}.json", std::process::id()));
+        let mut account = Account::new(dec!(250));
+        account
+            .place_limit(
+                &book(),
+                Side::Buy,
+                dec!(0.52),
+                dec!(10),
+                OrderType::GTC,
+                false,
+            )
+            .unwrap();
+        save_to(&path, &account).unwrap();
+        let loaded = load_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.cash, dec!(244.8));
+        assert_eq!(loaded.position(U256::from(7)), dec!(10));
+        assert_eq!(loaded.trades.len(), 1);
+    }
+}
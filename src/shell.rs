@@ -47,7 +47,9 @@ pub async fn run_shell() {
                 match crate::Cli::try_parse_from(&full_args) {
                     Ok(cli) => {
                         let output = cli.output;
-                        if let Err(e) = crate::run(cli).await {
+                        if let Err(e) = crate::run(cli).await
+                            && !e.is::<crate::commands::ExitStatus>()
+                        {
                             match output {
                                 OutputFormat::Json => {
                                     println!("{}", serde_json::json!({"error": e.to_string()}));
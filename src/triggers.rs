@@ -0,0 +1,207 @@
+//! Locally persisted stop-loss / take-profit triggers.
+//!
+//! The CLOB has no native stop orders, so pending triggers live in
+//! `~/.config/polymarket/triggers.json` and are checked by `clob watch-triggers`.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::types::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+/// Which way the price has to cross for a trigger to fire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Fire when the price falls to or below the trigger price
+    Below,
+    /// Fire when the price rises to or above the trigger price
+    Above,
+}
+
+/// Which price a trigger watches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum PriceSource {
+    Midpoint,
+    LastTrade,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Trigger {
+    pub id: u64,
+    pub token_id: String,
+    pub side: Side,
+    pub direction: Direction,
+    pub trigger_price: Decimal,
+    pub size: Decimal,
+    /// Limit price for the order sent on trigger; `None` sends a market order.
+    pub limit_price: Option<Decimal>,
+    pub source: PriceSource,
+    pub created_at: String,
+}
+
+impl Trigger {
+    pub fn is_crossed(&self, price: Decimal) -> bool {
+        match self.direction {
+            Direction::Below => price <= self.trigger_price,
+            Direction::Above => price >= self.trigger_price,
+        }
+    }
+}
+
+pub fn load() -> Result<Vec<Trigger>> {
+    load_from(&config::triggers_path()?)
+}
+
+/// Append a new trigger, assigning it the next free ID.
+pub fn add(trigger: Trigger) -> Result<Trigger> {
+    add_to(&config::triggers_path()?, trigger)
+}
+
+/// Remove a trigger by ID. Returns `false` if it was not pending, so of
+/// several watchers racing to fire the same trigger only one sees `true`.
+pub fn remove(id: u64) -> Result<bool> {
+    remove_from(&config::triggers_path()?, id)
+}
+
+fn add_to(path: &Path, mut trigger: Trigger) -> Result<Trigger> {
+    update(path, |triggers| {
+        trigger.id = triggers.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+        triggers.push(trigger.clone());
+        true
+    })?;
+    Ok(trigger)
+}
+
+fn remove_from(path: &Path, id: u64) -> Result<bool> {
+    update(path, |triggers| {
+        let before = triggers.len();
+        triggers.retain(|t| t.id != id);
+        triggers.len() != before
+    })
+}
+
+/// Loads, mutates and saves the triggers file under an exclusive lock so
+/// concurrent watchers and edits don't lose updates. `f` returns whether it
+/// changed anything, which is passed back to the caller.
+fn update(path: &Path, f: impl FnOnce(&mut Vec<Trigger>) -> bool) -> Result<bool> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("lock"))
+        .context("Failed to open triggers lock file")?;
+    lock.lock().context("Failed to lock triggers file")?;
+
+    let mut triggers = load_from(path)?;
+    let changed = f(&mut triggers);
+    if changed {
+        save_to(path, &triggers)?;
+    }
+    Ok(changed)
+}
+
+fn load_from(path: &Path) -> Result<Vec<Trigger>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(path).context("Failed to read triggers file")?;
+    serde_json::from_str(&data).context("Failed to parse triggers file")
+}
+
+fn save_to(path: &Path, triggers: &[Trigger]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let json = serde_json::to_string_pretty(triggers)?;
+    // Write then rename so readers never see a half-written file.
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).context("Failed to write triggers file")?;
+    fs::rename(&tmp, path).context("Failed to write triggers file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn trigger(direction: Direction) -> Trigger {
+        Trigger {
+            id: 1,
+            token_id: "123".into(),
+            side: Side::Sell,
+            direction,
+            trigger_price: dec!(0.30),
+            size: dec!(100),
+            limit_price: Some(dec!(0.28)),
+            source: PriceSource::Midpoint,
+            created_at: "2026-01-01T00:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn below_fires_at_or_under_trigger() {
+        let t = trigger(Direction::Below);
+        assert!(t.is_crossed(dec!(0.30)));
+        assert!(t.is_crossed(dec!(0.25)));
+        assert!(!t.is_crossed(dec!(0.31)));
+    }
+
+    #[test]
+    fn above_fires_at_or_over_trigger() {
+        let t = trigger(Direction::Above);
+        assert!(t.is_crossed(dec!(0.30)));
+        assert!(t.is_crossed(dec!(0.45)));
+        assert!(!t.is_crossed(dec!(0.29)));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "polymarket-triggers-test-{}.json",
+            std::process::id()
+        ));
+        save_to(&path, &[trigger(Direction::Below)]).unwrap();
+        let loaded = load_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].side, Side::Sell);
+        assert_eq!(loaded[0].direction, Direction::Below);
+        assert_eq!(loaded[0].limit_price, Some(dec!(0.28)));
+    }
+
+    #[test]
+    fn remove_reports_only_the_first_caller() {
+        let path = std::env::temp_dir().join(format!(
+            "polymarket-triggers-remove-{}.json",
+            std::process::id()
+        ));
+        let first = add_to(&path, trigger(Direction::Below)).unwrap();
+        let second = add_to(&path, trigger(Direction::Above)).unwrap();
+        assert_eq!((first.id, second.id), (1, 2));
+
+        assert!(remove_from(&path, first.id).unwrap());
+        assert!(!remove_from(&path, first.id).unwrap());
+        let left = load_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(path.with_extension("lock"));
+
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, second.id);
+    }
+
+    #[test]
+    fn load_missing_file_is_empty() {
+        let path = std::env::temp_dir().join("polymarket-triggers-does-not-exist.json");
+        assert!(load_from(&path).unwrap().is_empty());
+    }
+}
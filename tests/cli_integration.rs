@@ -318,6 +318,725 @@ fn clob_price_requires_token() {
     polymarket().args(["clob", "price"]).assert().failure();
 }
 
+#[test]
+fn clob_ladder_requires_range_and_size() {
+    polymarket()
+        .args(["clob", "ladder", "--token", "1", "--side", "buy"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_ladder_rejects_unknown_distribution() {
+    polymarket()
+        .args([
+            "clob",
+            "ladder",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--from",
+            "0.40",
+            "--to",
+            "0.48",
+            "--levels",
+            "5",
+            "--total-size",
+            "500",
+            "--distribution",
+            "exponential",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn clob_replace_requires_order_id() {
+    polymarket()
+        .args(["clob", "replace", "--price", "0.5"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_replace_requires_a_change() {
+    polymarket()
+        .args(["clob", "replace", "0xabc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to change"));
+}
+
+#[test]
+fn clob_twap_requires_duration() {
+    polymarket()
+        .args([
+            "clob", "twap", "--token", "1", "--side", "buy", "--total", "100",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_twap_rejects_bad_duration() {
+    polymarket()
+        .args([
+            "clob",
+            "twap",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--total",
+            "100",
+            "--duration",
+            "soon",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+}
+
+#[test]
+fn clob_iceberg_requires_visible() {
+    polymarket()
+        .args([
+            "clob", "iceberg", "--token", "1", "--side", "sell", "--price", "0.5", "--total", "100",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_stop_triggers_persist_between_runs() {
+    let home = std::env::temp_dir().join(format!("polymarket-stop-test-{}", std::process::id()));
+    let run = |args: &[&str]| {
+        let mut cmd = polymarket();
+        cmd.env("HOME", &home).args(args);
+        cmd
+    };
+
+    run(&["clob", "triggers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No pending triggers"));
+    run(&[
+        "clob",
+        "stop",
+        "--token",
+        "123",
+        "--side",
+        "sell",
+        "--trigger-price",
+        "0.30",
+        "--size",
+        "100",
+        "--no-watch",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Trigger #1 saved"));
+    run(&["-o", "json", "clob", "triggers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"direction\": \"below\""));
+    run(&["clob", "cancel-trigger", "1"]).assert().success();
+    run(&["clob", "cancel-trigger", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No pending trigger"));
+
+    std::fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn clob_market_order_rejects_max_price_on_sell() {
+    polymarket()
+        .args([
+            "clob",
+            "market-order",
+            "--token",
+            "1",
+            "--side",
+            "sell",
+            "--amount",
+            "10",
+            "--max-price",
+            "0.5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only applies to buys"));
+}
+
+#[test]
+fn clob_quote_requires_amount() {
+    polymarket()
+        .args(["clob", "quote", "--token", "1", "--side", "buy"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_quote_rejects_unknown_unit() {
+    polymarket()
+        .args([
+            "clob", "quote", "--token", "1", "--side", "buy", "--amount", "250", "--unit", "eth",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn clob_price_requires_token_or_market() {
+    polymarket()
+        .args(["clob", "price", "--side", "buy"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_market_requires_outcome() {
+    polymarket()
+        .args(["clob", "book", "--market", "will-it-rain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--outcome"));
+}
+
+#[test]
+fn clob_token_conflicts_with_market() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--market",
+            "will-it-rain",
+            "--outcome",
+            "Yes",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn clob_heartbeat_requires_touch_file() {
+    polymarket()
+        .args(["clob", "heartbeat", "--timeout", "30s"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--touch-file"));
+}
+
+#[test]
+fn clob_make_requires_max_inventory() {
+    polymarket()
+        .args([
+            "clob", "make", "--token", "1", "--spread", "2t", "--size", "100",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-inventory"));
+}
+
+#[test]
+fn clob_close_requires_limit_or_market() {
+    polymarket()
+        .args(["clob", "close", "--token", "1"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_close_limit_conflicts_with_market() {
+    polymarket()
+        .args([
+            "clob", "close", "--token", "1", "--limit", "0.5", "--market",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn clob_flatten_requires_scope() {
+    polymarket()
+        .args(["clob", "flatten"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--event"));
+}
+
+#[test]
+fn clob_flatten_scopes_are_exclusive() {
+    polymarket()
+        .args(["clob", "flatten", "--all", "--tag", "politics"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn clob_flatten_json_requires_force_or_dry_run() {
+    polymarket()
+        .args(["-o", "json", "clob", "flatten", "--all"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "--force or --dry-run is required with -o json",
+        ));
+}
+
+#[test]
+fn clob_arb_complement_requires_market_and_size() {
+    polymarket()
+        .args(["clob", "arb-complement", "--market", "some-market"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-size"));
+}
+
+#[test]
+fn clob_arb_complement_rejects_bad_size_before_network() {
+    polymarket()
+        .args([
+            "clob",
+            "arb-complement",
+            "--market",
+            "some-market",
+            "--max-size",
+            "0",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-size must be positive"));
+}
+
+#[test]
+fn clob_cancel_where_requires_a_filter() {
+    polymarket()
+        .args(["clob", "cancel-where", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--side"));
+}
+
+#[test]
+fn clob_cancel_where_rejects_bad_duration() {
+    polymarket()
+        .args(["clob", "cancel-where", "--older-than", "soon", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+}
+
+#[test]
+fn clob_wait_rejects_unknown_state() {
+    polymarket()
+        .args(["clob", "wait", "0xabc", "--until", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn clob_create_order_wait_timeout_requires_wait() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+            "--wait-timeout",
+            "1m",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--wait"));
+}
+
+#[test]
+fn clob_journal_empty_then_show_missing() {
+    let home = std::env::temp_dir().join(format!("polymarket-journal-test-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["clob", "journal", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No journal entries"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["clob", "journal", "show", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No journal entry #1"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["clob", "journal", "export", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout("entry,at,action,command,order_ids,request,response\n");
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn clob_create_order_rejects_unknown_round_mode() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+            "--round",
+            "sideways",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn paper_account_reset_and_balance() {
+    let home = std::env::temp_dir().join(format!("polymarket-paper-test-{}", std::process::id()));
+    let run = |args: &[&str]| {
+        let mut cmd = polymarket();
+        cmd.env("HOME", &home).args(args);
+        cmd
+    };
+
+    run(&["clob", "paper-reset", "--cash", "250"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("250 USDC"));
+    run(&[
+        "--paper",
+        "-o",
+        "json",
+        "clob",
+        "balance",
+        "--asset-type",
+        "collateral",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("\"balance\": \"250\""));
+    run(&["--paper", "clob", "cancel-all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No orders to cancel"));
+    run(&["--paper", "clob", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No open orders"));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn paper_rejects_unsupported_commands() {
+    polymarket()
+        .args(["--paper", "clob", "cancel-where", "--side", "buy"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--paper supports"));
+}
+
+#[test]
+fn paper_rejects_account_commands_before_any_request() {
+    // With a key set, a live run would reach the API; --paper must stop first.
+    polymarket()
+        .env(
+            "POLYMARKET_PRIVATE_KEY",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .args(["clob", "delete-api-key", "--paper"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--paper supports"));
+}
+
+#[test]
+fn scan_neg_risk_rejects_bad_min_edge() {
+    polymarket()
+        .args(["scan", "neg-risk", "--min-edge", "lots"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid edge"));
+}
+
+#[test]
+fn list_commands_accept_pagination_flags() {
+    for args in [
+        &["markets", "list", "--help"][..],
+        &["data", "trades", "--help"],
+        &["clob", "orders", "--help"],
+    ] {
+        polymarket()
+            .args(args)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--all").and(predicate::str::contains("--max-items")));
+    }
+}
+
+#[test]
+fn all_pages_rejects_zero_limit() {
+    polymarket()
+        .args(["events", "list", "--all", "--limit", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--limit must be positive"));
+}
+
+#[test]
+fn clob_trades_export_help_shows_range_and_format() {
+    polymarket()
+        .args(["clob", "trades", "export", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("--since")
+                .and(predicate::str::contains("--until"))
+                .and(predicate::str::contains("csv")),
+        );
+}
+
+#[test]
+fn clob_trades_export_rejects_inverted_range() {
+    polymarket()
+        .args([
+            "clob",
+            "trades",
+            "export",
+            "--since",
+            "2026-10-01",
+            "--until",
+            "2026-09-01",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--since must be before --until"));
+}
+
+#[test]
+fn clob_balances_help_shows_refresh_flags() {
+    polymarket()
+        .args(["clob", "balances", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--dry-run").and(predicate::str::contains("--force")));
+}
+
+#[test]
+fn clob_reward_check_rejects_price_outside_unit_range() {
+    polymarket()
+        .args([
+            "clob",
+            "reward-check",
+            "--token",
+            "123",
+            "--side",
+            "buy",
+            "--price",
+            "1.2",
+            "--size",
+            "50",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--price must be between 0 and 1"));
+}
+
+#[test]
+fn clob_earnings_range_rejects_reversed_dates() {
+    polymarket()
+        .args([
+            "clob",
+            "earnings",
+            "--from",
+            "2024-06-05",
+            "--to",
+            "2024-06-01",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--from must not be after --to"));
+}
+
+#[test]
+fn clob_rewards_requires_date_or_range() {
+    polymarket().args(["clob", "rewards"]).assert().failure();
+    polymarket()
+        .args([
+            "clob",
+            "rewards",
+            "--date",
+            "2024-06-01",
+            "--from",
+            "2024-06-01",
+            "--to",
+            "2024-06-02",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn clob_rewards_range_rejects_paging_flags() {
+    for (command, flag) in [
+        ("rewards", ["--cursor", "abc"].as_slice()),
+        ("rewards", ["--all"].as_slice()),
+        ("rewards", ["--max-items", "5"].as_slice()),
+        ("earnings-markets", ["--cursor", "abc"].as_slice()),
+    ] {
+        polymarket()
+            .args([
+                "clob",
+                command,
+                "--from",
+                "2024-06-01",
+                "--to",
+                "2024-06-02",
+            ])
+            .args(flag)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn clob_stream_rejects_bad_token_before_connecting() {
+    polymarket()
+        .args(["clob", "stream", "--tokens", "123,abc"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn clob_stop_rejects_out_of_range_trigger_before_saving() {
+    polymarket()
+        .args([
+            "clob",
+            "stop",
+            "--token",
+            "1",
+            "--side",
+            "sell",
+            "--trigger-price",
+            "1.5",
+            "--size",
+            "10",
+            "--no-watch",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--trigger-price must be between 0 and 1",
+        ));
+}
+
+#[test]
+fn clob_close_takes_outcome_market_and_outcome() {
+    polymarket()
+        .args([
+            "clob",
+            "close",
+            "--outcome-market",
+            "some-slug",
+            "--market",
+            "--token",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .args(["clob", "close", "--outcome-market", "some-slug", "--market"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--outcome"));
+}
+
+#[test]
+fn paper_rejects_non_clob_commands() {
+    polymarket()
+        .args([
+            "--paper",
+            "ctf",
+            "split",
+            "--condition",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--amount",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--paper only applies to clob commands",
+        ));
+    polymarket()
+        .args(["approve", "set", "--paper"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--paper only applies to clob commands",
+        ));
+}
+
+#[test]
+fn clob_balances_json_requires_force_or_dry_run() {
+    polymarket()
+        .args(["-o", "json", "clob", "balances"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("--force or --dry-run"));
+}
+
+#[test]
+fn clob_cancel_where_json_requires_force_or_dry_run() {
+    polymarket()
+        .args(["-o", "json", "clob", "cancel-where", "--side", "buy"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "--force or --dry-run is required with -o json",
+        ));
+}
+
+#[test]
+fn clob_wait_rejects_overflowing_timeout() {
+    polymarket()
+        .args(["clob", "wait", "0xabc", "--timeout", "999999999999999999d"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Duration too large"));
+}
+
 #[test]
 fn data_positions_requires_address() {
     polymarket().args(["data", "positions"]).assert().failure();
@@ -343,12 +1062,54 @@ fn ctf_help_lists_subcommands() {
                 .and(predicate::str::contains("merge"))
                 .and(predicate::str::contains("redeem"))
                 .and(predicate::str::contains("redeem-neg-risk"))
+                .and(predicate::str::contains("convert-neg-risk"))
                 .and(predicate::str::contains("condition-id"))
                 .and(predicate::str::contains("collection-id"))
                 .and(predicate::str::contains("position-id")),
         );
 }
 
+#[test]
+fn ctf_convert_neg_risk_rejects_bad_index_set_before_network() {
+    polymarket()
+        .args([
+            "ctf",
+            "convert-neg-risk",
+            "--market-id",
+            "0x0000000000000000000000000000000000000000000000000000000000000100",
+            "--index-set",
+            "0,x",
+            "--amount",
+            "10",
+            "--dry-run",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid question index"));
+}
+
+#[test]
+fn ctf_convert_neg_risk_json_requires_force_or_dry_run() {
+    polymarket()
+        .args([
+            "-o",
+            "json",
+            "ctf",
+            "convert-neg-risk",
+            "--market-id",
+            "0x0000000000000000000000000000000000000000000000000000000000000100",
+            "--index-set",
+            "0",
+            "--amount",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "--force or --dry-run is required with -o json",
+        ));
+}
+
 #[test]
 fn ctf_collection_id_requires_condition_and_index_set() {
     polymarket()